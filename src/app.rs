@@ -20,14 +20,17 @@ use crate::mode_state::{
 use crate::search_state::SearchState;
 use crate::terminal::{
     LastMouseClick, MouseClickClass, SelectionAutoScroll, SelectionEndpoint,
-    SelectionScrollDirection, TerminalPoint, compute_selection_for_view, make_selection_endpoint,
+    SelectionScrollDirection, SelectionShape, TerminalPoint, compute_selection_for_view,
+    make_selection_endpoint,
 };
 use crate::transfer::{ScpProgress, ScpResult};
 use crate::ui::{
-    ConnectionForm, DeleteConfirmationConfig, TerminalState, draw_connecting_popup,
-    draw_connection_form_popup, draw_connection_list, draw_delete_confirmation_popup,
-    draw_error_popup, draw_file_explorer, draw_info_popup, draw_port_forwarding_form_popup,
-    draw_port_forwarding_list, draw_scp_progress_popup, draw_terminal, rect_with_top_margin,
+    ConnectionForm, DeleteConfirmationConfig, TerminalState, draw_close_session_popup,
+    draw_connecting_popup, draw_connection_form_popup, draw_connection_list,
+    draw_delete_confirmation_popup, draw_error_popup, draw_export_menu, draw_file_explorer,
+    draw_info_popup, draw_paste_confirmation_popup, draw_port_forwarding_form_popup,
+    draw_port_forwarding_list, draw_scp_progress_popup, draw_snippet_palette, draw_terminal,
+    draw_url_picker, draw_zmodem_picker, rect_with_top_margin, terminal_text_area,
 };
 
 /// Enum to track where to return after SCP operations
@@ -158,13 +161,13 @@ pub enum CopyDirection {
 pub enum ScpReturnMode {
     #[allow(dead_code)]
     ConnectionList { current_selected: usize },
-    #[allow(dead_code)]
     Connected {
-        name: String,
+        connection: Connection,
         client: SshSession,
         state: Arc<Mutex<TerminalState>>,
         current_selected: usize,
         cancel_token: tokio_util::sync::CancellationToken,
+        return_to_explorer: Option<Box<ScpReturnMode>>,
     },
     FileExplorer {
         connection_name: String,
@@ -204,6 +207,39 @@ pub enum ConnectingSource {
     },
 }
 
+const RECONNECT_MAX_ATTEMPTS: u32 = 10;
+const RECONNECT_MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(30);
+/// How long a notice stays in the terminal title bar
+const TERMINAL_NOTICE_DURATION: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Progress of an automatic reconnect after an interactive session dropped.
+/// The frozen terminal stays on screen until a new session replaces the client.
+pub struct ReconnectState {
+    pub attempt: u32,
+    pub next_attempt_at: Instant,
+    pub cancel_token: tokio_util::sync::CancellationToken,
+    pub receiver: Option<mpsc::Receiver<Result<SshSession>>>,
+}
+
+impl ReconnectState {
+    fn new() -> Self {
+        Self {
+            attempt: 1,
+            next_attempt_at: Instant::now(),
+            cancel_token: tokio_util::sync::CancellationToken::new(),
+            receiver: None,
+        }
+    }
+
+    /// Schedule the next attempt with exponential backoff (1s, 2s, 4s, ... capped at 30s)
+    fn schedule_retry(&mut self) {
+        let backoff = std::time::Duration::from_secs(1 << (self.attempt - 1).min(5));
+        self.attempt += 1;
+        self.next_attempt_at = Instant::now() + backoff.min(RECONNECT_MAX_BACKOFF);
+        self.receiver = None;
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 pub enum AppMode {
     ConnectionList(ListSelectionState),
@@ -227,11 +263,20 @@ pub enum AppMode {
     },
     Connected {
         name: String,
+        connection: Connection,
         client: SshSession,
         state: Arc<Mutex<TerminalState>>,
         current_selected: usize,
         cancel_token: tokio_util::sync::CancellationToken, // Token to cancel the read task
         return_to_explorer: Option<ScpReturnMode>,
+        reconnect: Option<ReconnectState>, // Set while an auto-reconnect is in progress
+        snippet_palette: Option<crate::SnippetPalette>, // Set while the snippet palette is open
+        url_picker: Option<crate::terminal::UrlPicker>, // Set while the URL picker is open
+        export_menu: Option<crate::terminal::ExportMenu>, // Set while the export menu is open
+        copy_mode: Option<crate::terminal::CopyMode>, // Set while in keyboard copy mode
+        pending_paste: Option<String>,     // Multi-line paste awaiting confirmation
+        close_prompt: bool, // Asking whether to detach from or kill a persistent session
+        zmodem_picker: Option<crate::terminal::ZmodemPicker>, // Set while sz or rz waits for a local file
     },
     ScpProgress {
         progress: ScpProgress,
@@ -292,6 +337,7 @@ pub struct App<B: Backend + Write> {
     pub info: Option<String>,
     pub config: ConfigManager,
     pub port_forwarding_runtime: crate::async_ssh_client::PortForwardingRuntime,
+    pub session_pool: crate::async_ssh_client::SessionPool,
     terminal: Terminal<B>,
     needs_redraw: bool, // Track if UI needs redrawing
     event_tx: Option<tokio::sync::mpsc::Sender<AppEvent>>, // Event sender for SSH disconnect
@@ -304,22 +350,32 @@ pub struct App<B: Backend + Write> {
     selection_auto_scroll: Option<SelectionAutoScroll>,
     last_click: Option<LastMouseClick>,
     selection_force_nonempty: bool,
+    selection_shape: SelectionShape,
     clipboard: Option<Clipboard>,
+    terminal_notice: Option<(String, Instant)>, // Title bar notice and when it expires
+    bell_flash_until: Option<Instant>,          // When the visual bell's pending repaint is due
+    host_focused: bool, // Whether the terminal window running termirs has focus
 }
 
 impl<B: Backend + Write> Drop for App<B> {
     fn drop(&mut self) {
-        use crossterm::event::{DisableBracketedPaste, DisableMouseCapture};
+        use crossterm::event::{DisableBracketedPaste, DisableFocusChange, DisableMouseCapture};
         use crossterm::execute;
         use crossterm::terminal::{LeaveAlternateScreen, disable_raw_mode};
 
         disable_raw_mode().ok();
         #[cfg(target_os = "windows")]
-        execute!(self.terminal.backend_mut(), LeaveAlternateScreen,).ok();
+        execute!(
+            self.terminal.backend_mut(),
+            DisableFocusChange,
+            LeaveAlternateScreen,
+        )
+        .ok();
         #[cfg(not(target_os = "windows"))]
         execute!(
             self.terminal.backend_mut(),
             DisableBracketedPaste,
+            DisableFocusChange,
             DisableMouseCapture,
             LeaveAlternateScreen,
         )
@@ -329,12 +385,16 @@ impl<B: Backend + Write> Drop for App<B> {
 
 impl<B: Backend + Write> App<B> {
     pub fn new(terminal: Terminal<B>) -> Result<Self> {
+        let session_pool = crate::async_ssh_client::SessionPool::new();
         Ok(Self {
             mode: AppMode::ConnectionList(ListSelectionState::new(0)),
             error: None,
             info: None,
             config: ConfigManager::new()?,
-            port_forwarding_runtime: crate::async_ssh_client::PortForwardingRuntime::new(),
+            port_forwarding_runtime: crate::async_ssh_client::PortForwardingRuntime::with_pool(
+                session_pool.clone(),
+            ),
+            session_pool,
             terminal,
             needs_redraw: true,    // Initial redraw needed
             event_tx: None,        // Will be set later
@@ -347,7 +407,11 @@ impl<B: Backend + Write> App<B> {
             selection_auto_scroll: None,
             last_click: None,
             selection_force_nonempty: false,
+            selection_shape: SelectionShape::Linear,
             clipboard: Clipboard::new().ok(),
+            terminal_notice: None,
+            bell_flash_until: None,
+            host_focused: true,
         })
     }
 
@@ -378,6 +442,14 @@ impl<B: Backend + Write> App<B> {
                 )
             })?;
 
+        // Lets "notify when idle" tell whether the window is in the background
+        self.terminal
+            .backend_mut()
+            .execute(crossterm::event::EnableFocusChange)
+            .inspect_err(|e| {
+                tracing::error!("Error executing EnableFocusChange terminal command: {}", e)
+            })?;
+
         #[cfg(not(target_os = "windows"))]
         self.terminal
             .backend_mut()
@@ -397,6 +469,7 @@ impl<B: Backend + Write> App<B> {
         self.selection_auto_scroll = None;
         self.last_click = None;
         self.selection_force_nonempty = false;
+        self.selection_shape = SelectionShape::Linear;
 
         Ok(())
     }
@@ -456,16 +529,25 @@ impl<B: Backend + Write> App<B> {
     fn mode_needs_ticker(&self) -> bool {
         matches!(
             self.mode,
-            AppMode::ScpProgress { .. } | AppMode::Connecting { .. }
+            AppMode::ScpProgress { .. }
+                | AppMode::Connecting { .. }
+                | AppMode::Connected {
+                    reconnect: Some(_),
+                    ..
+                }
         )
     }
 
     /// Get the terminal size for SSH PTY (cols, rows), accounting for borders
     pub fn ssh_terminal_size(&self) -> Result<(u16, u16)> {
         let size = self.terminal.size()?;
-        // Account for top border (1 row for title bar)
+        // Account for top border (1 row for title bar) and the prompt gutter
         let rows = size.height.saturating_sub(1);
-        let cols = size.width;
+        let cols = if self.config.prompt_gutter() && size.width > 1 {
+            size.width - 1
+        } else {
+            size.width
+        };
         Ok((cols, rows))
     }
 
@@ -505,6 +587,7 @@ impl<B: Backend + Write> App<B> {
             self.selection_dragging = false;
             self.selection_auto_scroll = None;
             self.selection_force_nonempty = false;
+            self.selection_shape = SelectionShape::Linear;
             self.mark_redraw();
         }
     }
@@ -518,9 +601,40 @@ impl<B: Backend + Write> App<B> {
         self.selection_tail = Some(point);
         self.selection_dragging = true;
         self.selection_force_nonempty = false;
+        self.selection_shape = SelectionShape::Linear;
         self.mark_redraw();
     }
 
+    /// Select from `anchor` to `tail` in one go, as copy mode does
+    pub fn set_selection(
+        &mut self,
+        anchor: SelectionEndpoint,
+        tail: SelectionEndpoint,
+        shape: SelectionShape,
+    ) {
+        self.selection_anchor = Some(anchor);
+        self.selection_tail = Some(tail);
+        self.selection_dragging = false;
+        self.selection_force_nonempty = true;
+        self.selection_shape = shape;
+        self.mark_redraw();
+    }
+
+    /// Show copy mode's visual selection; without one the selection is cleared
+    pub fn sync_copy_mode_selection(&mut self) {
+        let selection = match &self.mode {
+            AppMode::Connected {
+                copy_mode: Some(copy_mode),
+                ..
+            } => copy_mode.selection(),
+            _ => None,
+        };
+        match selection {
+            Some((anchor, tail, shape)) => self.set_selection(anchor, tail, shape),
+            None => self.clear_selection(),
+        }
+    }
+
     pub fn update_selection(&mut self, point: SelectionEndpoint) {
         if self.selection_anchor.is_some() {
             self.selection_tail = Some(point);
@@ -549,9 +663,9 @@ impl<B: Backend + Write> App<B> {
         let (anchor, tail) = self.selection_endpoints()?;
         crate::terminal::selection::collect_selection_text(
             &state.terminal,
-            state.scrollback(),
             anchor,
             tail,
+            self.selection_shape,
         )
     }
 
@@ -650,6 +764,96 @@ impl<B: Backend + Write> App<B> {
         }
     }
 
+    /// Open a link from the terminal in the local browser
+    pub fn open_link(&mut self, url: &str) {
+        match crate::utils::open_url(url) {
+            Ok(()) => self.show_terminal_notice(format!("opened {url}")),
+            Err(e) => self.set_error(e),
+        }
+    }
+
+    /// Export the connected terminal as chosen in the export menu. Uploads
+    /// run in the background and report back with `AppEvent::ExportUploaded`.
+    pub async fn export_terminal(&mut self, menu: &crate::terminal::ExportMenu) {
+        use crate::terminal::ExportDestination;
+
+        let AppMode::Connected {
+            connection, state, ..
+        } = &self.mode
+        else {
+            return;
+        };
+        let connection = connection.clone();
+        let title = format!("{} ({})", connection.display_name, connection.host);
+        let text = state.lock().await.export(menu.scope, menu.format, &title);
+
+        if menu.destination == ExportDestination::Clipboard {
+            self.copy_text_to_clipboard(text);
+            return;
+        }
+        if menu.destination == ExportDestination::Remote && !connection.kind.is_ssh() {
+            return self.set_error(AppError::ValidationError(format!(
+                "Uploading needs SSH, but {} is a {} connection",
+                connection.display_name,
+                connection.kind.label()
+            )));
+        }
+
+        let path = match crate::terminal::export_path(
+            &connection.display_name,
+            menu.format,
+            chrono::Local::now(),
+        ) {
+            Ok(path) => path,
+            Err(e) => return self.set_error(e),
+        };
+        if let Err(e) = std::fs::write(&path, text) {
+            return self.set_error(e.into());
+        }
+        if menu.destination == ExportDestination::File {
+            self.set_info(format!("Saved to {}", path.display()));
+            return;
+        }
+
+        // A relative remote path lands in the home directory
+        let remote_path = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let pool = self.session_pool.clone();
+        let event_tx = self.event_tx.clone();
+        self.set_info(format!(
+            "Saved to {}, uploading to {}...",
+            path.display(),
+            connection.host
+        ));
+        tokio::spawn(async move {
+            let result = async {
+                let (channel, _lease, _) = pool
+                    .open_session_channel(
+                        &connection,
+                        None,
+                        &tokio_util::sync::CancellationToken::new(),
+                    )
+                    .await?;
+                SshSession::sftp_send_file(
+                    Some(channel),
+                    &connection,
+                    &path.to_string_lossy(),
+                    &remote_path,
+                    0,
+                    None,
+                )
+                .await?;
+                Ok::<_, AppError>(format!("{}:~/{remote_path}", connection.host))
+            }
+            .await;
+            if let Some(tx) = event_tx {
+                let _ = tx.send(AppEvent::ExportUploaded(result)).await;
+            }
+        });
+    }
+
     pub fn get_text_from_clipboard(&mut self) -> Option<String> {
         self.clipboard
             .as_mut()
@@ -708,18 +912,25 @@ impl<B: Backend + Write> App<B> {
 
     pub fn go_to_connected(
         &mut self,
-        name: String,
+        connection: Connection,
         client: SshSession,
         state: Arc<Mutex<TerminalState>>,
         current_selected: usize,
         cancel_token: tokio_util::sync::CancellationToken,
     ) {
-        self.go_to_connected_with_return(name, client, state, current_selected, cancel_token, None);
+        self.go_to_connected_with_return(
+            connection,
+            client,
+            state,
+            current_selected,
+            cancel_token,
+            None,
+        );
     }
 
     pub fn go_to_connected_with_return(
         &mut self,
-        name: String,
+        connection: Connection,
         client: SshSession,
         state: Arc<Mutex<TerminalState>>,
         current_selected: usize,
@@ -727,12 +938,21 @@ impl<B: Backend + Write> App<B> {
         return_to_explorer: Option<ScpReturnMode>,
     ) {
         self.mode = AppMode::Connected {
-            name,
+            name: connection.display_name.clone(),
+            connection,
             client,
             state,
             current_selected,
             cancel_token,
             return_to_explorer,
+            reconnect: None,
+            snippet_palette: None,
+            url_picker: None,
+            export_menu: None,
+            copy_mode: None,
+            pending_paste: None,
+            close_prompt: false,
+            zmodem_picker: None,
         };
         self.clear_selection();
         // Stop ticker - terminal updates are now event-driven via TerminalUpdate
@@ -859,7 +1079,8 @@ impl<B: Backend + Write> App<B> {
     pub async fn go_to_file_explorer(&mut self, conn: Connection, return_to: usize) -> Result<()> {
         // For SFTP, we need to create a new session directly since we need both the session and channel
         // We'll use the existing sftp_send_file pattern but adapt it for our needs
-        let (sftp_session, channel, ssh_session) = Self::create_sftp_session(&conn).await?;
+        let (sftp_session, channel, ssh_session) =
+            Self::create_sftp_session(&self.session_pool, &conn).await?;
 
         // Initialize local file explorer
         // Use current directory as it's more reliable than HOME which might be on a slow network mount
@@ -954,10 +1175,8 @@ impl<B: Backend + Write> App<B> {
             .await
             {
                 Ok(local_explorer) => {
-                    // Disconnect old left SSH session before clearing
-                    if let Some(old_session) = left_session.take() {
-                        Self::disconnect_session(&old_session).await;
-                    }
+                    // Release the old left SSH session to the pool
+                    left_session.take();
                     *left_pane = FileExplorerPane::Local;
                     *left_explorer = LeftExplorer::Local(local_explorer);
                     self.needs_redraw = true;
@@ -973,6 +1192,7 @@ impl<B: Backend + Write> App<B> {
 
     /// Switch left pane to an SSH connection
     pub async fn switch_left_pane_to_ssh(&mut self, conn: Connection) {
+        let pool = self.session_pool.clone();
         if let AppMode::FileExplorer {
             left_pane,
             left_explorer,
@@ -997,7 +1217,9 @@ impl<B: Backend + Write> App<B> {
             }
 
             // Switch left pane to SSH connection
-            match Self::setup_left_ssh_pane(&conn, left_pane, left_explorer, left_session).await {
+            match Self::setup_left_ssh_pane(&pool, &conn, left_pane, left_explorer, left_session)
+                .await
+            {
                 Ok(()) => {
                     self.needs_redraw = true;
                 }
@@ -1010,6 +1232,7 @@ impl<B: Backend + Write> App<B> {
 
     /// Switch right pane to a different SSH connection
     pub async fn switch_right_pane_to_ssh(&mut self, conn: Connection) {
+        let pool = self.session_pool.clone();
         if let AppMode::FileExplorer {
             connection_name,
             remote_explorer,
@@ -1040,7 +1263,7 @@ impl<B: Backend + Write> App<B> {
             }
 
             // Create new SFTP session for the right pane
-            match Self::create_sftp_session(&conn).await {
+            match Self::create_sftp_session(&pool, &conn).await {
                 Ok((sftp_session, new_channel, new_ssh_session)) => {
                     let remote_home = match sftp_session.canonicalize(".").await {
                         Ok(h) => h,
@@ -1060,8 +1283,7 @@ impl<B: Backend + Write> App<B> {
                     .await
                     {
                         Ok(new_explorer) => {
-                            // Disconnect old right SSH session before replacing
-                            Self::disconnect_session(ssh_session).await;
+                            // Replacing the handle releases the old right SSH session to the pool
                             *connection_name = conn.display_name.clone();
                             *remote_explorer = new_explorer;
                             *channel = Some(new_channel);
@@ -1087,6 +1309,7 @@ impl<B: Backend + Write> App<B> {
 
     /// Setup left pane to connect to an SSH server
     async fn setup_left_ssh_pane(
+        pool: &crate::async_ssh_client::SessionPool,
         conn: &Connection,
         left_pane: &mut FileExplorerPane,
         left_explorer: &mut LeftExplorer,
@@ -1095,7 +1318,7 @@ impl<B: Backend + Write> App<B> {
         >,
     ) -> Result<()> {
         // Create SFTP session
-        let (sftp_session, _explorer_channel, ssh_session) = Self::create_sftp_session(conn)
+        let (sftp_session, _explorer_channel, ssh_session) = Self::create_sftp_session(pool, conn)
             .await
             .map_err(|e| AppError::SftpError(format!("Failed to create SFTP session: {e}")))?;
 
@@ -1113,11 +1336,6 @@ impl<B: Backend + Write> App<B> {
                     AppError::SftpError(format!("Failed to initialize remote explorer: {e}"))
                 })?;
 
-        // Disconnect old left SSH session before replacing
-        if let Some(old_session) = left_session.take() {
-            Self::disconnect_session(&old_session).await;
-        }
-
         // Update state
         *left_pane = FileExplorerPane::RemoteSsh {
             connection_name: conn.display_name.clone(),
@@ -1129,35 +1347,18 @@ impl<B: Backend + Write> App<B> {
         Ok(())
     }
 
-    /// Send SSH disconnect on a session handle, ignoring errors.
-    async fn disconnect_session(
-        handle: &tokio::sync::Mutex<russh::client::Handle<crate::async_ssh_client::SshClient>>,
-    ) {
-        let guard = handle.lock().await;
-        if !guard.is_closed() {
-            let _ = guard
-                .disconnect(russh::Disconnect::ByApplication, "", "")
-                .await;
-        }
-    }
-
     async fn create_sftp_session(
+        pool: &crate::async_ssh_client::SessionPool,
         conn: &Connection,
     ) -> Result<(
         russh_sftp::client::SftpSession,
         russh::Channel<russh::client::Msg>,
-        Arc<tokio::sync::Mutex<russh::client::Handle<crate::async_ssh_client::SshClient>>>,
+        crate::async_ssh_client::SharedHandle,
     )> {
-        // Create a new SSH session specifically for SFTP
-        let (session, _server_key) = SshSession::new_session_with_timeout(
-            conn,
-            None,
-            &tokio_util::sync::CancellationToken::new(),
-        )
-        .await?;
-
-        // Open a channel for SFTP
-        let channel = session.channel_open_session().await?;
+        // Open the SFTP channel on the pooled SSH session for this connection
+        let (channel, session, _server_key) = pool
+            .open_session_channel(conn, None, &tokio_util::sync::CancellationToken::new())
+            .await?;
         channel.request_subsystem(true, "sftp").await?;
 
         // Create and initialize SFTP session
@@ -1165,12 +1366,9 @@ impl<B: Backend + Write> App<B> {
             .await
             .map_err(|e| AppError::SftpError(format!("SFTP session creation failed: {e}")))?;
 
-        let channel = session.channel_open_session().await?;
+        let channel = session.lock().await.channel_open_session().await?;
         channel.request_subsystem(true, "sftp").await?;
 
-        // Wrap session in Arc<Mutex> for shared access
-        let session = Arc::new(tokio::sync::Mutex::new(session));
-
         Ok((sftp, channel, session))
     }
 
@@ -1240,7 +1438,6 @@ impl<B: Backend + Write> App<B> {
     }
 
     /// Set info and mark for redraw
-    #[allow(dead_code)]
     pub fn set_info(&mut self, info: String) {
         self.info = Some(info);
         self.needs_redraw = true;
@@ -1250,7 +1447,14 @@ impl<B: Backend + Write> App<B> {
         let selection_anchor = self.selection_anchor;
         let selection_tail = self.selection_tail;
         let selection_forced = self.selection_force_nonempty;
+        let selection_shape = self.selection_shape;
+        let notice = self
+            .terminal_notice
+            .as_ref()
+            .filter(|(_, until)| Instant::now() < *until)
+            .map(|(text, _)| text.clone());
         let mut new_viewport = Rect::default();
+        let theme = *self.config.theme();
 
         self.terminal.draw(|f| {
             let size = f.area();
@@ -1258,7 +1462,15 @@ impl<B: Backend + Write> App<B> {
                 AppMode::ConnectionList(state) => {
                     let conns = self.config.connections();
 
-                    draw_connection_list(size, conns, state.selected, &state.search, f, false);
+                    draw_connection_list(
+                        size,
+                        conns,
+                        state.selected,
+                        &state.search,
+                        f,
+                        false,
+                        &theme,
+                    );
                 }
                 AppMode::FormNew {
                     current_selected, ..
@@ -1272,6 +1484,7 @@ impl<B: Backend + Write> App<B> {
                         &SearchState::Off,
                         f,
                         false,
+                        &theme,
                     );
                 }
                 AppMode::FormEdit {
@@ -1286,6 +1499,7 @@ impl<B: Backend + Write> App<B> {
                         &SearchState::Off,
                         f,
                         false,
+                        &theme,
                     );
                 }
                 AppMode::Connecting {
@@ -1304,8 +1518,9 @@ impl<B: Backend + Write> App<B> {
                                 &SearchState::Off,
                                 f,
                                 false,
+                                &theme,
                             );
-                            draw_connection_form_popup(size, form, true, f);
+                            draw_connection_form_popup(size, form, true, f, &theme);
                         }
                         ConnectingSource::FormEdit { form, .. } => {
                             let conns = self.config.connections();
@@ -1316,8 +1531,9 @@ impl<B: Backend + Write> App<B> {
                                 &SearchState::Off,
                                 f,
                                 false,
+                                &theme,
                             );
-                            draw_connection_form_popup(size, form, false, f);
+                            draw_connection_form_popup(size, form, false, f, &theme);
                         }
                         ConnectingSource::ConnectionList { .. } => {
                             let conns = self.config.connections();
@@ -1328,14 +1544,23 @@ impl<B: Backend + Write> App<B> {
                                 &SearchState::Off,
                                 f,
                                 false,
+                                &theme,
                             );
                         }
                     }
                 }
-                AppMode::Connected { name, state, .. } => {
-                    let inner = rect_with_top_margin(size, 1);
-                    new_viewport = inner;
+                AppMode::Connected {
+                    name,
+                    client,
+                    state,
+                    reconnect,
+                    copy_mode,
+                    ..
+                } => {
+                    new_viewport = rect_with_top_margin(size, 1);
                     if let Ok(mut guard) = state.try_lock() {
+                        let inner = terminal_text_area(size, &guard);
+                        new_viewport = inner;
                         if guard.screen_size() != (inner.height, inner.width) {
                             guard.resize(inner.height, inner.width);
                         }
@@ -1345,8 +1570,33 @@ impl<B: Backend + Write> App<B> {
                             &guard,
                             inner.width,
                             selection_forced,
+                            selection_shape,
+                        );
+                        let mut title = format!("Connected to {name}");
+                        // Whether the multiplexer session survived is what
+                        // the host reported when the connection was (re)made
+                        if let Some(persistent) = client.persistent_session() {
+                            title = format!(
+                                "{title} [{} {}]",
+                                persistent.multiplexer.label(),
+                                persistent.status(reconnect.is_some())
+                            );
+                        }
+                        if let Some(remote_title) = guard.window_title() {
+                            title = format!("{title}: {remote_title}");
+                        }
+                        if let Some(notice) = &notice {
+                            title = format!("{title} — {notice}");
+                        }
+                        draw_terminal(
+                            size,
+                            &mut guard,
+                            &title,
+                            f,
+                            selection,
+                            copy_mode.as_ref(),
+                            &theme,
                         );
-                        draw_terminal(size, &mut guard, name, f, selection);
                     }
                 }
                 AppMode::ScpProgress { return_mode, .. } => {
@@ -1361,12 +1611,16 @@ impl<B: Backend + Write> App<B> {
                                 &SearchState::Off,
                                 f,
                                 false,
+                                &theme,
                             );
                         }
-                        Some(ScpReturnMode::Connected { name, state, .. }) => {
-                            let inner = rect_with_top_margin(size, 1);
-                            new_viewport = inner;
+                        Some(ScpReturnMode::Connected {
+                            connection, state, ..
+                        }) => {
+                            new_viewport = rect_with_top_margin(size, 1);
                             if let Ok(mut guard) = state.try_lock() {
+                                let inner = terminal_text_area(size, &guard);
+                                new_viewport = inner;
                                 if guard.screen_size() != (inner.height, inner.width) {
                                     guard.resize(inner.height, inner.width);
                                 }
@@ -1376,8 +1630,16 @@ impl<B: Backend + Write> App<B> {
                                     &guard,
                                     inner.width,
                                     selection_forced,
+                                    selection_shape,
                                 );
-                                draw_terminal(size, &mut guard, name, f, selection);
+                                let title = match guard.window_title() {
+                                    Some(remote_title) => format!(
+                                        "Connected to {}: {remote_title}",
+                                        connection.display_name
+                                    ),
+                                    None => format!("Connected to {}", connection.display_name),
+                                };
+                                draw_terminal(size, &mut guard, &title, f, selection, None, &theme);
                             }
                         }
                         Some(ScpReturnMode::FileExplorer {
@@ -1401,6 +1663,7 @@ impl<B: Backend + Write> App<B> {
                                 copy_buffer,
                                 search,
                                 self.config.have_nerd_font(),
+                                &theme,
                             );
                         }
                         None => {}
@@ -1418,6 +1681,7 @@ impl<B: Backend + Write> App<B> {
                         &SearchState::Off,
                         f,
                         false,
+                        &theme,
                     );
                 }
                 AppMode::FileExplorer {
@@ -1444,6 +1708,7 @@ impl<B: Backend + Write> App<B> {
                         copy_buffer,
                         search,
                         self.config.have_nerd_font(),
+                        &theme,
                     );
 
                     // Draw source selector popup if active
@@ -1473,6 +1738,7 @@ impl<B: Backend + Write> App<B> {
                                 " Select Right Pane Source "
                             },
                             &source_selector.search,
+                            &theme,
                         );
                     }
 
@@ -1483,6 +1749,7 @@ impl<B: Backend + Write> App<B> {
                             size,
                             &crate::ui::DeleteConfirmationConfig::FILE,
                             &delete_confirmation.file_name,
+                            &theme,
                         );
                     }
                 }
@@ -1497,6 +1764,7 @@ impl<B: Backend + Write> App<B> {
                         state.selected,
                         &state.search,
                         f,
+                        &theme,
                     );
                 }
                 AppMode::PortForwardingFormNew(state) | AppMode::PortForwardingFormEdit(state) => {
@@ -1510,6 +1778,7 @@ impl<B: Backend + Write> App<B> {
                         state.current_selected,
                         &SearchState::Off,
                         f,
+                        &theme,
                     );
                 }
                 AppMode::PortForwardDeleteConfirmation {
@@ -1525,6 +1794,7 @@ impl<B: Backend + Write> App<B> {
                         *current_selected,
                         &SearchState::Off,
                         f,
+                        &theme,
                     );
                 }
             }
@@ -1532,11 +1802,25 @@ impl<B: Backend + Write> App<B> {
             // Overlay port forwarding form popup if in port forwarding form mode
             if let AppMode::PortForwardingFormNew(state) = &mut self.mode {
                 let connections = self.config.connections();
-                draw_port_forwarding_form_popup(size, &mut state.form, connections, true, f);
+                draw_port_forwarding_form_popup(
+                    size,
+                    &mut state.form,
+                    connections,
+                    true,
+                    f,
+                    &theme,
+                );
             }
             if let AppMode::PortForwardingFormEdit(state) = &mut self.mode {
                 let connections = self.config.connections();
-                draw_port_forwarding_form_popup(size, &mut state.form, connections, false, f);
+                draw_port_forwarding_form_popup(
+                    size,
+                    &mut state.form,
+                    connections,
+                    false,
+                    f,
+                    &theme,
+                );
             }
 
             // Overlay port forwarding connection selector popup when active
@@ -1553,6 +1837,7 @@ impl<B: Backend + Write> App<B> {
                     false,
                     " Choose Connection ",
                     &state.connection_selector.search,
+                    &theme,
                 );
             }
             if let AppMode::PortForwardingFormEdit(state) = &mut self.mode
@@ -1568,12 +1853,13 @@ impl<B: Backend + Write> App<B> {
                     false,
                     " Choose Connection ",
                     &state.connection_selector.search,
+                    &theme,
                 );
             }
 
             // Overlay SCP progress popup if in SCP progress mode
             if let AppMode::ScpProgress { progress, .. } = &mut self.mode {
-                draw_scp_progress_popup(size, progress, f);
+                draw_scp_progress_popup(size, progress, f, &theme);
             }
 
             // Overlay delete confirmation popup if in delete confirmation mode
@@ -1586,6 +1872,7 @@ impl<B: Backend + Write> App<B> {
                     size,
                     &DeleteConfirmationConfig::CONNECTION,
                     connection_name,
+                    &theme,
                 );
             }
 
@@ -1599,15 +1886,16 @@ impl<B: Backend + Write> App<B> {
                     size,
                     &DeleteConfirmationConfig::PORT_FORWARD,
                     port_forward_name,
+                    &theme,
                 );
             }
 
             // Overlay connection form popup if in form mode
             if let AppMode::FormNew { form, .. } = &self.mode {
-                draw_connection_form_popup(size, form, true, f);
+                draw_connection_form_popup(size, form, true, f, &theme);
             }
             if let AppMode::FormEdit { form, .. } = &mut self.mode {
-                draw_connection_form_popup(size, form, false, f);
+                draw_connection_form_popup(size, form, false, f, &theme);
             }
 
             // Overlay connecting popup if in connecting mode
@@ -1616,17 +1904,93 @@ impl<B: Backend + Write> App<B> {
             } = &self.mode
             {
                 let message = format!("Connecting to {connection_name}...");
-                draw_connecting_popup(size, &message, f);
+                draw_connecting_popup(size, &message, f, &theme);
+            }
+
+            // Overlay reconnect progress on the frozen terminal
+            if let AppMode::Connected {
+                client,
+                reconnect: Some(reconnect),
+                ..
+            } = &self.mode
+            {
+                let mut message = format!(
+                    "Connection lost — reconnecting (attempt {})...",
+                    reconnect.attempt
+                );
+                if let Some(persistent) = client.persistent_session() {
+                    message.push_str(&format!(
+                        "\nThe {} session keeps running on the host",
+                        persistent.multiplexer.label()
+                    ));
+                }
+                draw_connecting_popup(size, &message, f, &theme);
+            }
+
+            // Ask whether closing a persistent session detaches from or kills it
+            if let AppMode::Connected {
+                client,
+                close_prompt: true,
+                ..
+            } = &self.mode
+                && let Some(persistent) = client.persistent_session()
+            {
+                draw_close_session_popup(
+                    size,
+                    persistent.multiplexer.label(),
+                    &persistent.name,
+                    f,
+                    &theme,
+                );
+            }
+
+            // Ask before a multi-line paste runs line by line
+            if let AppMode::Connected {
+                pending_paste: Some(data),
+                ..
+            } = &self.mode
+            {
+                draw_paste_confirmation_popup(size, data.lines().count(), f, &theme);
+            }
+
+            // Overlay snippet palette on the terminal
+            if let AppMode::Connected {
+                snippet_palette: Some(palette),
+                ..
+            } = &self.mode
+            {
+                draw_snippet_palette(f, size, palette, &theme);
+            }
+            if let AppMode::Connected {
+                url_picker: Some(picker),
+                ..
+            } = &self.mode
+            {
+                draw_url_picker(f, size, picker, &theme);
+            }
+            if let AppMode::Connected {
+                export_menu: Some(menu),
+                ..
+            } = &self.mode
+            {
+                draw_export_menu(f, size, menu, &theme);
+            }
+            if let AppMode::Connected {
+                zmodem_picker: Some(picker),
+                ..
+            } = &mut self.mode
+            {
+                draw_zmodem_picker(f, size, picker, &theme);
             }
 
             // Overlay info popup if any
             if let Some(msg) = &self.info {
-                draw_info_popup(size, msg, f);
+                draw_info_popup(size, msg, f, &theme);
             }
 
             // Overlay error popup if any (always on top)
             if let Some(err) = &self.error {
-                draw_error_popup(size, &err.to_string(), f);
+                draw_error_popup(size, &err.to_string(), f, &theme);
             }
         })?;
 
@@ -1726,6 +2090,9 @@ impl<B: Backend + Write> App<B> {
                         }
                     }
 
+                    // Drive automatic reconnect of a dropped session
+                    self.poll_reconnect().await;
+
                     // Handle connection result polling in Connecting mode
                     if let AppMode::Connecting {
                         connection,
@@ -1760,6 +2127,8 @@ impl<B: Backend + Write> App<B> {
                                             return_from
                                         {
                                             if *file_explorer {
+                                                // The explorer reuses the pooled connection; only the shell channel goes away
+                                                let _ = client.close_channel().await;
                                                 let conn = connection.clone();
                                                 self.go_to_file_explorer(conn, return_to).await?;
                                                 continue;
@@ -1782,7 +2151,6 @@ impl<B: Backend + Write> App<B> {
                                             }
                                         }
 
-                                        let scrollback = self.config.terminal_scrollback_lines();
                                         let (cols, rows) =
                                             self.ssh_terminal_size().unwrap_or((80, 24));
                                         tracing::info!(
@@ -1790,11 +2158,8 @@ impl<B: Backend + Write> App<B> {
                                             rows,
                                             cols
                                         );
-                                        let state = Arc::new(Mutex::new(
-                                            TerminalState::new_with_scrollback(
-                                                rows, cols, scrollback,
-                                            ),
-                                        ));
+                                        let state =
+                                            self.new_terminal_state(&connection, rows, cols);
                                         let app_reader = state.clone();
                                         let reader =
                                             client.take_reader().expect("reader already taken");
@@ -1812,14 +2177,25 @@ impl<B: Backend + Write> App<B> {
                                             .await;
                                         });
 
+                                        if let Some(command) = conn.startup_command.as_deref()
+                                            && !Self::reattached(&client)
+                                        {
+                                            Self::send_startup_command(&client, command).await;
+                                        }
+
+                                        let notice =
+                                            client.persistent_session().map(persistent_notice);
                                         let _ = self.config.touch_last_used(&conn.id);
                                         self.go_to_connected(
-                                            conn.display_name.clone(),
+                                            conn,
                                             client,
                                             state,
                                             return_to,
                                             cancel_token,
                                         );
+                                        if let Some(notice) = notice {
+                                            self.show_terminal_notice(notice);
+                                        }
                                     }
                                     Err(e) => {
                                         // Connection failed - clone data before setting error
@@ -1899,6 +2275,8 @@ impl<B: Backend + Write> App<B> {
                         Event::Paste(data) => {
                             crate::key_event::handle_paste_event(self, &data).await;
                         }
+                        Event::FocusGained => self.host_focused = true,
+                        Event::FocusLost => self.host_focused = false,
                         Event::Resize(_, _) => {}
                         _ => {}
                     }
@@ -1921,12 +2299,36 @@ impl<B: Backend + Write> App<B> {
                         }
                     }
 
-                    self.clear_selection();
+                    if let AppMode::Connected {
+                        state,
+                        copy_mode: Some(copy_mode),
+                        ..
+                    } = &mut self.mode
+                    {
+                        copy_mode.follow_output(&*state.lock().await);
+                    }
+                    self.sync_copy_mode_selection();
+                    self.mark_redraw();
+                }
+                AppEvent::TerminalReply(reply) => {
+                    self.write_terminal_reply(&reply).await;
+                }
+                AppEvent::RemoteClipboard(text) => {
+                    self.handle_remote_clipboard(text);
+                }
+                AppEvent::TerminalAlert(alert) => {
+                    self.handle_terminal_alert(alert).await;
+                }
+                AppEvent::Redraw => {
                     self.mark_redraw();
                 }
                 AppEvent::SftpProgress(result) => {
                     if let AppMode::ScpProgress { progress, .. } = &mut self.mode {
                         match result {
+                            ScpResult::FileAdded(file) => {
+                                progress.add_file(file);
+                                self.mark_redraw();
+                            }
                             ScpResult::Progress(update) => {
                                 progress.update_progress(update);
                                 self.mark_redraw();
@@ -1970,7 +2372,10 @@ impl<B: Backend + Write> App<B> {
                                 }
 
                                 if !all_success {
-                                    let mut message = String::from("sftp transfer issues:");
+                                    let mut message = format!(
+                                        "{} transfer issues:",
+                                        progress.protocol.to_lowercase()
+                                    );
                                     for line in failure_lines {
                                         message.push('\n');
                                         message.push_str(&line);
@@ -2000,75 +2405,617 @@ impl<B: Backend + Write> App<B> {
                         }
                     }
                 }
+                AppEvent::ExportUploaded(result) => match result {
+                    Ok(remote) => self.set_info(format!("Uploaded to {remote}")),
+                    Err(e) => self.set_error(e),
+                },
                 AppEvent::Disconnect => {
-                    // SSH connection has been disconnected (e.g., user typed 'exit')
-                    tracing::info!("SSH connection disconnected");
-                    // Take ownership of the mode to avoid borrow issues
-                    let old_mode = std::mem::replace(
-                        &mut self.mode,
-                        AppMode::ConnectionList(crate::ListSelectionState::new(0)),
-                    );
-                    if let AppMode::Connected {
-                        current_selected,
-                        cancel_token,
-                        name,
-                        client,
-                        return_to_explorer,
-                        ..
-                    } = old_mode
-                    {
-                        tracing::debug!("Closing connection to '{}'", name);
-                        // Cancel the read task
-                        cancel_token.cancel();
-                        // Close the SSH channel
-                        if let Err(e) = client.close_channel().await {
-                            tracing::error!("Error close SSH channel: {}", e);
-                        }
-                        // Close the SSH connection (no-op if session is None)
-                        if let Err(e) = client.close().await {
-                            tracing::error!("Error closing SSH connection: {}", e);
-                        }
-                        // Check if we should return to file explorer
-                        if let Some(ScpReturnMode::FileExplorer {
-                            connection_name,
-                            left_pane,
-                            left_explorer,
-                            left_session,
-                            remote_explorer,
-                            ssh_connection,
-                            channel,
-                            ssh_session,
-                            active_pane,
-                            copy_buffer,
-                            return_to,
-                            search,
-                        }) = return_to_explorer
-                        {
-                            self.mode = AppMode::FileExplorer {
-                                connection_name,
-                                left_pane,
-                                left_explorer,
-                                left_session,
-                                remote_explorer,
-                                ssh_connection,
-                                channel,
-                                ssh_session,
-                                active_pane,
-                                copy_buffer,
-                                return_to,
-                                search,
-                                source_selector: Default::default(),
-                                delete_confirmation: Default::default(),
-                            };
-                        } else {
-                            self.go_to_connection_list_with_selected(current_selected);
-                        }
-                        self.stop_ticker();
-                        self.mark_redraw();
+                    self.handle_disconnect().await;
+                }
+                AppEvent::ConnectionLost => {
+                    if !self.start_reconnect() {
+                        self.handle_disconnect().await;
                     }
                 }
             }
         }
         Ok(())
     }
+
+    /// Leave Connected mode after the session ended (or the user gave up on it),
+    /// returning to the file explorer or the connection list.
+    async fn handle_disconnect(&mut self) {
+        // SSH connection has been disconnected (e.g., user typed 'exit')
+        tracing::info!("SSH connection disconnected");
+        // The session ended during a ZMODEM transfer; stop it and close the
+        // session as usual
+        if let AppMode::ScpProgress { return_mode, .. } = &mut self.mode
+            && matches!(return_mode, Some(ScpReturnMode::Connected { .. }))
+            && let Some(ScpReturnMode::Connected {
+                connection,
+                client,
+                state,
+                current_selected,
+                cancel_token,
+                return_to_explorer,
+            }) = return_mode.take()
+        {
+            state.lock().await.cancel_zmodem();
+            self.go_to_connected_with_return(
+                connection,
+                client,
+                state,
+                current_selected,
+                cancel_token,
+                return_to_explorer.map(|mode| *mode),
+            );
+        }
+        // Take ownership of the mode to avoid borrow issues
+        let old_mode = std::mem::replace(
+            &mut self.mode,
+            AppMode::ConnectionList(crate::ListSelectionState::new(0)),
+        );
+        if let AppMode::Connected {
+            current_selected,
+            cancel_token,
+            name,
+            connection,
+            client,
+            state,
+            return_to_explorer,
+            reconnect,
+            ..
+        } = old_mode
+        {
+            tracing::debug!("Closing connection to '{}'", name);
+            if connection.persist_scrollback {
+                save_scrollback(&connection, &state).await;
+            }
+            // Cancel the read task and any reconnect in flight
+            cancel_token.cancel();
+            if let Some(reconnect) = reconnect {
+                reconnect.cancel_token.cancel();
+            }
+            // Close the SSH channel
+            if let Err(e) = client.close_channel().await {
+                tracing::error!("Error close SSH channel: {}", e);
+            }
+            // Close the SSH connection (no-op if session is None)
+            if let Err(e) = client.close().await {
+                tracing::error!("Error closing SSH connection: {}", e);
+            }
+            // Check if we should return to file explorer
+            if let Some(ScpReturnMode::FileExplorer {
+                connection_name,
+                left_pane,
+                left_explorer,
+                left_session,
+                remote_explorer,
+                ssh_connection,
+                channel,
+                ssh_session,
+                active_pane,
+                copy_buffer,
+                return_to,
+                search,
+            }) = return_to_explorer
+            {
+                self.mode = AppMode::FileExplorer {
+                    connection_name,
+                    left_pane,
+                    left_explorer,
+                    left_session,
+                    remote_explorer,
+                    ssh_connection,
+                    channel,
+                    ssh_session,
+                    active_pane,
+                    copy_buffer,
+                    return_to,
+                    search,
+                    source_selector: Default::default(),
+                    delete_confirmation: Default::default(),
+                };
+            } else {
+                self.go_to_connection_list_with_selected(current_selected);
+            }
+            self.stop_ticker();
+            self.mark_redraw();
+        }
+    }
+
+    /// Begin reconnecting a dropped session if its connection opted in.
+    /// Returns false when the caller should fall back to a plain disconnect.
+    fn start_reconnect(&mut self) -> bool {
+        let AppMode::Connected {
+            connection,
+            reconnect,
+            ..
+        } = &mut self.mode
+        else {
+            return false;
+        };
+        if !connection.auto_reconnect {
+            return false;
+        }
+        if reconnect.is_none() {
+            tracing::info!(
+                "Connection to '{}' lost, reconnecting",
+                connection.host_port()
+            );
+            *reconnect = Some(ReconnectState::new());
+        }
+        self.start_ticker();
+        self.mark_redraw();
+        true
+    }
+
+    /// Drive an in-progress reconnect: start the next attempt once its backoff
+    /// has elapsed and swap in the new session when it is ready. The terminal
+    /// state (and its scrollback) is kept and fed by a fresh read loop.
+    async fn poll_reconnect(&mut self) {
+        let (cols, rows) = self.ssh_terminal_size().unwrap_or((80, 24));
+        let pool = self.session_pool.clone();
+        let event_tx = self.event_tx.clone();
+
+        let AppMode::Connected {
+            connection,
+            client,
+            state,
+            cancel_token,
+            reconnect: reconnect_slot,
+            ..
+        } = &mut self.mode
+        else {
+            return;
+        };
+        let Some(reconnect) = reconnect_slot.as_mut() else {
+            return;
+        };
+
+        let Some(receiver) = reconnect.receiver.as_mut() else {
+            if Instant::now() >= reconnect.next_attempt_at {
+                tracing::debug!("Reconnect attempt {}", reconnect.attempt);
+                let (cancel, receiver) =
+                    SshSession::initiate_connection(connection.clone(), cols, rows, pool);
+                reconnect.cancel_token = cancel;
+                reconnect.receiver = Some(receiver);
+            }
+            return;
+        };
+
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => Err(AppError::SshConnectionError(
+                "Reconnect task ended unexpectedly".to_string(),
+            )),
+        };
+
+        match result {
+            Ok(mut new_client) => {
+                let reader = new_client.take_reader().expect("reader already taken");
+                let new_cancel = tokio_util::sync::CancellationToken::new();
+                let cancel_for_task = new_cancel.clone();
+                let processor = state.clone();
+                tokio::spawn(async move {
+                    SshSession::read_loop(reader, processor, cancel_for_task, event_tx).await;
+                });
+
+                let old_client = std::mem::replace(client, new_client);
+                let _ = old_client.close_channel().await;
+                *cancel_token = new_cancel;
+
+                if let Some(command) = connection.startup_command.as_deref()
+                    && !Self::reattached(client)
+                {
+                    Self::send_startup_command(client, command).await;
+                }
+                tracing::info!(
+                    "Reconnected to '{}' after {} attempt(s)",
+                    connection.host_port(),
+                    reconnect.attempt
+                );
+                let notice = client.persistent_session().map(persistent_notice);
+                *reconnect_slot = None;
+                self.stop_ticker();
+                if let Some(notice) = notice {
+                    self.show_terminal_notice(notice);
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Reconnect attempt {} failed: {}", reconnect.attempt, e);
+                if reconnect.attempt >= RECONNECT_MAX_ATTEMPTS {
+                    self.set_error(e);
+                    self.handle_disconnect().await;
+                    return;
+                }
+                reconnect.schedule_retry();
+            }
+        }
+        self.mark_redraw();
+    }
+
+    /// Create the emulator state for a new terminal session. Its replies to
+    /// terminal queries are forwarded to the event loop, which writes them to
+    /// whichever session is current (so they survive a reconnect).
+    pub fn new_terminal_state(
+        &self,
+        connection: &Connection,
+        rows: u16,
+        cols: u16,
+    ) -> Arc<Mutex<TerminalState>> {
+        let scrollback = self.config.terminal_scrollback_lines();
+        // A broken scheme shouldn't stop the session; fall back to the host's colours
+        let color_scheme = self
+            .config
+            .color_scheme_for(connection)
+            .unwrap_or_else(|e| {
+                tracing::warn!("{}", e);
+                None
+            });
+        let mut state = TerminalState::new_with_color_scheme(rows, cols, scrollback, color_scheme);
+        state.set_prompt_gutter(self.config.prompt_gutter());
+        // Like the colour scheme, a recording that can't be started doesn't stop the session
+        if connection.record_sessions {
+            match state.start_recording(&connection.display_name) {
+                Ok(path) => tracing::info!("Recording session to {}", path.display()),
+                Err(e) => tracing::warn!("Could not start recording: {}", e),
+            }
+        }
+        if let Some(options) = &connection.session_log {
+            match crate::terminal::SessionLogger::new(&connection.display_name, options) {
+                Ok(logger) => state.set_session_logger(logger),
+                Err(e) => tracing::warn!("Could not open the session log: {}", e),
+            }
+        }
+        if connection.persist_scrollback {
+            let history = crate::terminal::history_path(&connection.id)
+                .and_then(|path| crate::terminal::load_history(&path));
+            match history {
+                Ok(Some((text, saved))) => state.restore_history(&text, saved),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Could not restore the previous scrollback: {}", e),
+            }
+        }
+        if let (Some(mut replies), Some(event_tx)) =
+            (state.take_reply_receiver(), self.event_tx.clone())
+        {
+            tokio::spawn(async move {
+                while let Some(reply) = replies.recv().await {
+                    if event_tx.send(AppEvent::TerminalReply(reply)).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        if let (Some(mut alerts), Some(event_tx)) =
+            (state.take_alert_receiver(), self.event_tx.clone())
+        {
+            tokio::spawn(async move {
+                while let Some(alert) = alerts.recv().await {
+                    if event_tx.send(AppEvent::TerminalAlert(alert)).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        if let (Some(mut requests), Some(event_tx)) =
+            (state.take_clipboard_receiver(), self.event_tx.clone())
+        {
+            tokio::spawn(async move {
+                while let Some(request) = requests.recv().await {
+                    if event_tx
+                        .send(AppEvent::RemoteClipboard(request))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+        }
+        if connection.notify_when_idle {
+            state.set_idle_watch(Some(self.config.idle_notify_after()));
+        }
+        let state = Arc::new(Mutex::new(state));
+        if connection.notify_when_idle {
+            spawn_idle_poller(&state);
+        }
+        state
+    }
+
+    /// Copy text a remote program set with OSC 52, unless the
+    /// `remote_clipboard` setting is off
+    fn handle_remote_clipboard(&mut self, text: String) {
+        if !self.config.remote_clipboard().allows_write() {
+            tracing::debug!("Ignoring OSC 52 clipboard write (remote_clipboard = off)");
+            return;
+        }
+        let chars = text.chars().count();
+        self.copy_text_to_clipboard(text);
+        self.show_terminal_notice(format!("remote copied {chars} characters"));
+    }
+
+    /// Flash the title bar on BEL, show desktop notifications and finished
+    /// commands as info popups, and ask where ZMODEM transfers go
+    async fn handle_terminal_alert(&mut self, alert: crate::ui::TerminalAlert) {
+        match alert {
+            crate::ui::TerminalAlert::Bell => self.flash_bell(),
+            crate::ui::TerminalAlert::CommandDone(done) => {
+                self.flash_bell();
+                // Ring the real terminal too, so a window or tab in the
+                // background gets marked
+                if !self.host_focused {
+                    let backend = self.terminal.backend_mut();
+                    if let Err(e) = backend.write_all(b"\x07").and_then(|_| backend.flush()) {
+                        tracing::warn!("Could not ring the terminal bell: {}", e);
+                    }
+                }
+                let name = match &self.mode {
+                    AppMode::Connected { connection, .. }
+                    | AppMode::ScpProgress {
+                        return_mode: Some(ScpReturnMode::Connected { connection, .. }),
+                        ..
+                    } => connection.display_name.clone(),
+                    _ => "the session".to_string(),
+                };
+                self.set_info(match done {
+                    crate::terminal::CommandDone::Prompt => format!("Command finished on {name}"),
+                    crate::terminal::CommandDone::Idle(after) => format!(
+                        "No output from {name} for {}s; the command looks done",
+                        after.as_secs()
+                    ),
+                });
+            }
+            crate::ui::TerminalAlert::Notification { title, body } => {
+                let message = match title {
+                    Some(title) if !title.is_empty() => format!("{title}\n\n{body}"),
+                    _ => body,
+                };
+                self.set_info(message);
+            }
+            crate::ui::TerminalAlert::Zmodem(direction) => {
+                self.open_zmodem_picker(direction).await;
+            }
+        }
+    }
+
+    fn flash_bell(&mut self) {
+        self.mark_redraw();
+        // Repaint when the flash ends. A flash in progress isn't restarted,
+        // so its repaint already covers this bell.
+        let now = Instant::now();
+        if self.bell_flash_until.is_some_and(|until| now < until) {
+            return;
+        }
+        self.bell_flash_until = Some(now + crate::ui::terminal::BELL_FLASH_DURATION);
+        if let Some(event_tx) = self.event_tx.clone() {
+            tokio::spawn(async move {
+                tokio::time::sleep(crate::ui::terminal::BELL_FLASH_DURATION).await;
+                let _ = event_tx.send(AppEvent::Redraw).await;
+            });
+        }
+    }
+
+    /// `sz` or `rz` started a transfer in the terminal; ask for the file to
+    /// send or where to save
+    async fn open_zmodem_picker(&mut self, direction: crate::zmodem::Direction) {
+        if !matches!(
+            self.mode,
+            AppMode::Connected {
+                reconnect: None,
+                ..
+            }
+        ) {
+            return;
+        }
+        match crate::terminal::ZmodemPicker::new(direction).await {
+            Ok(picker) => {
+                if let AppMode::Connected { zmodem_picker, .. } = &mut self.mode {
+                    *zmodem_picker = Some(picker);
+                }
+                self.mark_redraw();
+            }
+            Err(e) => {
+                self.set_error(e);
+                self.refuse_zmodem();
+            }
+        }
+    }
+
+    /// Turn down the transfer waiting in the terminal
+    pub fn refuse_zmodem(&mut self) {
+        let AppMode::Connected {
+            client,
+            state,
+            zmodem_picker,
+            ..
+        } = &mut self.mode
+        else {
+            return;
+        };
+        *zmodem_picker = None;
+        let (state, output) = (state.clone(), client.transport());
+        let event_tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            let Some((input, cancel)) = state.lock().await.take_zmodem_input() else {
+                return;
+            };
+            let mut io = crate::zmodem::TransferIo {
+                input,
+                output,
+                cancel,
+            };
+            let leftover = crate::zmodem::refuse(&mut io).await;
+            state.lock().await.finish_zmodem(leftover, &mut io.input);
+            if let Some(tx) = event_tx {
+                let _ = tx.send(AppEvent::TerminalUpdate).await;
+            }
+        });
+        self.mark_redraw();
+    }
+
+    /// Run the transfer waiting in the terminal with the local side chosen in
+    /// the picker. Its progress is shown in the transfer popup, which returns
+    /// to the terminal.
+    pub async fn start_zmodem_transfer(&mut self, job: crate::zmodem::Job) {
+        let AppMode::Connected { client, state, .. } = &self.mode else {
+            return;
+        };
+        let Some((input, cancel)) = state.lock().await.take_zmodem_input() else {
+            return;
+        };
+        let mut io = crate::zmodem::TransferIo {
+            input,
+            output: client.transport(),
+            cancel,
+        };
+
+        let old_mode = std::mem::replace(
+            &mut self.mode,
+            AppMode::ConnectionList(crate::ListSelectionState::new(0)),
+        );
+        let AppMode::Connected {
+            connection,
+            client,
+            state,
+            current_selected,
+            cancel_token,
+            return_to_explorer,
+            ..
+        } = old_mode
+        else {
+            return;
+        };
+        let mut progress = ScpProgress::new(connection.display_name.clone(), Vec::new());
+        progress.protocol = "ZMODEM";
+        self.go_to_scp_progress(
+            progress,
+            ScpReturnMode::Connected {
+                connection,
+                client,
+                state: state.clone(),
+                current_selected,
+                cancel_token,
+                return_to_explorer: return_to_explorer.map(Box::new),
+            },
+        );
+        self.start_ticker();
+
+        let (progress_tx, mut progress_rx) = mpsc::channel::<ScpResult>(64);
+        let event_tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            let leftover = crate::zmodem::run(job, &mut io, progress_tx).await;
+            state.lock().await.finish_zmodem(leftover, &mut io.input);
+            if let Some(tx) = &event_tx {
+                let _ = tx.send(AppEvent::TerminalUpdate).await;
+            }
+        });
+        if let Some(event_tx) = self.event_tx.clone() {
+            tokio::spawn(async move {
+                while let Some(result) = progress_rx.recv().await {
+                    if event_tx.send(AppEvent::SftpProgress(result)).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+
+    /// Show a short-lived notice in the terminal title bar
+    fn show_terminal_notice(&mut self, text: String) {
+        self.terminal_notice = Some((text, Instant::now() + TERMINAL_NOTICE_DURATION));
+        self.mark_redraw();
+        // Repaint once more when it expires so it doesn't linger on an idle screen
+        if let Some(event_tx) = self.event_tx.clone() {
+            tokio::spawn(async move {
+                tokio::time::sleep(TERMINAL_NOTICE_DURATION).await;
+                let _ = event_tx.send(AppEvent::Redraw).await;
+            });
+        }
+    }
+
+    /// Write the emulator's answer to a terminal query to the current session
+    async fn write_terminal_reply(&self, reply: &[u8]) {
+        let client = match &self.mode {
+            AppMode::Connected {
+                client,
+                reconnect: None,
+                ..
+            } => client,
+            AppMode::ScpProgress {
+                return_mode: Some(ScpReturnMode::Connected { client, .. }),
+                ..
+            } => client,
+            _ => return,
+        };
+        if let Err(e) = client.write_all(reply).await {
+            tracing::warn!("Failed to send terminal reply: {}", e);
+        }
+    }
+
+    /// Type the connection's startup command into a freshly opened shell
+    async fn send_startup_command(client: &SshSession, command: &str) {
+        let line = format!("{command}\r");
+        if let Err(e) = client.write_all(line.as_bytes()).await {
+            tracing::warn!("Failed to send startup command: {}", e);
+        }
+    }
+
+    /// Whether the client re-attached to a persistent session that was already
+    /// running, where the startup command has been typed before
+    fn reattached(client: &SshSession) -> bool {
+        client
+            .persistent_session()
+            .is_some_and(|persistent| persistent.reattached)
+    }
+}
+
+/// Title bar notice for the tmux or screen session a terminal attached to
+fn persistent_notice(persistent: &crate::persistent_session::PersistentSession) -> String {
+    let verb = if persistent.reattached {
+        "re-attached to"
+    } else {
+        "started"
+    };
+    format!(
+        "{verb} {} session {}",
+        persistent.multiplexer.label(),
+        persistent.name
+    )
+}
+
+/// Save the scrollback of a closing session for the next one. Failures are
+/// only logged, as the session is going away regardless.
+async fn save_scrollback(connection: &Connection, state: &Mutex<TerminalState>) {
+    let Some(text) = state.lock().await.history_to_save() else {
+        tracing::debug!("Not saving scrollback: a full-screen program is running");
+        return;
+    };
+    let result = crate::terminal::history_path(&connection.id)
+        .and_then(|path| crate::terminal::save_history(&path, &text));
+    if let Err(e) = result {
+        tracing::warn!("Could not save the scrollback: {}", e);
+    }
+}
+
+/// Check a session's output for going quiet every second, until "notify
+/// when idle" is turned off or the session is dropped
+pub fn spawn_idle_poller(state: &Arc<Mutex<TerminalState>>) {
+    let state = Arc::downgrade(state);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            let Some(state) = state.upgrade() else {
+                break;
+            };
+            let mut guard = state.lock().await;
+            if guard.idle_watch().is_none() {
+                break;
+            }
+            guard.poll_idle_watch();
+        }
+    });
 }
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use futures::stream::{self, StreamExt, TryStreamExt};
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufWriter};
@@ -19,9 +22,13 @@ use russh_sftp::client::rawsession::RawSftpSession;
 use russh_sftp::protocol::{FileAttributes, OpenFlags, StatusCode};
 use tokio::net::{TcpListener, TcpStream};
 
-use crate::config::manager::{AuthMethod, Connection, PortForward, PortForwardType};
+use crate::config::manager::{
+    AuthMethod, Connection, ConnectionKind, PortForward, PortForwardType, SshOptions,
+};
 use crate::error::{AppError, Result};
+use crate::persistent_session::PersistentSession;
 use crate::transfer::{ScpResult, ScpTransferProgress};
+use crate::transport::{ChunkReader, LocalPty, TcpTransport, TerminalTransport};
 
 const STANDARD_KEY_PATHS: &[&str] = &[
     "~/.ssh/id_rsa",
@@ -35,6 +42,20 @@ const FILE_BUFFER_SIZE: usize = 512 * 1024;
 const CHUNK_SIZE: usize = 128 * 1024;
 const MAX_CONCURRENT_READS: usize = 12;
 const MAX_CONCURRENT_WRITES: usize = 12;
+pub(crate) const DEFAULT_TERM: &str = "xterm-256color";
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_KEEPALIVE_MAX: usize = 3;
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+const MAX_POOL_RECONNECT_ATTEMPTS: usize = 3;
+
+/// Authenticated SSH connection shared by everything that opens channels on it.
+/// Holding a clone acts as a lease that keeps the pool from reaping it.
+pub(crate) type SharedHandle = Arc<tokio::sync::Mutex<client::Handle<SshClient>>>;
+
+/// Remote (`-R`) forward receivers keyed by the remote port the server binds.
+type ForwardedRoutes =
+    Arc<tokio::sync::Mutex<HashMap<u32, mpsc::UnboundedSender<Channel<client::Msg>>>>>;
 
 pub(crate) trait ByteProcessor {
     fn process_bytes(&mut self, bytes: &[u8]);
@@ -112,8 +133,8 @@ impl ProgressReporter for TxProgressReporter {
 pub struct SshClient {
     connection: Connection,
     server_key: Arc<OnceCell<String>>,
-    // Channel for forwarding remote port forwarding connections
-    forwarded_tcpip_tx: Option<tokio::sync::mpsc::UnboundedSender<Channel<client::Msg>>>,
+    // Receivers for remote port forwarding connections, one per bound port
+    forwarded_routes: ForwardedRoutes,
 }
 
 impl client::Handler for SshClient {
@@ -163,8 +184,14 @@ impl client::Handler for SshClient {
             connected_address, connected_port, originator_address, originator_port
         );
 
-        // Send the channel to the handler if we have a sender
-        if let Some(ref tx) = self.forwarded_tcpip_tx {
+        // Send the channel to the handler registered for this port
+        let route = self
+            .forwarded_routes
+            .lock()
+            .await
+            .get(&connected_port)
+            .cloned();
+        if let Some(tx) = route {
             if let Err(e) = tx.send(channel) {
                 error!("Failed to send forwarded-tcpip channel: {}", e);
                 return Err(AppError::PortForwardingError(
@@ -181,10 +208,54 @@ impl client::Handler for SshClient {
 }
 
 pub struct SshSession {
-    session: Arc<tokio::sync::Mutex<Option<client::Handle<SshClient>>>>,
-    r: Option<russh::ChannelReadHalf>,
-    w: russh::ChannelWriteHalf<client::Msg>,
+    session: Option<SharedHandle>,
+    r: Option<SessionReader>,
+    w: Arc<dyn TerminalTransport>,
     server_key: Arc<OnceCell<String>>,
+    /// The tmux or screen session the terminal is attached to
+    persistent: Option<PersistentSession>,
+}
+
+/// Where a terminal session's output comes from: an SSH channel, or the
+/// reader of another transport
+pub enum SessionReader {
+    Ssh(russh::ChannelReadHalf),
+    Chunks(ChunkReader),
+}
+
+impl TerminalTransport for russh::ChannelWriteHalf<client::Msg> {
+    fn write_all<'a>(&'a self, data: &'a [u8]) -> futures::future::BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut writer = self.make_writer();
+            match writer.write_all(data).await {
+                Ok(_) => Ok(()),
+                Err(e) => match e.kind() {
+                    std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::UnexpectedEof => {
+                        Err(AppError::ChannelClosedError(e.to_string()))
+                    }
+                    _ => Err(AppError::SshWriteError(format!(
+                        "Failed to write to SSH channel: {e}"
+                    ))),
+                },
+            }
+        })
+    }
+
+    fn resize(&self, cols: u16, rows: u16) -> futures::future::BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            self.window_change(cols as u32, rows as u32, 0, 0).await?;
+            Ok(())
+        })
+    }
+
+    fn close(&self) -> futures::future::BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            russh::ChannelWriteHalf::close(self).await?;
+            Ok(())
+        })
+    }
 }
 
 impl SshSession {
@@ -193,15 +264,16 @@ impl SshSession {
         timeout: Option<Duration>,
         cancel: &tokio_util::sync::CancellationToken,
     ) -> Result<(client::Handle<SshClient>, Arc<OnceCell<String>>)> {
-        Self::new_session_with_timeout_and_forwarding(connection, timeout, cancel, None).await
+        Self::new_session_with_routes(connection, timeout, cancel, ForwardedRoutes::default()).await
     }
 
-    /// Create a new SSH session with optional remote port forwarding channel
-    pub(crate) async fn new_session_with_timeout_and_forwarding(
+    /// Create a new SSH session that dispatches remote port forwarding
+    /// connections through `forwarded_routes`
+    async fn new_session_with_routes(
         connection: &Connection,
         timeout: Option<Duration>,
         cancel: &tokio_util::sync::CancellationToken,
-        forwarded_tcpip_tx: Option<tokio::sync::mpsc::UnboundedSender<Channel<client::Msg>>>,
+        forwarded_routes: ForwardedRoutes,
     ) -> Result<(client::Handle<SshClient>, Arc<OnceCell<String>>)> {
         info!(
             "Initiating SSH connection to {}@{}",
@@ -209,38 +281,18 @@ impl SshSession {
             connection.host_port()
         );
 
-        // Configure preferred algorithms, especially compression
-        // We prefer zlib@openssh.com over zlib because:
-        // - zlib starts compression IMMEDIATELY after key exchange (before auth)
-        // - zlib@openssh.com starts compression AFTER authentication
-        // russh only initializes decompression after auth success, so using "zlib"
-        // with servers that compress immediately (like tmate) will fail.
-        let mut preferred = Preferred::default();
-        preferred.compression = std::borrow::Cow::Borrowed(&[
-            compression::NONE,
-            compression::ZLIB_LEGACY, // zlib@openssh.com - compression after auth
-            compression::ZLIB,        // zlib - compression immediately (fallback)
-        ]);
-
-        let config = client::Config {
-            keepalive_interval: Some(std::time::Duration::from_secs(30)),
-            keepalive_max: 3,
-            preferred,
-            ..Default::default()
-        };
-
-        let config = Arc::new(config);
+        let config = Arc::new(Self::client_config(&connection.ssh_options)?);
         let server_key = Arc::new(OnceCell::new());
         let ssh_client = SshClient {
             connection: connection.clone(),
             server_key: server_key.clone(),
-            forwarded_tcpip_tx,
+            forwarded_routes,
         };
 
         debug!("Establishing TCP connection to {}", connection.host_port());
         let mut session = client::connect(config, connection.host_port(), ssh_client)
             .or_cancel(cancel)
-            .or_timeout(timeout.unwrap_or(Duration::from_secs(10)))
+            .or_timeout(Self::connect_timeout(connection, timeout))
             .await
             .flatten()
             .flatten()?;
@@ -252,6 +304,85 @@ impl SshSession {
         Ok((session, server_key))
     }
 
+    /// Build the russh client config, applying the connection's overrides
+    /// on top of the defaults
+    fn client_config(options: &SshOptions) -> Result<client::Config> {
+        use std::borrow::Cow;
+
+        // Configure preferred algorithms, especially compression
+        // We prefer zlib@openssh.com over zlib because:
+        // - zlib starts compression IMMEDIATELY after key exchange (before auth)
+        // - zlib@openssh.com starts compression AFTER authentication
+        // russh only initializes decompression after auth success, so using "zlib"
+        // with servers that compress immediately (like tmate) will fail.
+        let mut preferred = Preferred::default();
+        preferred.compression = match options.compression {
+            None => Cow::Borrowed(&[
+                compression::NONE,
+                compression::ZLIB_LEGACY, // zlib@openssh.com - compression after auth
+                compression::ZLIB,        // zlib - compression immediately (fallback)
+            ]),
+            Some(true) => Cow::Borrowed(&[
+                compression::ZLIB_LEGACY,
+                compression::ZLIB,
+                compression::NONE,
+            ]),
+            Some(false) => Cow::Borrowed(&[compression::NONE]),
+        };
+
+        if let Some(kex) = preferred_algorithms(
+            "key exchange",
+            &options.kex_algorithms,
+            &preferred.kex,
+            |name| russh::kex::Name::try_from(name).ok(),
+        )? {
+            preferred.kex = Cow::Owned(kex);
+        }
+        if let Some(ciphers) =
+            preferred_algorithms("cipher", &options.ciphers, &preferred.cipher, |name| {
+                russh::cipher::Name::try_from(name).ok()
+            })?
+        {
+            preferred.cipher = Cow::Owned(ciphers);
+        }
+        if let Some(macs) = preferred_algorithms("MAC", &options.macs, &preferred.mac, |name| {
+            russh::mac::Name::try_from(name).ok()
+        })? {
+            preferred.mac = Cow::Owned(macs);
+        }
+        if let Some(keys) = preferred_algorithms(
+            "host key",
+            &options.host_key_algorithms,
+            &preferred.key,
+            |name| name.parse::<ssh_key::Algorithm>().ok(),
+        )? {
+            preferred.key = Cow::Owned(keys);
+        }
+
+        let keepalive_interval = match options.keepalive_interval {
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => Some(DEFAULT_KEEPALIVE_INTERVAL),
+        };
+
+        Ok(client::Config {
+            keepalive_interval,
+            keepalive_max: options.keepalive_max.unwrap_or(DEFAULT_KEEPALIVE_MAX),
+            preferred,
+            ..Default::default()
+        })
+    }
+
+    /// The connection's own timeout wins over the caller's, which wins over the default
+    fn connect_timeout(connection: &Connection, timeout: Option<Duration>) -> Duration {
+        connection
+            .ssh_options
+            .connect_timeout
+            .map(Duration::from_secs)
+            .or(timeout)
+            .unwrap_or(DEFAULT_CONNECT_TIMEOUT)
+    }
+
     pub(crate) async fn setup_sftp_session(
         channel: Option<Channel<client::Msg>>,
         connection: &Connection,
@@ -528,24 +659,15 @@ impl SshSession {
         }
     }
 
-    pub(crate) async fn new_session(
-        connection: &Connection,
-    ) -> Result<(client::Handle<SshClient>, Arc<OnceCell<String>>)> {
-        Self::new_session_with_timeout(
-            connection,
-            None,
-            &tokio_util::sync::CancellationToken::new(),
-        )
-        .await
-    }
-
     /// Initiate an SSH connection asynchronously
     /// Returns a cancel token and a receiver for the connection result
     /// `cols` and `rows` specify the initial PTY size
+    /// The terminal channel is opened on the pooled connection for `conn`
     pub(crate) fn initiate_connection(
         conn: Connection,
         cols: u16,
         rows: u16,
+        pool: SessionPool,
     ) -> (
         tokio_util::sync::CancellationToken,
         mpsc::Receiver<Result<SshSession>>,
@@ -555,7 +677,9 @@ impl SshSession {
         let cancel_clone = cancel_token.clone();
 
         tokio::spawn(async move {
-            let result = Self::connect_with_cancel(&conn, cols, rows, None, &cancel_clone).await;
+            let result =
+                Self::connect_with_cancel(&conn, cols, rows, None, &cancel_clone, Some(&pool))
+                    .await;
             // Only send result if not cancelled
             if !cancel_clone.is_cancelled() {
                 let _ = tx.send(result).await;
@@ -571,26 +695,56 @@ impl SshSession {
         rows: u16,
         timeout: Option<Duration>,
         cancel: &tokio_util::sync::CancellationToken,
+        pool: Option<&SessionPool>,
     ) -> Result<Self> {
-        let timeout = timeout.unwrap_or(Duration::from_secs(10));
+        let timeout = Self::connect_timeout(connection, timeout);
+        match connection.kind {
+            ConnectionKind::Ssh => {}
+            ConnectionKind::Local => {
+                return Self::open_local_terminal(connection, cols, rows, None);
+            }
+            ConnectionKind::Telnet | ConnectionKind::Tcp => {
+                let (transport, reader) = TcpTransport::connect(connection, cols, rows)
+                    .or_cancel(cancel)
+                    .or_timeout(timeout)
+                    .await
+                    .flatten()
+                    .flatten()?;
+                return Ok(Self::with_transport(transport, reader));
+            }
+        }
 
         let f = async {
-            let (session, server_key) =
-                Self::new_session_with_timeout(connection, Some(timeout), cancel).await?;
-
-            debug!("Opening SSH session channel");
-            let channel = session.channel_open_session().await?;
-            info!("Requesting PTY with size {} cols x {} rows", cols, rows);
-
-            let _ = channel.set_env(false, "LC_CTYPE", "C.UTF-8").await;
-
-            channel
-                .request_pty(true, "xterm-256color", cols as u32, rows as u32, 0, 0, &[])
+            let (channel, session, server_key) = match pool {
+                Some(pool) => {
+                    pool.open_session_channel(connection, Some(timeout), cancel)
+                        .await?
+                }
+                None => {
+                    let (session, server_key) =
+                        Self::new_session_with_timeout(connection, Some(timeout), cancel).await?;
+
+                    debug!("Opening SSH session channel");
+                    let channel = session.channel_open_session().await?;
+                    (
+                        channel,
+                        Arc::new(tokio::sync::Mutex::new(session)),
+                        server_key,
+                    )
+                }
+            };
+            let persistent = if connection.persistent_session {
+                Self::probe_persistent_session(&session, connection).await
+            } else {
+                None
+            };
+            let command = match &persistent {
+                Some(persistent) => Some(persistent.attach_command()),
+                None => connection.remote_command.clone(),
+            };
+            Self::start_terminal(&channel, connection, cols, rows, None, command.as_deref())
                 .await?;
 
-            debug!("Requesting shell");
-            channel.request_shell(true).await?;
-
             let (r, w) = channel.split();
 
             info!(
@@ -599,10 +753,11 @@ impl SshSession {
             );
 
             Ok::<Self, AppError>(Self {
-                session: Arc::new(tokio::sync::Mutex::new(Some(session))),
-                r: Some(r),
-                w,
+                session: Some(session),
+                r: Some(SessionReader::Ssh(r)),
+                w: Arc::new(w),
                 server_key,
+                persistent,
             })
         };
 
@@ -614,11 +769,13 @@ impl SshSession {
     }
 
     /// Open a new interactive terminal on an existing SSH session, optionally
-    /// starting in `start_dir`. Uses `exec` instead of `request_shell` so the
-    /// `cd` happens before the shell prompt — no PTY echo artifacts.
-    /// The session handle is NOT stored — close() will be a no-op.
+    /// starting in `start_dir` instead of the connection's own start directory.
+    /// It runs a login shell even if the connection has a remote command,
+    /// which only the connection's own terminal runs.
+    /// The session handle is held as a lease, so close() leaves it open.
     pub async fn open_terminal_on(
-        session_handle: &tokio::sync::Mutex<client::Handle<SshClient>>,
+        session_handle: &SharedHandle,
+        connection: &Connection,
         cols: u16,
         rows: u16,
         start_dir: Option<&str>,
@@ -627,30 +784,139 @@ impl SshSession {
         // channel operations don't need the session handle.
         let channel = session_handle.lock().await.channel_open_session().await?;
 
+        Self::start_terminal(&channel, connection, cols, rows, start_dir, None).await?;
+
+        let (r, w) = channel.split();
+
+        Ok(Self {
+            session: Some(session_handle.clone()),
+            r: Some(SessionReader::Ssh(r)),
+            w: Arc::new(w),
+            server_key: Arc::new(OnceCell::new()),
+            persistent: None,
+        })
+    }
+
+    /// Start a local shell in a PTY, in `start_dir` or the connection's
+    /// start directory. There is no SSH connection behind it.
+    pub fn open_local_terminal(
+        connection: &Connection,
+        cols: u16,
+        rows: u16,
+        start_dir: Option<&str>,
+    ) -> Result<Self> {
+        let (pty, reader) = LocalPty::spawn(connection, cols, rows, start_dir)?;
+        Ok(Self::with_transport(pty, reader))
+    }
+
+    /// A session on a transport other than SSH
+    fn with_transport(transport: impl TerminalTransport + 'static, reader: ChunkReader) -> Self {
+        Self {
+            session: None,
+            r: Some(SessionReader::Chunks(reader)),
+            w: Arc::new(transport),
+            server_key: Arc::new(OnceCell::new()),
+            persistent: None,
+        }
+    }
+
+    /// Look for tmux or screen on the host and whether this connection's
+    /// session is still running there. Without either the terminal falls
+    /// back to a plain shell.
+    async fn probe_persistent_session(
+        session: &SharedHandle,
+        connection: &Connection,
+    ) -> Option<PersistentSession> {
+        let name = crate::persistent_session::session_name(&connection.id);
+        let command = crate::persistent_session::probe_command(&name);
+        match Self::exec_output(session, &command).await {
+            Ok(output) => {
+                let persistent = crate::persistent_session::parse_probe(&name, &output);
+                if persistent.is_none() {
+                    warn!(
+                        "Neither tmux nor screen found on {}, starting a plain shell",
+                        connection.host_port()
+                    );
+                }
+                persistent
+            }
+            Err(e) => {
+                warn!("Failed to probe for tmux or screen: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Run a command on its own channel and collect its standard output
+    async fn exec_output(session: &SharedHandle, command: &str) -> Result<String> {
+        let mut channel = session.lock().await.channel_open_session().await?;
+        channel.exec(true, command.as_bytes()).await?;
+        let mut output = Vec::new();
+        while let Some(msg) = channel.wait().await {
+            match msg {
+                ChannelMsg::Data { data } => output.extend_from_slice(&data),
+                ChannelMsg::ExitStatus { .. } | ChannelMsg::Close => break,
+                _ => {}
+            }
+        }
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+
+    /// The tmux or screen session behind the terminal, if any
+    pub fn persistent_session(&self) -> Option<&PersistentSession> {
+        self.persistent.as_ref()
+    }
+
+    /// End the tmux or screen session on the host, e.g. when closing the
+    /// terminal for good
+    pub async fn kill_persistent_session(&self) -> Result<()> {
+        let (Some(session), Some(persistent)) = (&self.session, &self.persistent) else {
+            return Ok(());
+        };
+        Self::exec_output(session, &persistent.kill_command()).await?;
+        Ok(())
+    }
+
+    /// Send the connection's environment, request a PTY and start either
+    /// `remote_command` (the configured one, or attaching to a persistent
+    /// session) or a login shell. A start directory is applied with `exec` rather than a typed
+    /// `cd` so it happens before the shell prompt — no PTY echo artifacts.
+    async fn start_terminal(
+        channel: &Channel<client::Msg>,
+        connection: &Connection,
+        cols: u16,
+        rows: u16,
+        start_dir: Option<&str>,
+        remote_command: Option<&str>,
+    ) -> Result<()> {
         let _ = channel.set_env(false, "LC_CTYPE", "C.UTF-8").await;
+        for (name, value) in &connection.env {
+            // Servers silently drop variables not listed in AcceptEnv
+            let _ = channel.set_env(false, name.as_str(), value.as_str()).await;
+        }
+
+        let term = connection.term.as_deref().unwrap_or(DEFAULT_TERM);
+        info!(
+            "Requesting {} PTY with size {} cols x {} rows",
+            term, cols, rows
+        );
         channel
-            .request_pty(true, "xterm-256color", cols as u32, rows as u32, 0, 0, &[])
+            .request_pty(true, term, cols as u32, rows as u32, 0, 0, &[])
             .await?;
 
-        match start_dir {
-            Some(dir) => {
-                let escaped = dir.replace('\'', "'\\''");
-                let cmd = format!("cd '{}' && exec $SHELL -l", escaped);
+        let start_dir = start_dir.or(connection.start_dir.as_deref());
+        match terminal_command(start_dir, remote_command) {
+            Some(cmd) => {
+                debug!("Executing terminal command: {}", cmd);
                 channel.exec(true, cmd.as_bytes()).await?;
             }
             None => {
+                debug!("Requesting shell");
                 channel.request_shell(true).await?;
             }
         }
 
-        let (r, w) = channel.split();
-
-        Ok(Self {
-            session: Arc::new(tokio::sync::Mutex::new(None)),
-            r: Some(r),
-            w,
-            server_key: Arc::new(OnceCell::new()),
-        })
+        Ok(())
     }
 
     pub async fn connect(connection: &Connection, cols: u16, rows: u16) -> Result<Self> {
@@ -660,42 +926,82 @@ impl SshSession {
             rows,
             None,
             &tokio_util::sync::CancellationToken::new(),
+            None,
         )
         .await
     }
 
     pub async fn request_size(&self, cols: u16, rows: u16) {
-        let _ = self.w.window_change(cols as u32, rows as u32, 0, 0).await;
+        let _ = self.w.resize(cols, rows).await;
     }
 
     pub async fn write_all(&self, data: &[u8]) -> Result<()> {
-        use tokio::io::AsyncWriteExt;
-        let mut writer = self.w.make_writer();
-
-        match writer.write_all(data).await {
-            Ok(_) => Ok(()),
-            Err(e) => match e.kind() {
-                std::io::ErrorKind::BrokenPipe
-                | std::io::ErrorKind::ConnectionReset
-                | std::io::ErrorKind::UnexpectedEof => {
-                    Err(AppError::ChannelClosedError(e.to_string()))
-                }
-                _ => Err(AppError::SshWriteError(format!(
-                    "Failed to write to SSH channel: {e}"
-                ))),
-            },
-        }
+        self.w.write_all(data).await
+    }
+
+    /// The session's input, for writers that outlive a borrow of the session
+    pub fn transport(&self) -> Arc<dyn TerminalTransport> {
+        self.w.clone()
     }
 
     /// Take the reader from this session. Returns `None` if already taken.
     /// The reader should be passed to `read_loop` in a separate task.
-    pub fn take_reader(&mut self) -> Option<russh::ChannelReadHalf> {
+    pub fn take_reader(&mut self) -> Option<SessionReader> {
         self.r.take()
     }
 
+    /// Read loop that feeds the session's output to `processor` until the
+    /// session ends or `cancel` fires
+    pub(crate) async fn read_loop<B: ByteProcessor>(
+        reader: SessionReader,
+        processor: Arc<tokio::sync::Mutex<B>>,
+        cancel: tokio_util::sync::CancellationToken,
+        event_tx: Option<tokio::sync::mpsc::Sender<crate::AppEvent>>,
+    ) {
+        match reader {
+            SessionReader::Ssh(reader) => {
+                Self::read_channel(reader, processor, cancel, event_tx).await
+            }
+            SessionReader::Chunks(reader) => {
+                Self::read_chunks(reader, processor, cancel, event_tx).await
+            }
+        }
+    }
+
+    /// Read loop for transports other than SSH. The other side closing the
+    /// session ends it like an SSH exit status does; a read error is a lost
+    /// connection.
+    async fn read_chunks<B: ByteProcessor>(
+        mut reader: ChunkReader,
+        processor: Arc<tokio::sync::Mutex<B>>,
+        cancel: tokio_util::sync::CancellationToken,
+        event_tx: Option<tokio::sync::mpsc::Sender<crate::AppEvent>>,
+    ) {
+        let mut ended = crate::AppEvent::Disconnect;
+        while let Ok(Some(chunk)) = reader.recv().or_cancel(&cancel).await {
+            let data = match chunk {
+                Ok(data) => data,
+                Err(e) => {
+                    warn!("Session read failed: {}", e);
+                    ended = crate::AppEvent::ConnectionLost;
+                    break;
+                }
+            };
+            processor.lock().await.process_bytes(&data);
+            if let Some(tx) = &event_tx {
+                let _ = tx.send(crate::AppEvent::TerminalUpdate).await;
+            }
+        }
+        if !cancel.is_cancelled()
+            && let Some(tx) = &event_tx
+        {
+            let _ = tx.send(ended).await;
+        }
+    }
+
     /// Read loop that processes incoming SSH channel messages.
     /// Takes ownership of the reader and uses stream-based iteration with cancellation support.
-    pub(crate) async fn read_loop<B: ByteProcessor>(
+    async fn read_channel<B: ByteProcessor>(
         reader: russh::ChannelReadHalf,
         processor: Arc<tokio::sync::Mutex<B>>,
         cancel: tokio_util::sync::CancellationToken,
@@ -712,6 +1018,10 @@ impl SshSession {
 
         // Take messages until cancellation
         let mut msg_stream = std::pin::pin!(msg_stream.take_until(cancel.cancelled()));
+        // Whether the remote shell reported how it ended, or at least closed
+        // its output before the channel
+        let mut exited = false;
+        let mut eof = false;
 
         while let Some(msg) = msg_stream.next().await {
             match msg {
@@ -725,43 +1035,73 @@ impl SshSession {
                         let _ = tx.send(crate::AppEvent::TerminalUpdate).await;
                     }
                 }
-                ChannelMsg::Eof | ChannelMsg::Close | ChannelMsg::ExitStatus { .. } => {
+                ChannelMsg::ExitStatus { .. } | ChannelMsg::ExitSignal { .. } => {
                     // Notify the main loop that the connection has been disconnected
+                    exited = true;
                     if let Some(tx) = &event_tx {
                         let _ = tx.send(crate::AppEvent::Disconnect).await;
                     }
                     break;
                 }
+                // Wait for the exit status that usually follows EOF
+                ChannelMsg::Eof => eof = true,
+                // Some servers close the channel after EOF without an exit
+                // status; that is still a clean end of the session
+                ChannelMsg::Close => {
+                    if eof {
+                        exited = true;
+                        if let Some(tx) = &event_tx {
+                            let _ = tx.send(crate::AppEvent::Disconnect).await;
+                        }
+                    }
+                    break;
+                }
                 _ => {}
             }
         }
+
+        // The channel went away without the shell exiting (network drop,
+        // server restart, keepalive timeout)
+        if !exited
+            && !cancel.is_cancelled()
+            && let Some(tx) = &event_tx
+        {
+            let _ = tx.send(crate::AppEvent::ConnectionLost).await;
+        }
     }
 
+    /// Disconnect the SSH connection if this session is its only user.
+    /// Connections shared with the `SessionPool` or a file explorer are left
+    /// open; the pool disconnects them once they go idle.
     pub async fn close(&self) -> Result<()> {
         debug!("Closing SSH session");
-        let guard = self.session.lock().await;
-        if let Some(session) = guard.as_ref() {
-            // If the session is already closed, return early
-            if session.is_closed() {
-                debug!("SSH session already closed");
-                return Ok(());
-            }
-            session
-                .disconnect(Disconnect::ByApplication, "", "")
-                .await
-                .map_err(|e| {
-                    error!("Failed to disconnect SSH session: {}", e);
-                    AppError::SshConnectionError(format!("Failed to disconnect: {e}"))
-                })?;
-            info!("SSH session closed successfully");
+        let Some(handle) = self.session.as_ref() else {
+            return Ok(());
+        };
+        if Arc::strong_count(handle) > 1 {
+            debug!("SSH connection is shared, releasing it to the pool");
+            return Ok(());
+        }
+        let session = handle.lock().await;
+        // If the session is already closed, return early
+        if session.is_closed() {
+            debug!("SSH session already closed");
+            return Ok(());
         }
+        session
+            .disconnect(Disconnect::ByApplication, "", "")
+            .await
+            .map_err(|e| {
+                error!("Failed to disconnect SSH session: {}", e);
+                AppError::SshConnectionError(format!("Failed to disconnect: {e}"))
+            })?;
+        info!("SSH session closed successfully");
         Ok(())
     }
 
     #[allow(dead_code)]
     pub async fn close_channel(&self) -> Result<()> {
-        self.w.close().await?;
-        Ok(())
+        self.w.close().await
     }
 
     /// Get the server public key that was received during connection
@@ -1078,16 +1418,18 @@ impl SshSession {
 
     /// Read the first `len` bytes from a remote file via SFTP.
     ///
-    /// Opens a new SSH/SFTP session, reads up to `len` bytes from offset 0,
-    /// and returns the data. Useful for binary detection without downloading
-    /// the entire file.
+    /// Opens an SFTP channel on the pooled connection, reads up to `len` bytes
+    /// from offset 0, and returns the data. Useful for binary detection without
+    /// downloading the entire file.
     pub async fn sftp_read_head(
+        pool: &SessionPool,
         connection: &Connection,
         remote_path: &str,
         len: usize,
     ) -> Result<Vec<u8>> {
         let cancel = tokio_util::sync::CancellationToken::new();
-        let sftp = Self::setup_sftp_session(None, connection, None, &cancel).await?;
+        let (channel, _lease, _) = pool.open_session_channel(connection, None, &cancel).await?;
+        let sftp = Self::setup_sftp_session(Some(channel), connection, None, &cancel).await?;
 
         let handle = sftp
             .open(remote_path, OpenFlags::READ, FileAttributes::empty())
@@ -1122,18 +1464,22 @@ impl SshSession {
         connection: &Connection,
         service_host: &str,
         service_port: u16,
+        pool: &SessionPool,
     ) -> Result<(JoinHandle<()>, CancellationToken)> {
         info!(
             "Setting up port forwarding: {}:{} -> {}:{}",
             local_addr, local_port, service_host, service_port
         );
-        let (session, _) = Self::new_session(connection).await?;
+        // Make sure the connection works before binding the listener
+        pool.get(connection, None, &CancellationToken::new())
+            .await?;
         let cancel_token = CancellationToken::new();
         let cancel_token_for_task = cancel_token.clone();
 
         let local_addr = local_addr.to_string();
         let service_host = service_host.to_string();
         let connection = connection.clone();
+        let pool = pool.clone();
 
         let local_listener = match TcpListener::bind((local_addr.as_str(), local_port)).await {
             Ok(listener) => {
@@ -1155,7 +1501,6 @@ impl SshSession {
         };
 
         let handle = tokio::spawn(async move {
-            let mut session = session;
             loop {
                 tokio::select! {
                     _ = cancel_token_for_task.cancelled() => {
@@ -1164,50 +1509,26 @@ impl SshSession {
                     result = local_listener.accept() => {
                         match result {
                             Ok((mut local_socket, _)) => {
-                                let mut attempts = 0;
-                                let ssh_channel = loop {
-                                    match session
-                                        .channel_open_direct_tcpip(
-                                            service_host.clone(),
-                                            service_port as u32,
-                                            local_addr.clone(),
-                                            local_port as u32,
-                                        )
-                                        .await
-                                    {
-                                        Ok(channel) => {
-                                            debug!("Port forwarding channel opened successfully");
-                                            break Some(channel);
-                                        }
-                                        Err(e) => {
-                                            warn!("Failed to open SSH forwarding channel: {}", e);
-
-                                            let should_recreate = Self::is_port_forwarding_session_error(&session, &e);
-
-                                            if should_recreate && attempts < 3 {
-                                                debug!("Attempting to recreate SSH session for port forwarding (attempt {})", attempts + 1);
-                                                match Self::new_session(&connection).await {
-                                                    Ok((new_session, _)) => {
-                                                        info!("SSH session recreated successfully for port forwarding");
-                                                        session = new_session;
-                                                        attempts += 1;
-                                                        continue;
-                                                    }
-                                                    Err(new_err) => {
-                                                        error!("Failed to recreate SSH session for port forwarding: {}", new_err);
-                                                    }
-                                                }
-                                            }
-
-                                            break None;
-                                        }
+                                let (ssh_channel, lease) = match pool
+                                    .open_direct_tcpip(
+                                        &connection,
+                                        &service_host,
+                                        service_port as u32,
+                                        &local_addr,
+                                        local_port as u32,
+                                    )
+                                    .await
+                                {
+                                    Ok(opened) => {
+                                        debug!("Port forwarding channel opened successfully");
+                                        opened
+                                    }
+                                    Err(e) => {
+                                        warn!("Failed to open SSH forwarding channel: {}", e);
+                                        // Unable to establish forwarding channel; close the local socket and continue.
+                                        let _ = local_socket.shutdown().await;
+                                        continue;
                                     }
-                                };
-
-                                let Some(ssh_channel) = ssh_channel else {
-                                    // Unable to establish forwarding channel; close the local socket and continue.
-                                    let _ = local_socket.shutdown().await;
-                                    continue;
                                 };
 
                                 let mut ssh_stream = ssh_channel.into_stream();
@@ -1215,6 +1536,8 @@ impl SshSession {
                                 // Handle the connection in a separate task
                                 let cancel_for_connection = cancel_token_for_task.clone();
                                 tokio::spawn(async move {
+                                    // Keep the pooled connection alive while the stream is open
+                                    let _lease = lease;
                                     tokio::select! {
                                         _ = cancel_for_connection.cancelled() => {
                                             // Connection cancelled
@@ -1248,6 +1571,7 @@ impl SshSession {
         connection: &Connection,
         service_host: &str,
         service_port: u16,
+        pool: &SessionPool,
     ) -> Result<(JoinHandle<()>, CancellationToken)> {
         let remote_bind = remote_bind_addr.unwrap_or("127.0.0.1");
         info!(
@@ -1259,56 +1583,28 @@ impl SshSession {
         let (forwarded_tx, forwarded_rx) = tokio::sync::mpsc::unbounded_channel();
 
         let cancel_token = CancellationToken::new();
-        let cancel_token_for_session = cancel_token.clone();
 
-        // Create a new session with forwarding channel
-        let (mut session, _) = Self::new_session_with_timeout_and_forwarding(
-            connection,
-            None,
-            &cancel_token_for_session,
-            Some(forwarded_tx),
-        )
-        .await?;
+        let (session, _) = pool.get(connection, None, &cancel_token).await?;
 
-        let remote_bind = remote_bind.to_string();
+        // Create a handler that will process forwarded connections
+        let mut forwarding_handler = RemoteForwardingHandler {
+            service_host: service_host.to_string(),
+            service_port,
+            pool: pool.clone(),
+            connection: connection.clone(),
+            session,
+            remote_bind: remote_bind.to_string(),
+            remote_port,
+            route_port: remote_port as u32,
+            cancel_token: cancel_token.clone(),
+            forwarded_tx,
+            forwarded_rx,
+        };
 
         // Request remote port forwarding
-        match session
-            .tcpip_forward(&remote_bind, remote_port as u32)
-            .await
-        {
-            Ok(bound_port) => {
-                info!(
-                    "Remote port forwarding established on {}:{} (actual port: {})",
-                    remote_bind, remote_port, bound_port
-                );
-            }
-            Err(e) => {
-                error!("Failed to establish remote port forwarding: {}", e);
-                return Err(AppError::PortForwardingError(format!(
-                    "Failed to request remote port forwarding: {e}"
-                )));
-            }
-        }
-
-        // Clone these for the async task
-        let service_host_clone = service_host.to_string();
-        let service_port_clone = service_port;
-        let remote_bind_clone = remote_bind.clone();
-        let cancel_token_for_task = cancel_token.clone();
+        forwarding_handler.request_forward().await?;
 
         let handle = tokio::spawn(async move {
-            // Create a handler that will process forwarded connections
-            let mut forwarding_handler = RemoteForwardingHandler {
-                service_host: service_host_clone,
-                service_port: service_port_clone,
-                session,
-                remote_bind: remote_bind_clone,
-                remote_port,
-                cancel_token: cancel_token_for_task,
-                forwarded_rx,
-            };
-
             if let Err(e) = forwarding_handler.run().await {
                 error!("Remote forwarding handler error: {}", e);
             }
@@ -1323,6 +1619,7 @@ impl SshSession {
         local_addr: &str,
         local_port: u16,
         connection: &Connection,
+        pool: &SessionPool,
     ) -> Result<(JoinHandle<()>, CancellationToken)> {
         info!(
             "Setting up dynamic SOCKS5 proxy on {}:{}",
@@ -1334,6 +1631,7 @@ impl SshSession {
 
         let local_addr = local_addr.to_string();
         let connection = connection.clone();
+        let pool = pool.clone();
 
         let local_listener = match TcpListener::bind((local_addr.as_str(), local_port)).await {
             Ok(listener) => {
@@ -1365,14 +1663,16 @@ impl SshSession {
 
                                 let cancel_for_connection = cancel_token_for_task.clone();
                                 let connection_clone = connection.clone();
+                                let pool_clone = pool.clone();
 
                                 // Handle SOCKS5 connection in a separate task
-                                // Each connection gets its own SSH session
+                                // Channels are multiplexed over the pooled SSH session
                                 tokio::spawn(async move {
                                     if let Err(e) = Self::handle_socks5_connection(
                                         local_socket,
                                         cancel_for_connection,
                                         connection_clone,
+                                        pool_clone,
                                     ).await {
                                         debug!("SOCKS5 connection error: {}", e);
                                     }
@@ -1396,14 +1696,76 @@ impl SshSession {
 struct RemoteForwardingHandler {
     service_host: String,
     service_port: u16,
-    session: client::Handle<SshClient>,
+    pool: SessionPool,
+    connection: Connection,
+    session: SharedHandle,
     remote_bind: String,
     remote_port: u16,
+    // Port the server actually bound; differs from remote_port when it is 0
+    route_port: u32,
     cancel_token: CancellationToken,
+    forwarded_tx: tokio::sync::mpsc::UnboundedSender<Channel<client::Msg>>,
     forwarded_rx: tokio::sync::mpsc::UnboundedReceiver<Channel<client::Msg>>,
 }
 
 impl RemoteForwardingHandler {
+    /// Ask the server to listen on the remote port and route its connections
+    /// to this handler
+    async fn request_forward(&mut self) -> Result<()> {
+        // Register before the request so early connections are not dropped
+        self.pool
+            .register_forward(
+                &self.connection.id,
+                self.remote_port as u32,
+                self.forwarded_tx.clone(),
+            )
+            .await;
+
+        let result = self
+            .session
+            .lock()
+            .await
+            .tcpip_forward(&self.remote_bind, self.remote_port as u32)
+            .await;
+
+        match result {
+            Ok(bound_port) => {
+                info!(
+                    "Remote port forwarding established on {}:{} (actual port: {})",
+                    self.remote_bind, self.remote_port, bound_port
+                );
+                let bound_port = if self.remote_port == 0 {
+                    bound_port
+                } else {
+                    self.remote_port as u32
+                };
+                if bound_port != self.remote_port as u32 {
+                    self.pool
+                        .unregister_forward(&self.connection.id, self.remote_port as u32)
+                        .await;
+                    self.pool
+                        .register_forward(
+                            &self.connection.id,
+                            bound_port,
+                            self.forwarded_tx.clone(),
+                        )
+                        .await;
+                }
+                self.route_port = bound_port;
+                Ok(())
+            }
+            Err(e) => {
+                error!("Failed to establish remote port forwarding: {}", e);
+                self.pool
+                    .unregister_forward(&self.connection.id, self.remote_port as u32)
+                    .await;
+                Err(AppError::PortForwardingError(format!(
+                    "Failed to request remote port forwarding: {e}"
+                )))
+            }
+        }
+    }
+
     /// Run the forwarding handler, processing incoming connections
     async fn run(&mut self) -> Result<()> {
         info!(
@@ -1415,8 +1777,9 @@ impl RemoteForwardingHandler {
             tokio::select! {
                 _ = self.cancel_token.cancelled() => {
                     info!("Remote port forwarding task cancelled");
-                    // Cancel the remote forwarding
-                    let _ = self.session.cancel_tcpip_forward(&self.remote_bind, self.remote_port as u32).await;
+                    // Cancel the remote forwarding on the port the server bound
+                    let _ = self.session.lock().await.cancel_tcpip_forward(&self.remote_bind, self.route_port).await;
+                    self.pool.unregister_forward(&self.connection.id, self.route_port).await;
                     break;
                 }
                 Some(channel) = self.forwarded_rx.recv() => {
@@ -1441,11 +1804,13 @@ impl RemoteForwardingHandler {
                 }
                 _ = tokio::time::sleep(Duration::from_secs(30)) => {
                     // Periodic check if session is still alive
-                    if self.session.is_closed() {
-                        warn!("SSH session closed, remote forwarding terminated");
-                        return Err(AppError::PortForwardingError(
-                            "SSH session closed".to_string(),
-                        ));
+                    if self.session.lock().await.is_closed() {
+                        warn!("SSH session closed, re-establishing remote forwarding");
+                        if let Err(e) = self.reconnect().await {
+                            self.pool.unregister_forward(&self.connection.id, self.route_port).await;
+                            return Err(e);
+                        }
+                        continue;
                     }
                     debug!("Remote forwarding session still alive");
                 }
@@ -1454,6 +1819,23 @@ impl RemoteForwardingHandler {
 
         Ok(())
     }
+
+    /// Re-issue the forward request on a fresh pooled connection
+    async fn reconnect(&mut self) -> Result<()> {
+        self.pool
+            .invalidate(&self.connection.id, &self.session)
+            .await;
+        let (session, _) = self
+            .pool
+            .get(&self.connection, None, &self.cancel_token)
+            .await
+            .map_err(|e| AppError::PortForwardingError(format!("SSH session closed: {e}")))?;
+        self.session = session;
+        self.pool
+            .unregister_forward(&self.connection.id, self.route_port)
+            .await;
+        self.request_forward().await
+    }
 }
 
 impl SshSession {
@@ -1514,9 +1896,8 @@ impl SshSession {
         mut local_socket: TcpStream,
         cancel_token: CancellationToken,
         connection: Connection,
+        pool: SessionPool,
     ) -> Result<()> {
-        // Create a session for this SOCKS5 connection
-        let (mut session, _) = Self::new_session(&connection).await?;
         // SOCKS5 handshake: receive greeting
         let mut buf = [0u8; 2];
         tokio::select! {
@@ -1685,52 +2066,31 @@ impl SshSession {
 
         debug!("SOCKS5 CONNECT to {}:{}", dest_addr, dest_port);
 
-        // Open SSH channel to destination
-        let mut attempts = 0;
-        let ssh_channel = loop {
-            match session
-                .channel_open_direct_tcpip(
-                    dest_addr.clone(),
-                    dest_port as u32,
-                    "127.0.0.1".to_string(), // Originator address for SOCKS5
-                    0,                       // local port doesn't matter for SOCKS5
-                )
-                .await
-            {
-                Ok(channel) => {
-                    debug!(
-                        "SSH channel opened for SOCKS5 connection to {}:{}",
-                        dest_addr, dest_port
-                    );
-                    break Some(channel);
-                }
-                Err(e) => {
-                    warn!("Failed to open SSH channel for SOCKS5: {}", e);
-
-                    if Self::is_port_forwarding_session_error(&session, &e) && attempts < 3 {
-                        debug!(
-                            "Attempting to recreate SSH session (attempt {})",
-                            attempts + 1
-                        );
-                        match Self::new_session(&connection).await {
-                            Ok((new_session, _)) => {
-                                info!("SSH session recreated successfully");
-                                session = new_session;
-                                attempts += 1;
-                                continue;
-                            }
-                            Err(e) => {
-                                error!("Failed to recreate SSH session: {}", e);
-                            }
-                        }
-                    }
-
-                    break None;
-                }
+        // Open SSH channel to destination on the pooled session
+        let ssh_channel = match pool
+            .open_direct_tcpip(
+                &connection,
+                &dest_addr,
+                dest_port as u32,
+                "127.0.0.1", // Originator address for SOCKS5
+                0,           // local port doesn't matter for SOCKS5
+            )
+            .await
+        {
+            Ok(opened) => {
+                debug!(
+                    "SSH channel opened for SOCKS5 connection to {}:{}",
+                    dest_addr, dest_port
+                );
+                Some(opened)
+            }
+            Err(e) => {
+                warn!("Failed to open SSH channel for SOCKS5: {}", e);
+                None
             }
         };
 
-        let Some(ssh_channel) = ssh_channel else {
+        let Some((ssh_channel, _lease)) = ssh_channel else {
             // Send SOCKS5 error response
             let _ = local_socket
                 .write_all(&[5, 1, 0, 1, 0, 0, 0, 0, 0, 0])
@@ -1767,10 +2127,8 @@ impl SshSession {
         Ok(())
     }
 
-    fn is_port_forwarding_session_error(
-        session: &client::Handle<SshClient>,
-        err: &RusshError,
-    ) -> bool {
+    /// Whether a channel open failure means the connection itself is gone
+    fn is_session_error(session: &client::Handle<SshClient>, err: &RusshError) -> bool {
         if session.is_closed() {
             return true;
         }
@@ -1795,12 +2153,19 @@ type ActiveForwardMap = HashMap<String, (JoinHandle<()>, CancellationToken)>;
 /// Runtime management for port forwarding sessions
 pub struct PortForwardingRuntime {
     active_forwards: ActiveForwardMap,
+    pool: SessionPool,
 }
 
 impl PortForwardingRuntime {
     pub fn new() -> Self {
+        Self::with_pool(SessionPool::new())
+    }
+
+    /// Create a runtime whose forwards share connections from `pool`
+    pub fn with_pool(pool: SessionPool) -> Self {
         Self {
             active_forwards: HashMap::new(),
+            pool,
         }
     }
 
@@ -1828,6 +2193,7 @@ impl PortForwardingRuntime {
                     connection,
                     &port_forward.service_host,
                     port_forward.service_port,
+                    &self.pool,
                 )
                 .await?
             }
@@ -1838,6 +2204,7 @@ impl PortForwardingRuntime {
                     connection,
                     &port_forward.service_host,
                     port_forward.service_port,
+                    &self.pool,
                 )
                 .await?
             }
@@ -1846,6 +2213,7 @@ impl PortForwardingRuntime {
                     &port_forward.local_addr,
                     port_forward.local_port,
                     connection,
+                    &self.pool,
                 )
                 .await?
             }
@@ -1905,6 +2273,375 @@ impl Default for PortForwardingRuntime {
     }
 }
 
+/// A pooled connection together with what is needed to decide whether it can
+/// still be handed out
+struct PooledSession {
+    handle: SharedHandle,
+    server_key: Arc<OnceCell<String>>,
+    // `user@host:port` the handle was opened for; a mismatch means the
+    // connection was edited and must be re-established
+    fingerprint: String,
+    last_used: Instant,
+}
+
+/// Per-connection pool entry. The slot lock serialises handshakes so
+/// concurrent requests for the same connection share one.
+#[derive(Default)]
+struct PoolSlot {
+    session: tokio::sync::Mutex<Option<PooledSession>>,
+    routes: ForwardedRoutes,
+}
+
+struct SessionPoolInner {
+    slots: tokio::sync::Mutex<HashMap<String, Arc<PoolSlot>>>,
+    idle_timeout: Duration,
+    reaper_started: AtomicBool,
+}
+
+/// Which kind of channel to open on a pooled connection
+#[derive(Clone, Copy)]
+enum PooledChannel<'a> {
+    Session,
+    DirectTcpip {
+        host: &'a str,
+        port: u32,
+        originator_address: &'a str,
+        originator_port: u32,
+    },
+}
+
+/// One SSH connection per `Connection::id`, shared by the terminal, the file
+/// explorer, file editing and port forwards, which all open their channels on
+/// it. Dead connections are replaced on the next request, and connections
+/// without any lease holder are disconnected after the idle timeout.
+#[derive(Clone)]
+pub struct SessionPool {
+    inner: Arc<SessionPoolInner>,
+}
+
+impl SessionPool {
+    pub fn new() -> Self {
+        Self::with_idle_timeout(DEFAULT_POOL_IDLE_TIMEOUT)
+    }
+
+    pub fn with_idle_timeout(idle_timeout: Duration) -> Self {
+        Self {
+            inner: Arc::new(SessionPoolInner {
+                slots: tokio::sync::Mutex::new(HashMap::new()),
+                idle_timeout,
+                reaper_started: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// What a pooled session was opened with. Editing the address, the
+    /// credentials or the SSH options opens a new session; the secrets only
+    /// go into a hash.
+    fn fingerprint(connection: &Connection) -> String {
+        let mut hasher = DefaultHasher::new();
+        match &connection.auth_method {
+            AuthMethod::Password(password) => ("password", password.as_str()).hash(&mut hasher),
+            AuthMethod::PublicKey {
+                private_key_path,
+                passphrase,
+            } => ("public_key", private_key_path, passphrase.as_deref()).hash(&mut hasher),
+            AuthMethod::AutoLoadKey => "auto_load_key".hash(&mut hasher),
+            AuthMethod::None => "none".hash(&mut hasher),
+        }
+        connection.ssh_options.hash(&mut hasher);
+        format!(
+            "{}@{}#{:016x}",
+            connection.username,
+            connection.host_port(),
+            hasher.finish()
+        )
+    }
+
+    async fn slot(&self, connection_id: &str) -> Arc<PoolSlot> {
+        let mut slots = self.inner.slots.lock().await;
+        slots.entry(connection_id.to_string()).or_default().clone()
+    }
+
+    /// Get the shared handle for `connection`, connecting if there is no live one.
+    /// The returned handle is a lease: the pool won't reap it while a clone is alive.
+    pub(crate) async fn get(
+        &self,
+        connection: &Connection,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
+    ) -> Result<(SharedHandle, Arc<OnceCell<String>>)> {
+        if !connection.kind.is_ssh() {
+            return Err(AppError::ValidationError(format!(
+                "SFTP and port forwarding need SSH, but {} is a {} connection",
+                connection.display_name,
+                connection.kind.label()
+            )));
+        }
+        self.ensure_reaper();
+
+        let slot = self.slot(&connection.id).await;
+        let mut entry = slot.session.lock().await;
+        let fingerprint = Self::fingerprint(connection);
+
+        let alive = match entry.as_ref() {
+            Some(pooled) if pooled.fingerprint == fingerprint => {
+                !pooled.handle.lock().await.is_closed()
+            }
+            _ => false,
+        };
+        if alive && let Some(pooled) = entry.as_mut() {
+            pooled.last_used = Instant::now();
+            return Ok((pooled.handle.clone(), pooled.server_key.clone()));
+        }
+
+        if entry.take().is_some() {
+            debug!(
+                "Replacing pooled SSH session for {}",
+                connection.host_port()
+            );
+        }
+
+        let (handle, server_key) =
+            SshSession::new_session_with_routes(connection, timeout, cancel, slot.routes.clone())
+                .await?;
+        let handle = Arc::new(tokio::sync::Mutex::new(handle));
+        *entry = Some(PooledSession {
+            handle: handle.clone(),
+            server_key: server_key.clone(),
+            fingerprint,
+            last_used: Instant::now(),
+        });
+        info!("Pooled SSH session for {}", connection.host_port());
+
+        Ok((handle, server_key))
+    }
+
+    /// Drop `handle` from the pool so the next request reconnects. Does
+    /// nothing if the pool already moved on to another handle.
+    pub(crate) async fn invalidate(&self, connection_id: &str, handle: &SharedHandle) {
+        let slot = self.slot(connection_id).await;
+        let mut entry = slot.session.lock().await;
+        if entry
+            .as_ref()
+            .is_some_and(|pooled| Arc::ptr_eq(&pooled.handle, handle))
+        {
+            debug!("Invalidating pooled SSH session {}", connection_id);
+            entry.take();
+        }
+    }
+
+    /// Open a session channel on the pooled connection
+    pub(crate) async fn open_session_channel(
+        &self,
+        connection: &Connection,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
+    ) -> Result<(Channel<client::Msg>, SharedHandle, Arc<OnceCell<String>>)> {
+        self.open_channel(connection, timeout, cancel, PooledChannel::Session)
+            .await
+    }
+
+    /// Open a direct-tcpip channel (local and dynamic forwards) on the pooled connection
+    pub(crate) async fn open_direct_tcpip(
+        &self,
+        connection: &Connection,
+        host: &str,
+        port: u32,
+        originator_address: &str,
+        originator_port: u32,
+    ) -> Result<(Channel<client::Msg>, SharedHandle)> {
+        let kind = PooledChannel::DirectTcpip {
+            host,
+            port,
+            originator_address,
+            originator_port,
+        };
+        let (channel, handle, _) = self
+            .open_channel(connection, None, &CancellationToken::new(), kind)
+            .await?;
+        Ok((channel, handle))
+    }
+
+    /// Open a channel, reconnecting when the failure means the pooled
+    /// connection is dead
+    async fn open_channel(
+        &self,
+        connection: &Connection,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
+        kind: PooledChannel<'_>,
+    ) -> Result<(Channel<client::Msg>, SharedHandle, Arc<OnceCell<String>>)> {
+        let mut attempts = 0;
+        loop {
+            let (handle, server_key) = self.get(connection, timeout, cancel).await?;
+
+            let result = {
+                let session = handle.lock().await;
+                let opened = match kind {
+                    PooledChannel::Session => session.channel_open_session().await,
+                    PooledChannel::DirectTcpip {
+                        host,
+                        port,
+                        originator_address,
+                        originator_port,
+                    } => {
+                        session
+                            .channel_open_direct_tcpip(
+                                host.to_string(),
+                                port,
+                                originator_address.to_string(),
+                                originator_port,
+                            )
+                            .await
+                    }
+                };
+                opened.map_err(|e| (SshSession::is_session_error(&session, &e), e))
+            };
+
+            match result {
+                Ok(channel) => return Ok((channel, handle, server_key)),
+                Err((true, e)) if attempts < MAX_POOL_RECONNECT_ATTEMPTS => {
+                    attempts += 1;
+                    warn!(
+                        "Pooled SSH session for {} failed ({}), reconnecting (attempt {})",
+                        connection.host_port(),
+                        e,
+                        attempts
+                    );
+                    self.invalidate(&connection.id, &handle).await;
+                }
+                Err((_, e)) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Route forwarded-tcpip channels for `port` on this connection to `tx`
+    async fn register_forward(
+        &self,
+        connection_id: &str,
+        port: u32,
+        tx: mpsc::UnboundedSender<Channel<client::Msg>>,
+    ) {
+        let slot = self.slot(connection_id).await;
+        slot.routes.lock().await.insert(port, tx);
+    }
+
+    async fn unregister_forward(&self, connection_id: &str, port: u32) {
+        let slot = self.slot(connection_id).await;
+        slot.routes.lock().await.remove(&port);
+    }
+
+    /// Spawn the idle reaper on first use. It holds only a weak reference so
+    /// it stops once the last pool clone is dropped.
+    fn ensure_reaper(&self) {
+        if self.inner.reaper_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let inner = Arc::downgrade(&self.inner);
+        let period =
+            (self.inner.idle_timeout / 2).clamp(Duration::from_secs(1), Duration::from_secs(30));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(inner) = inner.upgrade() else {
+                    break;
+                };
+                SessionPool { inner }.reap_idle().await;
+            }
+        });
+    }
+
+    /// Disconnect pooled connections nobody has leased for longer than the idle timeout
+    async fn reap_idle(&self) {
+        let slots: Vec<(String, Arc<PoolSlot>)> = self
+            .inner
+            .slots
+            .lock()
+            .await
+            .iter()
+            .map(|(id, slot)| (id.clone(), slot.clone()))
+            .collect();
+
+        for (id, slot) in slots {
+            // A slot that is busy connecting is in use
+            let Ok(mut entry) = slot.session.try_lock() else {
+                continue;
+            };
+            let Some(pooled) = entry.as_mut() else {
+                continue;
+            };
+            if Arc::strong_count(&pooled.handle) > 1 {
+                // Idle time counts from when the last lease is released
+                pooled.last_used = Instant::now();
+                continue;
+            }
+            if pooled.last_used.elapsed() < self.inner.idle_timeout {
+                continue;
+            }
+            if let Some(pooled) = entry.take() {
+                debug!("Disconnecting idle pooled SSH session {}", id);
+                let session = pooled.handle.lock().await;
+                if !session.is_closed() {
+                    let _ = session.disconnect(Disconnect::ByApplication, "", "").await;
+                }
+            }
+        }
+    }
+}
+
+impl Default for SessionPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build the command that starts a terminal, or `None` for a plain login shell
+fn terminal_command(start_dir: Option<&str>, remote_command: Option<&str>) -> Option<String> {
+    let remote_command = remote_command.map(str::trim).filter(|cmd| !cmd.is_empty());
+    match (start_dir, remote_command) {
+        (None, None) => None,
+        (None, Some(cmd)) => Some(cmd.to_string()),
+        (Some(dir), cmd) => Some(format!(
+            "cd {} && {}",
+            crate::utils::shell_quote(dir),
+            cmd.unwrap_or("exec $SHELL -l")
+        )),
+    }
+}
+
+/// Resolve a preferred algorithm list from configured names. Returns `None`
+/// when nothing is configured. A list made only of `+name` entries extends
+/// `defaults`; otherwise it replaces them.
+fn preferred_algorithms<T: Clone>(
+    kind: &str,
+    names: &[String],
+    defaults: &[T],
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Option<Vec<T>>> {
+    if names.is_empty() {
+        return Ok(None);
+    }
+
+    let mut algorithms = if names.iter().all(|name| name.starts_with('+')) {
+        defaults.to_vec()
+    } else {
+        Vec::new()
+    };
+    for name in names {
+        let name = name.trim_start_matches('+').trim();
+        let algorithm = parse(name).ok_or_else(|| {
+            AppError::ValidationError(format!("Unsupported {kind} algorithm '{name}'"))
+        })?;
+        algorithms.push(algorithm);
+    }
+
+    Ok(Some(algorithms))
+}
+
 /// Extension trait for futures that can be cancelled
 pub trait OrCancelExt: Sized {
     type Output;
@@ -3477,6 +4214,7 @@ kWCczR3NfAIXj6HNJ5DEAAAAEHRlc3RfY2xpZW50QHRlc3QBAgMEBQ==\n\
             &conn,
             "127.0.0.1",
             http_port,
+            &SessionPool::new(),
         )
         .await
         .expect("failed to start port forwarding");
@@ -3561,6 +4299,7 @@ kWCczR3NfAIXj6HNJ5DEAAAAEHRlc3RfY2xpZW50QHRlc3QBAgMEBQ==\n\
             &conn,
             "127.0.0.1",
             http_port,
+            &SessionPool::new(),
         )
         .await
         .expect("failed to start port forwarding");
@@ -3598,4 +4337,125 @@ kWCczR3NfAIXj6HNJ5DEAAAAEHRlc3RfY2xpZW50QHRlc3QBAgMEBQ==\n\
         assert_eq!(disconnects, 1, "expected exactly one forced disconnect");
         server.shutdown().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_session_pool_shares_and_reconnects() {
+        let server = EmbeddedSshServer::start("tester", "testerpass")
+            .await
+            .expect("failed to start embedded server");
+
+        let conn = Connection::new(
+            "127.0.0.1".to_string(),
+            server.port(),
+            "tester".to_string(),
+            AuthMethod::Password("testerpass".to_string().into()),
+        );
+        let pool = SessionPool::new();
+        let cancel = CancellationToken::new();
+
+        let (first, _) = pool
+            .get(&conn, None, &cancel)
+            .await
+            .expect("failed to get pooled session");
+        let (second, _) = pool
+            .get(&conn, None, &cancel)
+            .await
+            .expect("failed to get pooled session");
+        assert!(
+            Arc::ptr_eq(&first, &second),
+            "expected both requests to share one session"
+        );
+
+        first
+            .lock()
+            .await
+            .disconnect(Disconnect::ByApplication, "", "")
+            .await
+            .expect("failed to disconnect pooled session");
+        for _ in 0..50 {
+            if first.lock().await.is_closed() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let (_channel, third, _) = pool
+            .open_session_channel(&conn, None, &cancel)
+            .await
+            .expect("pool did not reconnect after the session closed");
+        assert!(
+            !Arc::ptr_eq(&first, &third),
+            "expected a fresh session after disconnect"
+        );
+
+        server.shutdown().await.unwrap();
+    }
+
+    #[test]
+    fn test_pool_fingerprint_covers_auth_and_options() {
+        let connection = Connection::new(
+            "db.example".to_string(),
+            22,
+            "ops".to_string(),
+            AuthMethod::Password("old".to_string().into()),
+        );
+        let fingerprint = SessionPool::fingerprint(&connection);
+        assert_eq!(fingerprint, SessionPool::fingerprint(&connection.clone()));
+        assert!(!fingerprint.contains("old"));
+
+        let mut new_password = connection.clone();
+        new_password.auth_method = AuthMethod::Password("new".to_string().into());
+        assert_ne!(SessionPool::fingerprint(&new_password), fingerprint);
+
+        let mut new_options = connection.clone();
+        new_options.ssh_options.compression = Some(true);
+        assert_ne!(SessionPool::fingerprint(&new_options), fingerprint);
+    }
+
+    #[test]
+    fn test_client_config_applies_ssh_options() {
+        let defaults = SshSession::client_config(&SshOptions::default()).unwrap();
+        assert_eq!(defaults.keepalive_interval, Some(Duration::from_secs(30)));
+        assert_eq!(defaults.keepalive_max, 3);
+
+        let options = SshOptions {
+            keepalive_interval: Some(0),
+            compression: Some(false),
+            ciphers: vec!["aes256-ctr".to_string()],
+            kex_algorithms: vec!["+diffie-hellman-group14-sha1".to_string()],
+            ..Default::default()
+        };
+        let config = SshSession::client_config(&options).unwrap();
+        assert_eq!(config.keepalive_interval, None);
+        assert_eq!(&*config.preferred.compression, &[compression::NONE]);
+        assert_eq!(config.preferred.cipher.len(), 1);
+        assert_eq!(config.preferred.kex.len(), defaults.preferred.kex.len() + 1);
+
+        let invalid = SshOptions {
+            macs: vec!["not-a-mac".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            SshSession::client_config(&invalid),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_terminal_command() {
+        assert_eq!(terminal_command(None, None), None);
+        assert_eq!(terminal_command(None, Some("  ")), None);
+        assert_eq!(
+            terminal_command(None, Some("tmux new -A -s main")),
+            Some("tmux new -A -s main".to_string())
+        );
+        assert_eq!(
+            terminal_command(Some("/srv/it's"), None),
+            Some("cd '/srv/it'\\''s' && exec $SHELL -l".to_string())
+        );
+        assert_eq!(
+            terminal_command(Some("/srv"), Some("htop")),
+            Some("cd '/srv' && htop".to_string())
+        );
+    }
 }
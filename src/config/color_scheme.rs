@@ -0,0 +1,474 @@
+//! Terminal colour schemes: built-in schemes, inline definitions from
+//! config.toml, and imports from iTerm2 (`.itermcolors`) and Alacritty
+//! (`.toml`) theme files.
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+
+/// Scheme used as the starting point when a definition names no `base` or `file`
+const FALLBACK_SCHEME: &str = "xterm";
+
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// A 24-bit colour, written as `#rrggbb` in config files
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl FromStr for Rgb {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        let hex = trimmed
+            .strip_prefix('#')
+            .or_else(|| trimmed.strip_prefix("0x"))
+            .unwrap_or(trimmed);
+        let value = (hex.len() == 6)
+            .then(|| u32::from_str_radix(hex, 16).ok())
+            .flatten()
+            .ok_or_else(|| AppError::ConfigError(format!("Invalid colour '{s}'")))?;
+        Ok(Rgb((value >> 16) as u8, (value >> 8) as u8, value as u8))
+    }
+}
+
+impl TryFrom<String> for Rgb {
+    type Error = AppError;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<Rgb> for String {
+    fn from(rgb: Rgb) -> Self {
+        rgb.to_string()
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl From<Rgb> for ratatui::style::Color {
+    fn from(rgb: Rgb) -> Self {
+        ratatui::style::Color::Rgb(rgb.0, rgb.1, rgb.2)
+    }
+}
+
+/// Fully resolved colours for the terminal emulator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColorScheme {
+    pub foreground: Rgb,
+    pub background: Rgb,
+    pub cursor: Rgb,
+    pub selection: Rgb,
+    /// ANSI colours 0-15: the eight normal colours followed by the bright ones
+    pub ansi: [Rgb; 16],
+}
+
+/// A `[color_schemes.<name>]` table from config.toml. The scheme starts
+/// from an imported `file` or a built-in `base` (xterm colours if neither
+/// is set) and the remaining fields override individual colours.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ColorSchemeConfig {
+    /// Name of a built-in scheme to start from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// iTerm2 `.itermcolors` or Alacritty `.toml` theme to import; relative
+    /// paths are resolved against the config directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<Rgb>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<Rgb>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<Rgb>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection: Option<Rgb>,
+    /// Replaces ANSI colours from index 0 onwards (at most 16 entries)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ansi: Vec<Rgb>,
+}
+
+impl ColorSchemeConfig {
+    /// Build the scheme, reading the imported theme file if there is one
+    pub fn resolve(&self, config_dir: &Path) -> Result<ColorScheme> {
+        let mut scheme = match (&self.file, &self.base) {
+            (Some(_), Some(_)) => {
+                return Err(AppError::ConfigError(
+                    "A colour scheme can set `file` or `base`, not both".to_string(),
+                ));
+            }
+            (Some(file), None) => import_scheme(&resolve_path(file, config_dir))?,
+            (None, Some(base)) => builtin_scheme(base).ok_or_else(|| {
+                AppError::ConfigError(format!("Unknown built-in colour scheme '{base}'"))
+            })?,
+            (None, None) => builtin_scheme(FALLBACK_SCHEME).expect("fallback scheme is built in"),
+        };
+
+        if self.ansi.len() > scheme.ansi.len() {
+            return Err(AppError::ConfigError(format!(
+                "A colour scheme has at most 16 ANSI colours, got {}",
+                self.ansi.len()
+            )));
+        }
+        scheme.ansi[..self.ansi.len()].copy_from_slice(&self.ansi);
+        if let Some(foreground) = self.foreground {
+            scheme.foreground = foreground;
+        }
+        if let Some(background) = self.background {
+            scheme.background = background;
+        }
+        if let Some(cursor) = self.cursor {
+            scheme.cursor = cursor;
+        }
+        if let Some(selection) = self.selection {
+            scheme.selection = selection;
+        }
+        Ok(scheme)
+    }
+}
+
+/// Look up a scheme by name. Schemes defined in config.toml take precedence
+/// over built-in schemes of the same name.
+pub fn resolve_scheme(
+    name: &str,
+    schemes: &BTreeMap<String, ColorSchemeConfig>,
+    config_dir: &Path,
+) -> Result<ColorScheme> {
+    if let Some(config) = schemes.get(name) {
+        return config
+            .resolve(config_dir)
+            .map_err(|e| AppError::ConfigError(format!("Colour scheme '{name}': {e}")));
+    }
+    builtin_scheme(name)
+        .ok_or_else(|| AppError::ConfigError(format!("Unknown colour scheme '{name}'")))
+}
+
+/// Names of the schemes that ship with termirs
+pub const BUILTIN_SCHEMES: [&str; 5] = [
+    "xterm",
+    "solarized-dark",
+    "dracula",
+    "gruvbox-dark",
+    "production",
+];
+
+/// Built-in scheme by name
+pub fn builtin_scheme(name: &str) -> Option<ColorScheme> {
+    let (foreground, background, cursor, selection, ansi) = match name {
+        "xterm" => (
+            0xe5e5e5,
+            0x000000,
+            0xe5e5e5,
+            0x4d4d4d,
+            [
+                0x000000, 0xcd0000, 0x00cd00, 0xcdcd00, 0x0000ee, 0xcd00cd, 0x00cdcd, 0xe5e5e5,
+                0x7f7f7f, 0xff0000, 0x00ff00, 0xffff00, 0x5c5cff, 0xff00ff, 0x00ffff, 0xffffff,
+            ],
+        ),
+        "solarized-dark" => (
+            0x839496,
+            0x002b36,
+            0x93a1a1,
+            0x073642,
+            [
+                0x073642, 0xdc322f, 0x859900, 0xb58900, 0x268bd2, 0xd33682, 0x2aa198, 0xeee8d5,
+                0x002b36, 0xcb4b16, 0x586e75, 0x657b83, 0x839496, 0x6c71c4, 0x93a1a1, 0xfdf6e3,
+            ],
+        ),
+        "dracula" => (
+            0xf8f8f2,
+            0x282a36,
+            0xf8f8f2,
+            0x44475a,
+            [
+                0x21222c, 0xff5555, 0x50fa7b, 0xf1fa8c, 0xbd93f9, 0xff79c6, 0x8be9fd, 0xf8f8f2,
+                0x6272a4, 0xff6e6e, 0x69ff94, 0xffffa5, 0xd6acff, 0xff92df, 0xa4ffff, 0xffffff,
+            ],
+        ),
+        "gruvbox-dark" => (
+            0xebdbb2,
+            0x282828,
+            0xebdbb2,
+            0x504945,
+            [
+                0x282828, 0xcc241d, 0x98971a, 0xd79921, 0x458588, 0xb16286, 0x689d6a, 0xa89984,
+                0x928374, 0xfb4934, 0xb8bb26, 0xfabd2f, 0x83a598, 0xd3869b, 0x8ec07c, 0xebdbb2,
+            ],
+        ),
+        // Red-tinted background so a production shell can't be mistaken for another host
+        "production" => (
+            0xf2dede,
+            0x2b0a0a,
+            0xff5f5f,
+            0x6b1f1f,
+            [
+                0x3d1414, 0xff5f5f, 0x5fd75f, 0xffd75f, 0x87afff, 0xd787d7, 0x5fd7d7, 0xe5d0d0,
+                0x7a4a4a, 0xff8787, 0x87ff87, 0xffff87, 0xafd7ff, 0xffafff, 0x87ffff, 0xffffff,
+            ],
+        ),
+        _ => return None,
+    };
+
+    let rgb = |value: u32| Rgb((value >> 16) as u8, (value >> 8) as u8, value as u8);
+    Some(ColorScheme {
+        foreground: rgb(foreground),
+        background: rgb(background),
+        cursor: rgb(cursor),
+        selection: rgb(selection),
+        ansi: ansi.map(rgb),
+    })
+}
+
+fn resolve_path(file: &str, config_dir: &Path) -> PathBuf {
+    let path = crate::expand_tilde(file);
+    if path.is_relative() {
+        config_dir.join(path)
+    } else {
+        path
+    }
+}
+
+/// Import a theme file, picking the format from its extension
+fn import_scheme(path: &Path) -> Result<ColorScheme> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        AppError::ConfigError(format!(
+            "Failed to read colour scheme {}: {e}",
+            path.display()
+        ))
+    })?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("itermcolors") => parse_iterm2(&content),
+        Some("toml") => parse_alacritty(&content),
+        _ => Err(AppError::ConfigError(format!(
+            "Unsupported colour scheme format: {} (expected .itermcolors or .toml)",
+            path.display()
+        ))),
+    }
+}
+
+/// Parse an Alacritty theme (`[colors.primary]`, `[colors.normal]`, ...).
+/// Colours the theme leaves out keep their xterm values.
+pub fn parse_alacritty(content: &str) -> Result<ColorScheme> {
+    let value: toml::Value = toml::from_str(content)
+        .map_err(|e| AppError::ConfigError(format!("Invalid Alacritty theme: {e}")))?;
+    let colors = value
+        .get("colors")
+        .ok_or_else(|| AppError::ConfigError("Alacritty theme has no [colors]".to_string()))?;
+    let color = |section: &str, key: &str| -> Result<Option<Rgb>> {
+        match colors.get(section).and_then(|s| s.get(key)) {
+            Some(toml::Value::String(s)) => s.parse().map(Some),
+            // Alacritty allows "CellForeground"-style references; those can't be represented
+            _ => Ok(None),
+        }
+    };
+
+    let mut scheme = builtin_scheme(FALLBACK_SCHEME).expect("fallback scheme is built in");
+    if let Some(foreground) = color("primary", "foreground")? {
+        scheme.foreground = foreground;
+    }
+    if let Some(background) = color("primary", "background")? {
+        scheme.background = background;
+    }
+    if let Some(cursor) = color("cursor", "cursor")? {
+        scheme.cursor = cursor;
+    }
+    if let Some(selection) = color("selection", "background")? {
+        scheme.selection = selection;
+    }
+    for (idx, name) in ANSI_NAMES.iter().enumerate() {
+        if let Some(normal) = color("normal", name)? {
+            scheme.ansi[idx] = normal;
+        }
+        if let Some(bright) = color("bright", name)? {
+            scheme.ansi[idx + 8] = bright;
+        }
+    }
+    Ok(scheme)
+}
+
+/// Parse an iTerm2 `.itermcolors` property list. Each colour is a
+/// `<key>Ansi 0 Color</key>` entry followed by a dict of 0.0-1.0 components.
+pub fn parse_iterm2(content: &str) -> Result<ColorScheme> {
+    let mut scheme = builtin_scheme(FALLBACK_SCHEME).expect("fallback scheme is built in");
+    let mut found = false;
+    let mut rest = content;
+
+    while let Some((key, after_key)) = next_element(rest, "key") {
+        rest = after_key;
+        let slot = match key.as_str() {
+            "Foreground Color" => &mut scheme.foreground,
+            "Background Color" => &mut scheme.background,
+            "Cursor Color" => &mut scheme.cursor,
+            "Selection Color" => &mut scheme.selection,
+            _ => match key
+                .strip_prefix("Ansi ")
+                .and_then(|k| k.strip_suffix(" Color"))
+                .and_then(|idx| idx.parse::<usize>().ok())
+                .and_then(|idx| scheme.ansi.get_mut(idx))
+            {
+                Some(slot) => slot,
+                None => continue,
+            },
+        };
+
+        let Some(start) = rest.find("<dict>") else {
+            break;
+        };
+        let Some(end) = rest[start..].find("</dict>") else {
+            break;
+        };
+        let dict = &rest[start..start + end];
+        *slot = parse_iterm2_color(dict)
+            .ok_or_else(|| AppError::ConfigError(format!("Invalid iTerm2 colour '{key}'")))?;
+        found = true;
+        rest = &rest[start + end..];
+    }
+
+    if !found {
+        return Err(AppError::ConfigError(
+            "No colours found in iTerm2 colour scheme".to_string(),
+        ));
+    }
+    Ok(scheme)
+}
+
+/// Read the red, green and blue components of an iTerm2 colour dict
+fn parse_iterm2_color(dict: &str) -> Option<Rgb> {
+    let component = |name: &str| -> Option<u8> {
+        let key = format!("<key>{name}</key>");
+        let after_key = &dict[dict.find(&key)? + key.len()..];
+        let (value, _) = next_element(after_key, "real")?;
+        let value: f64 = value.parse().ok()?;
+        Some((value.clamp(0.0, 1.0) * 255.0).round() as u8)
+    };
+    Some(Rgb(
+        component("Red Component")?,
+        component("Green Component")?,
+        component("Blue Component")?,
+    ))
+}
+
+/// Text of the next `<tag>...</tag>` element and the input following it
+fn next_element<'a>(input: &'a str, tag: &str) -> Option<(String, &'a str)> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let start = input.find(&open)? + open.len();
+    let len = input[start..].find(&close)?;
+    Some((
+        input[start..start + len].trim().to_string(),
+        &input[start + len + close.len()..],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_overrides_base() {
+        let mut schemes = BTreeMap::new();
+        schemes.insert(
+            "prod".to_string(),
+            ColorSchemeConfig {
+                base: Some("dracula".to_string()),
+                background: Some("#330000".parse().unwrap()),
+                ansi: vec![Rgb(1, 2, 3)],
+                ..Default::default()
+            },
+        );
+
+        let scheme = resolve_scheme("prod", &schemes, Path::new("/")).unwrap();
+        let dracula = builtin_scheme("dracula").unwrap();
+        assert_eq!(scheme.background, Rgb(0x33, 0, 0));
+        assert_eq!(scheme.foreground, dracula.foreground);
+        assert_eq!(scheme.ansi[0], Rgb(1, 2, 3));
+        assert_eq!(scheme.ansi[1], dracula.ansi[1]);
+
+        for name in BUILTIN_SCHEMES {
+            assert!(resolve_scheme(name, &schemes, Path::new("/")).is_ok());
+        }
+        assert!(resolve_scheme("missing", &schemes, Path::new("/")).is_err());
+        assert!("#12345".parse::<Rgb>().is_err());
+        assert_eq!("0xFF8000".parse::<Rgb>().unwrap().to_string(), "#ff8000");
+    }
+
+    #[test]
+    fn test_parse_alacritty() {
+        let scheme = parse_alacritty(
+            r##"
+[colors.primary]
+background = "#1d1f21"
+foreground = "0xc5c8c6"
+
+[colors.normal]
+red = "#cc6666"
+
+[colors.bright]
+red = "#d54e53"
+"##,
+        )
+        .unwrap();
+
+        assert_eq!(scheme.background, Rgb(0x1d, 0x1f, 0x21));
+        assert_eq!(scheme.foreground, Rgb(0xc5, 0xc8, 0xc6));
+        assert_eq!(scheme.ansi[1], Rgb(0xcc, 0x66, 0x66));
+        assert_eq!(scheme.ansi[9], Rgb(0xd5, 0x4e, 0x53));
+        // Unset colours keep the xterm defaults
+        assert_eq!(scheme.ansi[2], Rgb(0x00, 0xcd, 0x00));
+        assert!(parse_alacritty("[window]\nopacity = 1.0").is_err());
+    }
+
+    #[test]
+    fn test_parse_iterm2() {
+        let scheme = parse_iterm2(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+	<key>Ansi 1 Color</key>
+	<dict>
+		<key>Alpha Component</key>
+		<real>1</real>
+		<key>Blue Component</key>
+		<real>0.0</real>
+		<key>Color Space</key>
+		<string>sRGB</string>
+		<key>Green Component</key>
+		<real>0.0</real>
+		<key>Red Component</key>
+		<real>1.0</real>
+	</dict>
+	<key>Background Color</key>
+	<dict>
+		<key>Blue Component</key>
+		<real>0.2</real>
+		<key>Green Component</key>
+		<real>0.1</real>
+		<key>Red Component</key>
+		<real>0.0</real>
+	</dict>
+</dict>
+</plist>"#,
+        )
+        .unwrap();
+
+        assert_eq!(scheme.ansi[1], Rgb(255, 0, 0));
+        assert_eq!(scheme.background, Rgb(0, 26, 51));
+        assert!(parse_iterm2("<plist></plist>").is_err());
+    }
+}
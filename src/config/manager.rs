@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     fmt, fs,
     path::{Path, PathBuf},
 };
@@ -8,6 +9,8 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use crate::config::color_scheme::{ColorScheme, ColorSchemeConfig, resolve_scheme};
+use crate::config::theme::{Theme, ThemeConfig};
 use crate::error::{AppError, Result};
 
 pub const DEFAULT_TERMINAL_SCROLLBACK_LINES: usize = 2000;
@@ -17,6 +20,17 @@ fn default_terminal_scrollback_lines() -> usize {
     DEFAULT_TERMINAL_SCROLLBACK_LINES
 }
 
+pub const DEFAULT_IDLE_NOTIFY_SECONDS: u64 = 10;
+
+fn default_idle_notify_seconds() -> u64 {
+    DEFAULT_IDLE_NOTIFY_SECONDS
+}
+
+/// Keeps `false` flags out of saved connections
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 /// A string that holds a sensitive value (password/passphrase) with an optional
 /// cached encrypted form. When the cached form is present (i.e. the value was
 /// loaded from disk and hasn't been modified), serialization reuses the cache
@@ -115,6 +129,39 @@ pub struct AppSettings {
     pub terminal_scrollback_lines: usize,
     #[serde(default)]
     pub have_nerd_font: bool,
+    /// What remote programs may do with the local clipboard through OSC 52
+    #[serde(default)]
+    pub remote_clipboard: RemoteClipboard,
+    /// Colour scheme for terminals; unset keeps the host terminal's colours
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_scheme: Option<String>,
+    /// Seconds without output after which "notify when idle" reports a
+    /// command as done
+    #[serde(default = "default_idle_notify_seconds")]
+    pub idle_notify_seconds: u64,
+    /// Keep a column left of the terminal for marking the prompts and
+    /// output of shells with OSC 133 shell integration
+    #[serde(default)]
+    pub prompt_gutter: bool,
+}
+
+/// Access granted to OSC 52 clipboard requests from remote programs.
+/// Clipboard queries are never answered.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteClipboard {
+    /// Ignore OSC 52 entirely
+    Off,
+    /// Let remote programs set the clipboard
+    #[default]
+    #[serde(alias = "read_write")]
+    Write,
+}
+
+impl RemoteClipboard {
+    pub fn allows_write(self) -> bool {
+        matches!(self, Self::Write)
+    }
 }
 
 impl Default for AppSettings {
@@ -124,6 +171,10 @@ impl Default for AppSettings {
             connection_timeout: 20,
             terminal_scrollback_lines: DEFAULT_TERMINAL_SCROLLBACK_LINES,
             have_nerd_font: false,
+            remote_clipboard: RemoteClipboard::default(),
+            color_scheme: None,
+            idle_notify_seconds: DEFAULT_IDLE_NOTIFY_SECONDS,
+            prompt_gutter: false,
         }
     }
 }
@@ -132,6 +183,8 @@ impl Default for AppSettings {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Connection {
     pub id: String,
+    #[serde(default, skip_serializing_if = "ConnectionKind::is_ssh")]
+    pub kind: ConnectionKind,
     pub display_name: String,
     pub host: String,
     pub port: u16,
@@ -140,6 +193,126 @@ pub struct Connection {
     pub created_at: DateTime<Utc>,
     pub last_used: Option<DateTime<Utc>>,
     pub public_key: Option<String>,
+    /// Reconnect automatically when the interactive session drops
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub auto_reconnect: bool,
+    /// Command typed into the shell after connecting and after every reconnect
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup_command: Option<String>,
+    /// Environment variables sent before the shell starts (subject to the
+    /// server's `AcceptEnv`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Command run instead of a login shell, e.g. `tmux new -A -s main`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_command: Option<String>,
+    /// Remote directory the terminal starts in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_dir: Option<String>,
+    /// `TERM` value requested for the PTY (defaults to `xterm-256color`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub term: Option<String>,
+    /// Colour scheme overriding the global one, e.g. a red-tinted scheme for production
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_scheme: Option<String>,
+    /// Snippets offered in the palette only for this connection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snippets: Vec<Snippet>,
+    /// Record every terminal session to an asciicast file
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub record_sessions: bool,
+    /// Write the session's output to plain-text log files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_log: Option<SessionLogOptions>,
+    /// Save the scrollback on disconnect and show it above the next session
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub persist_scrollback: bool,
+    /// Run the terminal inside tmux (or screen) on the host and re-attach to
+    /// it on every connection
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub persistent_session: bool,
+    /// Start sessions with "notify when idle" on
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub notify_when_idle: bool,
+    #[serde(default, skip_serializing_if = "SshOptions::is_default")]
+    pub ssh_options: SshOptions,
+}
+
+/// Per-connection SSH transport overrides. Unset fields keep the built-in
+/// defaults; algorithm lists use OpenSSH names, and a list made only of
+/// `+name` entries is appended to the defaults instead of replacing them.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Hash)]
+pub struct SshOptions {
+    /// Seconds between keepalive probes; 0 disables keepalives
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keepalive_interval: Option<u64>,
+    /// Unanswered keepalives before the connection is considered dead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keepalive_max: Option<usize>,
+    /// Seconds to wait for the TCP connection and handshake
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<u64>,
+    /// Prefer (true) or refuse (false) zlib compression
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kex_algorithms: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ciphers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub macs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub host_key_algorithms: Vec<String>,
+}
+
+impl SshOptions {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// How a connection reaches its terminal. Only SSH connections have SFTP
+/// and port forwarding.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionKind {
+    #[default]
+    Ssh,
+    /// Telnet with basic option negotiation (echo, window size, terminal type)
+    Telnet,
+    /// A plain TCP socket, e.g. a console server port
+    Tcp,
+    /// The user's shell in a local PTY
+    Local,
+}
+
+impl ConnectionKind {
+    pub fn is_ssh(&self) -> bool {
+        *self == Self::Ssh
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ssh => "ssh",
+            Self::Telnet => "telnet",
+            Self::Tcp => "raw tcp",
+            Self::Local => "local shell",
+        }
+    }
+}
+
+/// Settings of a connection's plain-text session log. Logs go to
+/// `~/.local/share/termirs/logs/<connection>/<date>.log`, a new file each
+/// day and whenever the current one reaches the size limit.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SessionLogOptions {
+    /// Prefix every line with the local time it was received
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub timestamps: bool,
+    /// Size in MiB after which the log continues in a new file
+    /// (default 10, 0 for no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
 }
 
 /// Type of port forwarding
@@ -333,11 +506,24 @@ pub enum AuthMethod {
 }
 
 impl Connection {
+    /// The "Local" pseudo-connection: the user's shell in a local PTY. It
+    /// isn't stored in the config and is known by its kind alone.
+    pub fn local() -> Self {
+        let username = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_default();
+        let mut connection = Self::new("localhost".to_string(), 0, username, AuthMethod::None);
+        connection.kind = ConnectionKind::Local;
+        connection.display_name = "Local".to_string();
+        connection
+    }
+
     /// Creates a new connection with the given parameters
     pub fn new(host: String, port: u16, username: String, auth_method: AuthMethod) -> Self {
         let display_name = host.clone(); // Default display name is the host
         Self {
             id: Uuid::new_v4().to_string(),
+            kind: ConnectionKind::Ssh,
             display_name,
             host,
             port,
@@ -346,6 +532,20 @@ impl Connection {
             created_at: Utc::now(),
             last_used: None,
             public_key: None,
+            auto_reconnect: false,
+            startup_command: None,
+            env: BTreeMap::new(),
+            remote_command: None,
+            start_dir: None,
+            term: None,
+            color_scheme: None,
+            snippets: Vec::new(),
+            record_sessions: false,
+            session_log: None,
+            persist_scrollback: false,
+            persistent_session: false,
+            notify_when_idle: false,
+            ssh_options: SshOptions::default(),
         }
     }
 
@@ -367,7 +567,8 @@ impl Connection {
             ));
         }
 
-        if self.username.trim().is_empty() {
+        // Telnet and raw TCP hosts ask for a login in the session itself
+        if self.kind.is_ssh() && self.username.trim().is_empty() {
             return Err(AppError::ValidationError(
                 "Username cannot be empty".to_string(),
             ));
@@ -395,18 +596,36 @@ impl Connection {
     }
 }
 
+/// A saved command that can be inserted into a connected shell.
+/// `{{name}}` placeholders are prompted for before the command is sent.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Snippet {
+    pub name: String,
+    pub command: String,
+}
+
 /// Main configuration structure
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Config {
     pub connections: Vec<Connection>,
     #[serde(default)]
     pub port_forwards: Vec<PortForward>,
+    /// Snippets offered in the palette for every connection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snippets: Vec<Snippet>,
+    /// User-defined terminal colour schemes, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub color_schemes: BTreeMap<String, ColorSchemeConfig>,
+    /// Interface colours
+    #[serde(default, skip_serializing_if = "ThemeConfig::is_default")]
+    pub theme: ThemeConfig,
     pub settings: AppSettings,
 }
 /// Configuration manager for handling application settings and connection storage
 pub struct ConfigManager {
     config_path: PathBuf,
     config: Config,
+    theme: Theme,
 }
 
 impl ConfigManager {
@@ -416,6 +635,7 @@ impl ConfigManager {
         info!("Loading configuration from: {:?}", config_path);
         let mut config = Self::load_config_from_path(&config_path)?;
         Self::normalize_settings(&mut config);
+        let theme = config.theme.resolve()?;
         debug!(
             "Configuration loaded with {} connections",
             config.connections.len()
@@ -424,6 +644,7 @@ impl ConfigManager {
         Ok(Self {
             config_path,
             config,
+            theme,
         })
     }
 
@@ -435,16 +656,22 @@ impl ConfigManager {
         self.config.settings.have_nerd_font
     }
 
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
     /// Create a configuration manager with a custom config path (useful for testing)
     #[allow(dead_code)]
     pub fn with_path<P: AsRef<Path>>(config_path: P) -> Result<Self> {
         let config_path = config_path.as_ref().to_path_buf();
         let mut config = Self::load_config_from_path(&config_path)?;
         Self::normalize_settings(&mut config);
+        let theme = config.theme.resolve()?;
 
         Ok(Self {
             config_path,
             config,
+            theme,
         })
     }
 
@@ -519,6 +746,34 @@ impl ConfigManager {
         self.config.settings.terminal_scrollback_lines
     }
 
+    pub fn remote_clipboard(&self) -> RemoteClipboard {
+        self.config.settings.remote_clipboard
+    }
+
+    pub fn prompt_gutter(&self) -> bool {
+        self.config.settings.prompt_gutter
+    }
+
+    /// Idle time of "notify when idle"; at least a second
+    pub fn idle_notify_after(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.config.settings.idle_notify_seconds.max(1))
+    }
+
+    /// Colour scheme for a connection's terminal: its own `color_scheme`,
+    /// else the global setting. `None` keeps the host terminal's colours.
+    pub fn color_scheme_for(&self, connection: &Connection) -> Result<Option<ColorScheme>> {
+        let Some(name) = connection.color_scheme.as_deref().or(self
+            .config
+            .settings
+            .color_scheme
+            .as_deref())
+        else {
+            return Ok(None);
+        };
+        let config_dir = self.config_path.parent().unwrap_or(Path::new("."));
+        resolve_scheme(name, &self.config.color_schemes, config_dir).map(Some)
+    }
+
     /// Find a connection by ID
     pub fn find_connection(&self, id: &str) -> Option<&Connection> {
         self.config.connections.iter().find(|c| c.id == id)
@@ -593,6 +848,11 @@ impl ConfigManager {
         &self.config.port_forwards
     }
 
+    /// Return the global snippets
+    pub fn snippets(&self) -> &[Snippet] {
+        &self.config.snippets
+    }
+
     /// Return mutable slice of port forwards
     pub fn port_forwards_mut(&mut self) -> &mut Vec<PortForward> {
         &mut self.config.port_forwards
@@ -703,6 +963,16 @@ mod tests {
         let deserialized: Connection = toml::from_str(&serialized).unwrap();
         println!("deserialized: {:?}", deserialized);
         assert_eq!(conn.auth_method, deserialized.auth_method);
+        // Options left at their defaults aren't written out
+        for key in [
+            "kind",
+            "auto_reconnect",
+            "record_sessions",
+            "ssh_options",
+            "startup_command",
+        ] {
+            assert!(!serialized.contains(key), "{key} in {serialized}");
+        }
     }
 
     #[test]
@@ -725,9 +995,73 @@ mod tests {
         let config = Config {
             connections: vec![conn, conn1],
             port_forwards: vec![],
+            snippets: vec![],
+            color_schemes: BTreeMap::new(),
+            theme: ThemeConfig::default(),
             settings: AppSettings::default(),
         };
         let serialized = toml::to_string(&config).unwrap();
         println!("serialized: {}", serialized);
     }
+
+    #[test]
+    fn test_deserialize_snippets() {
+        let config: Config = toml::from_str(
+            r#"
+[settings]
+default_port = 22
+connection_timeout = 20
+
+[[snippets]]
+name = "Disk usage"
+command = "df -h"
+
+[[connections]]
+id = "id"
+display_name = "web"
+host = "example.com"
+port = 22
+username = "root"
+created_at = "2023-01-01T00:00:00Z"
+auth_method = "auto_load_key"
+
+[[connections.snippets]]
+name = "Tail logs"
+command = "journalctl -fu {{service}}"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.snippets[0].command, "df -h");
+        assert_eq!(config.connections[0].snippets[0].name, "Tail logs");
+    }
+
+    #[test]
+    fn test_color_scheme_for_connection() {
+        use crate::config::color_scheme::builtin_scheme;
+
+        let mut manager = ConfigManager::with_path("/nonexistent/termirs/config.toml").unwrap();
+        let mut conn = Connection::new(
+            "prod-db".to_string(),
+            22,
+            "root".to_string(),
+            AuthMethod::AutoLoadKey,
+        );
+        assert_eq!(manager.color_scheme_for(&conn).unwrap(), None);
+
+        manager.config.settings.color_scheme = Some("dracula".to_string());
+        assert_eq!(
+            manager.color_scheme_for(&conn).unwrap(),
+            builtin_scheme("dracula")
+        );
+
+        conn.color_scheme = Some("production".to_string());
+        assert_eq!(
+            manager.color_scheme_for(&conn).unwrap(),
+            builtin_scheme("production")
+        );
+
+        conn.color_scheme = Some("missing".to_string());
+        assert!(manager.color_scheme_for(&conn).is_err());
+    }
 }
@@ -1,3 +1,5 @@
+pub mod color_scheme;
 pub mod encryption;
 pub mod manager;
 pub mod ssh_config;
+pub mod theme;
@@ -0,0 +1,245 @@
+//! Colours used by termirs' own interface (lists, forms, popups, footers).
+//! The remote terminal's colours are configured separately, see
+//! [`crate::config::color_scheme`].
+
+use std::str::FromStr;
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+
+/// Names of the themes that ship with termirs
+pub const BUILTIN_THEMES: [&str; 3] = ["default", "light", "high_contrast"];
+
+/// Resolved interface colours, passed to every draw function
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// Borders of panels, popups and focused form fields
+    pub border: Color,
+    /// Panel and popup titles, table headers and key hints
+    pub title: Color,
+    /// Regular text
+    pub text: Color,
+    /// Field labels and secondary details
+    pub label: Color,
+    /// Placeholders and other de-emphasised text
+    pub muted: Color,
+    pub selection_fg: Color,
+    pub selection_bg: Color,
+    /// Footer hints and version
+    pub footer: Color,
+    /// Search input, confirmations and other attention-grabbing UI
+    pub warning: Color,
+    /// Confirm actions, completed transfers and active forwards
+    pub success: Color,
+    /// Error popups, failures and destructive actions
+    pub error: Color,
+    /// Info popups
+    pub info: Color,
+    /// Directories in the file explorer
+    pub directory: Color,
+    /// Transfer progress bars
+    pub progress: Color,
+    /// Background of terminal search matches
+    pub search_match: Color,
+    /// Background of the current terminal search match
+    pub search_current: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            border: Color::Cyan,
+            title: Color::Cyan,
+            text: Color::White,
+            label: Color::Gray,
+            muted: Color::DarkGray,
+            selection_fg: Color::White,
+            selection_bg: Color::Cyan,
+            footer: Color::White,
+            warning: Color::Yellow,
+            success: Color::Green,
+            error: Color::Red,
+            info: Color::Green,
+            directory: Color::LightBlue,
+            progress: Color::Cyan,
+            search_match: Color::Yellow,
+            search_current: Color::LightGreen,
+        }
+    }
+}
+
+impl Theme {
+    /// Built-in theme by name
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            // For terminals with a light background: no white text, no yellow
+            "light" => Some(Self {
+                border: Color::Blue,
+                title: Color::Blue,
+                text: Color::Black,
+                label: Color::DarkGray,
+                muted: Color::Gray,
+                selection_fg: Color::White,
+                selection_bg: Color::Blue,
+                footer: Color::DarkGray,
+                warning: Color::Rgb(0x9a, 0x60, 0x00),
+                success: Color::Rgb(0x1a, 0x7f, 0x37),
+                error: Color::Rgb(0xc0, 0x1c, 0x28),
+                info: Color::Rgb(0x1a, 0x7f, 0x37),
+                directory: Color::Blue,
+                progress: Color::Blue,
+                search_match: Color::Rgb(0xff, 0xd7, 0x5f),
+                search_current: Color::Rgb(0x87, 0xd7, 0x87),
+            }),
+            // Bright colours only, and no dark grey text
+            "high_contrast" => Some(Self {
+                border: Color::White,
+                title: Color::LightYellow,
+                text: Color::White,
+                label: Color::White,
+                muted: Color::Gray,
+                selection_fg: Color::Black,
+                selection_bg: Color::LightYellow,
+                footer: Color::White,
+                warning: Color::LightYellow,
+                success: Color::LightGreen,
+                error: Color::LightRed,
+                info: Color::LightGreen,
+                directory: Color::LightCyan,
+                progress: Color::LightGreen,
+                search_match: Color::LightYellow,
+                search_current: Color::LightCyan,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The `[theme]` table from config.toml: a built-in `base` theme plus
+/// per-role overrides. Colours are names (`cyan`, `light-blue`), indices
+/// (`208`) or hex (`#ff8700`).
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ThemeConfig {
+    /// Built-in theme to start from: `default`, `light` or `high_contrast`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muted: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection_fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection_bg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_match: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_current: Option<String>,
+}
+
+impl ThemeConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn resolve(&self) -> Result<Theme> {
+        let mut theme = match &self.base {
+            Some(base) => Theme::builtin(base).ok_or_else(|| {
+                AppError::ConfigError(format!(
+                    "Unknown theme '{base}' (expected one of: {})",
+                    BUILTIN_THEMES.join(", ")
+                ))
+            })?,
+            None => Theme::default(),
+        };
+
+        let overrides = [
+            ("border", &self.border, &mut theme.border),
+            ("title", &self.title, &mut theme.title),
+            ("text", &self.text, &mut theme.text),
+            ("label", &self.label, &mut theme.label),
+            ("muted", &self.muted, &mut theme.muted),
+            ("selection_fg", &self.selection_fg, &mut theme.selection_fg),
+            ("selection_bg", &self.selection_bg, &mut theme.selection_bg),
+            ("footer", &self.footer, &mut theme.footer),
+            ("warning", &self.warning, &mut theme.warning),
+            ("success", &self.success, &mut theme.success),
+            ("error", &self.error, &mut theme.error),
+            ("info", &self.info, &mut theme.info),
+            ("directory", &self.directory, &mut theme.directory),
+            ("progress", &self.progress, &mut theme.progress),
+            ("search_match", &self.search_match, &mut theme.search_match),
+            (
+                "search_current",
+                &self.search_current,
+                &mut theme.search_current,
+            ),
+        ];
+        for (role, value, slot) in overrides {
+            if let Some(value) = value {
+                *slot = Color::from_str(value).map_err(|_| {
+                    AppError::ConfigError(format!("Invalid theme colour for {role}: '{value}'"))
+                })?;
+            }
+        }
+        Ok(theme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_theme() {
+        assert_eq!(ThemeConfig::default().resolve().unwrap(), Theme::default());
+
+        let config = ThemeConfig {
+            base: Some("light".to_string()),
+            border: Some("magenta".to_string()),
+            selection_bg: Some("#ff8700".to_string()),
+            ..Default::default()
+        };
+        let theme = config.resolve().unwrap();
+        assert_eq!(theme.border, Color::Magenta);
+        assert_eq!(theme.selection_bg, Color::Rgb(0xff, 0x87, 0x00));
+        assert_eq!(theme.text, Theme::builtin("light").unwrap().text);
+
+        for name in BUILTIN_THEMES {
+            assert!(Theme::builtin(name).is_some());
+        }
+        let unknown = ThemeConfig {
+            base: Some("solarized".to_string()),
+            ..Default::default()
+        };
+        assert!(unknown.resolve().is_err());
+        let invalid = ThemeConfig {
+            error: Some("not-a-colour".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.resolve().is_err());
+    }
+}
@@ -44,6 +44,12 @@ pub enum AppError {
 
     #[error("Clipboard error: {0}")]
     ClipboardError(String),
+
+    #[error("Local terminal error: {0}")]
+    LocalTerminalError(String),
+
+    #[error("ZMODEM error: {0}")]
+    ZmodemError(String),
 }
 
 /// Application result type alias
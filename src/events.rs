@@ -13,6 +13,12 @@ pub enum AppEvent {
     Input(Event),
     Tick,
     Disconnect,                               // Sent when SSH connection is disconnected
+    ConnectionLost,                           // Sent when the channel drops without an exit status
     TerminalUpdate,                           // Sent when SSH terminal receives data
+    TerminalReply(Vec<u8>),                   // Emulator answers to queries from the remote side
+    RemoteClipboard(String),                  // OSC 52 clipboard write
+    TerminalAlert(crate::ui::TerminalAlert),  // Bell or desktop notification from the remote side
+    Redraw,                                   // Sent when something time-based needs repainting
     SftpProgress(crate::transfer::ScpResult), // Sent when SFTP transfer has progress/completion
+    ExportUploaded(crate::error::Result<String>), // Remote path of an uploaded terminal export
 }
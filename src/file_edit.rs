@@ -9,6 +9,7 @@ use std::time::SystemTime;
 
 use tokio::sync::mpsc::Sender;
 
+use crate::async_ssh_client::SessionPool;
 use crate::config::manager::Connection;
 use crate::error::{AppError, Result};
 use crate::events::TickControl;
@@ -49,12 +50,16 @@ pub async fn check_binary(path: &str) -> Result<bool> {
 
 /// Check whether a remote file looks like a binary file by reading only the
 /// first [`BINARY_DETECTION_BYTES`] bytes via SFTP.
-pub async fn check_remote_binary(connection: Option<&Connection>, path: &str) -> Result<bool> {
+pub async fn check_remote_binary(
+    pool: &SessionPool,
+    connection: Option<&Connection>,
+    path: &str,
+) -> Result<bool> {
     use crate::async_ssh_client::SshSession;
     let connection = connection.ok_or_else(|| {
         AppError::SftpError("Could not determine connection for remote file".to_string())
     })?;
-    let head = SshSession::sftp_read_head(connection, path, BINARY_DETECTION_BYTES).await?;
+    let head = SshSession::sftp_read_head(pool, connection, path, BINARY_DETECTION_BYTES).await?;
     Ok(is_binary(&head))
 }
 
@@ -139,8 +144,9 @@ pub async fn edit_local_file(path: &str, tick_tx: &Sender<TickControl>) -> Resul
 }
 
 /// Download a remote file to a temp file, open it in the editor, and upload
-/// back if modified.
+/// back if modified. Transfers run on the pooled connection for `connection`.
 pub async fn edit_remote_file(
+    pool: &SessionPool,
     remote_path: &str,
     connection: &Connection,
     tick_tx: &Sender<TickControl>,
@@ -168,10 +174,15 @@ pub async fn edit_remote_file(
     };
     let tmp_path = tmp_file.path().to_string_lossy().to_string();
 
-    let (session, _server_key) = SshSession::new_session(connection).await?;
-
-    let channel_recv = session.channel_open_session().await?;
-    let channel_send = session.channel_open_session().await?;
+    // The lease keeps the pooled connection open while the editor runs
+    let (channel_recv, lease, _server_key) = pool
+        .open_session_channel(
+            connection,
+            None,
+            &tokio_util::sync::CancellationToken::new(),
+        )
+        .await?;
+    let channel_send = lease.lock().await.channel_open_session().await?;
 
     // Download remote file to temp
     SshSession::sftp_receive_file(
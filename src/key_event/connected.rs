@@ -125,24 +125,365 @@ fn encode_key_event_to_ansi(app_cursor: bool, key: &KeyEvent) -> Option<Vec<u8>>
     }
 }
 
+/// Keys for the URL picker, which captures all keys while open.
+/// Returns `None` if the picker isn't open.
+fn handle_url_picker_key<B: Backend + Write>(app: &mut App<B>, key: &KeyEvent) -> Option<KeyFlow> {
+    let AppMode::Connected {
+        url_picker: Some(picker),
+        ..
+    } = &mut app.mode
+    else {
+        return None;
+    };
+
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let selected = picker.selected_link().map(|link| link.url.clone());
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => picker.select_prev(),
+        KeyCode::Char('p') if ctrl => picker.select_prev(),
+        KeyCode::Down | KeyCode::Char('j') => picker.select_next(),
+        KeyCode::Char('n') if ctrl => picker.select_next(),
+        KeyCode::Esc | KeyCode::Char('q') => close_url_picker(app),
+        KeyCode::Enter | KeyCode::Char('o') => {
+            close_url_picker(app);
+            if let Some(url) = selected {
+                app.open_link(&url);
+            }
+        }
+        KeyCode::Char('c') | KeyCode::Char('y') if !ctrl => {
+            close_url_picker(app);
+            if let Some(url) = selected {
+                app.copy_text_to_clipboard(url);
+            }
+        }
+        _ => {}
+    }
+    Some(KeyFlow::Continue)
+}
+
+/// Keys for the export menu, which captures all keys while open.
+/// Returns `None` if the menu isn't open.
+async fn handle_export_menu_key<B: Backend + Write>(
+    app: &mut App<B>,
+    key: &KeyEvent,
+) -> Option<KeyFlow> {
+    let AppMode::Connected {
+        export_menu: Some(menu),
+        ..
+    } = &mut app.mode
+    else {
+        return None;
+    };
+
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => menu.select_prev(),
+        KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => menu.select_next(),
+        KeyCode::Left | KeyCode::Char('h') => menu.change(-1),
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => menu.change(1),
+        KeyCode::Esc | KeyCode::Char('q') => close_export_menu(app),
+        KeyCode::Enter => {
+            let menu = menu.clone();
+            close_export_menu(app);
+            app.export_terminal(&menu).await;
+        }
+        _ => {}
+    }
+    Some(KeyFlow::Continue)
+}
+
+/// Keys for the ZMODEM picker, which captures all keys while open.
+/// Returns `None` if the picker isn't open.
+async fn handle_zmodem_picker_key<B: Backend + Write>(
+    app: &mut App<B>,
+    key: &KeyEvent,
+) -> Option<KeyFlow> {
+    use ratatui_async_explorer::Input;
+
+    let AppMode::Connected {
+        zmodem_picker: Some(picker),
+        ..
+    } = &mut app.mode
+    else {
+        return None;
+    };
+
+    let result = match key.code {
+        KeyCode::Up | KeyCode::Char('k') => picker.navigate(Input::Up).await.map(|_| None),
+        KeyCode::Down | KeyCode::Char('j') => picker.navigate(Input::Down).await.map(|_| None),
+        KeyCode::Home => picker.navigate(Input::Home).await.map(|_| None),
+        KeyCode::End => picker.navigate(Input::End).await.map(|_| None),
+        KeyCode::PageUp => picker.navigate(Input::PageUp).await.map(|_| None),
+        KeyCode::PageDown => picker.navigate(Input::PageDown).await.map(|_| None),
+        KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') => {
+            picker.navigate(Input::Left).await.map(|_| None)
+        }
+        KeyCode::Right | KeyCode::Char('l') => picker.navigate(Input::Right).await.map(|_| None),
+        KeyCode::Enter => picker.choose().await,
+        KeyCode::Char('s') => Ok(picker.save_here()),
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.refuse_zmodem();
+            return Some(KeyFlow::Continue);
+        }
+        _ => Ok(None),
+    };
+    match result {
+        Ok(Some(job)) => app.start_zmodem_transfer(job).await,
+        Ok(None) => {}
+        Err(e) => app.set_error(e),
+    }
+    app.mark_redraw();
+    Some(KeyFlow::Continue)
+}
+
+/// Keys for copy mode, which captures all keys while active.
+/// Returns `None` outside copy mode.
+async fn handle_copy_mode_key<B: Backend + Write>(
+    app: &mut App<B>,
+    key: &KeyEvent,
+) -> Option<KeyFlow> {
+    use crate::terminal::{Motion, VisualMode};
+
+    let AppMode::Connected {
+        copy_mode: Some(copy_mode),
+        state,
+        ..
+    } = &mut app.mode
+    else {
+        return None;
+    };
+    let state = state.clone();
+    let mut guard = state.lock().await;
+
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let pending_g = std::mem::take(&mut copy_mode.pending_g);
+    let motion = match key.code {
+        KeyCode::Char('h') | KeyCode::Left => Some(Motion::Left),
+        KeyCode::Char('l') | KeyCode::Right => Some(Motion::Right),
+        KeyCode::Char('k') | KeyCode::Up => Some(Motion::Up),
+        KeyCode::Char('j') | KeyCode::Down => Some(Motion::Down),
+        KeyCode::Char('w') => Some(Motion::WordForward),
+        KeyCode::Char('b') => Some(Motion::WordBackward),
+        KeyCode::Char('0') | KeyCode::Home => Some(Motion::LineStart),
+        KeyCode::Char('$') | KeyCode::End => Some(Motion::LineEnd),
+        KeyCode::Char('g') if pending_g => Some(Motion::Top),
+        KeyCode::Char('G') => Some(Motion::Bottom),
+        _ => None,
+    };
+    let (mut yank, mut exit, mut no_output) = (false, false, false);
+    match key.code {
+        _ if motion.is_some() => {}
+        KeyCode::Char('g') => copy_mode.pending_g = true,
+        KeyCode::Char('v') if ctrl => copy_mode.toggle_visual(VisualMode::Block),
+        KeyCode::Char('v') => copy_mode.toggle_visual(VisualMode::Char),
+        KeyCode::Char('V') => copy_mode.toggle_visual(VisualMode::Line),
+        KeyCode::Char('[') => copy_mode.jump_to_prompt(true, &mut guard),
+        KeyCode::Char(']') => copy_mode.jump_to_prompt(false, &mut guard),
+        KeyCode::Char('o') => no_output = !copy_mode.select_command_output(&mut guard),
+        KeyCode::Char('y') | KeyCode::Enter => yank = true,
+        KeyCode::Esc | KeyCode::Char('q') => {
+            if copy_mode.visual().is_some() {
+                copy_mode.clear_visual();
+            } else {
+                exit = true;
+            }
+        }
+        KeyCode::Char('c') if ctrl => exit = true,
+        _ => {}
+    }
+    if let Some(motion) = motion {
+        copy_mode.move_cursor(motion, &guard);
+        copy_mode.scroll_into_view(&mut guard);
+    }
+
+    // Yanking copies the selection and leaves copy mode, like tmux
+    let text = if yank {
+        copy_mode.selection().and_then(|(anchor, tail, shape)| {
+            crate::terminal::selection::collect_selection_text(&guard.terminal, anchor, tail, shape)
+        })
+    } else {
+        None
+    };
+    if yank || exit {
+        guard.scroll_to_bottom();
+        drop(guard);
+        if let AppMode::Connected { copy_mode, .. } = &mut app.mode {
+            *copy_mode = None;
+        }
+    } else {
+        drop(guard);
+    }
+
+    app.sync_copy_mode_selection();
+    if let Some(text) = text {
+        app.copy_text_to_clipboard(text);
+    }
+    if no_output {
+        app.info = Some(
+            "No command output found\n\nThis needs a shell that marks its prompts with OSC 133 \
+             (shell integration)"
+                .to_string(),
+        );
+    }
+    Some(KeyFlow::Continue)
+}
+
+/// Whether a key types `ch` into the snippet filter. Ctrl and Alt chords
+/// aren't text, except AltGr, which Windows reports as Ctrl+Alt with the
+/// character it produced (`@`, `€`, `{`...).
+fn is_filter_text(key: &KeyEvent, ch: char) -> bool {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    if !ctrl && !alt {
+        return true;
+    }
+    cfg!(windows) && ctrl && alt && !ch.is_ascii_alphanumeric() && !ch.is_control()
+}
+
+fn close_url_picker<B: Backend + Write>(app: &mut App<B>) {
+    if let AppMode::Connected { url_picker, .. } = &mut app.mode {
+        *url_picker = None;
+    }
+}
+
+fn close_export_menu<B: Backend + Write>(app: &mut App<B>) {
+    if let AppMode::Connected { export_menu, .. } = &mut app.mode {
+        *export_menu = None;
+    }
+}
+
 pub async fn handle_connected_key<B: Backend + Write>(app: &mut App<B>, key: KeyEvent) -> KeyFlow {
     app.clear_selection();
 
+    if let Some(flow) = handle_zmodem_picker_key(app, &key).await {
+        return flow;
+    }
+    if let Some(flow) = handle_url_picker_key(app, &key) {
+        return flow;
+    }
+    if let Some(flow) = handle_export_menu_key(app, &key).await {
+        return flow;
+    }
+    if let Some(flow) = handle_copy_mode_key(app, &key).await {
+        return flow;
+    }
+
     if let AppMode::Connected {
         name: _,
+        connection,
         client,
         state,
+        reconnect,
+        snippet_palette,
+        url_picker,
+        export_menu,
+        copy_mode,
+        pending_paste,
+        close_prompt,
         ..
     } = &mut app.mode
     {
-        // Check if in search mode first
+        // While reconnecting the terminal is frozen; Esc gives up and disconnects
+        if reconnect.is_some() {
+            if key.code == KeyCode::Esc {
+                app.send_event(AppEvent::Disconnect);
+            }
+            return KeyFlow::Continue;
+        }
+
+        // Closing a persistent session: detach and leave it running, or kill it
+        if *close_prompt {
+            match key.code {
+                KeyCode::Enter | KeyCode::Char('d') | KeyCode::Char('D') => {
+                    *close_prompt = false;
+                    app.send_event(AppEvent::Disconnect);
+                }
+                KeyCode::Char('k') | KeyCode::Char('K') => {
+                    *close_prompt = false;
+                    if let Err(e) = client.kill_persistent_session().await {
+                        app.error = Some(e);
+                    }
+                    app.send_event(AppEvent::Disconnect);
+                }
+                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => *close_prompt = false,
+                _ => {}
+            }
+            return KeyFlow::Continue;
+        }
+
+        // Close the terminal with F12, even while a full-screen program (or
+        // tmux) has the keyboard; Ctrl chords belong to the remote side
+        if key.code == KeyCode::F(12) {
+            if client.persistent_session().is_some() {
+                *close_prompt = true;
+            } else {
+                app.send_event(AppEvent::Disconnect);
+            }
+            return KeyFlow::Continue;
+        }
+
+        // A multi-line paste into a shell without bracketed paste waits for a yes/no
+        if let Some(data) = pending_paste.take() {
+            match key.code {
+                KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    ensure_scroll_to_bottom(state).await;
+                    if let Err(e) = client.write_all(&super::paste_payload(&data, false)).await {
+                        app.error = Some(e);
+                    }
+                }
+                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {}
+                _ => *pending_paste = Some(data),
+            }
+            return KeyFlow::Continue;
+        }
+
+        // The snippet palette captures all keys while open
+        if let Some(palette) = snippet_palette {
+            match key.code {
+                KeyCode::Esc => {
+                    if palette.prompt.is_some() {
+                        palette.cancel_prompt();
+                    } else {
+                        *snippet_palette = None;
+                    }
+                }
+                KeyCode::Enter => {
+                    if let Some(command) = palette.confirm() {
+                        *snippet_palette = None;
+                        ensure_scroll_to_bottom(state).await;
+                        if let Err(e) = client.write_all(command.as_bytes()).await {
+                            app.error = Some(e);
+                        }
+                    }
+                }
+                KeyCode::Up => palette.select_prev(),
+                KeyCode::Down => palette.select_next(),
+                KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    palette.select_prev();
+                }
+                KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    palette.select_next();
+                }
+                KeyCode::Backspace => palette.pop_char(),
+                KeyCode::Char(ch) if is_filter_text(&key, ch) => palette.push_char(ch),
+                _ => {}
+            }
+            return KeyFlow::Continue;
+        }
+
+        // Check if in search mode first; any key also acknowledges a pending
+        // bell and tells "notify when idle" whether a command was started
         let search_active = {
-            let guard = state.lock().await;
+            let mut guard = state.lock().await;
+            guard.acknowledge_bell();
+            if !guard.search.active {
+                guard.idle_watch_key(key.code == KeyCode::Enter);
+            }
             guard.search.active
         };
 
         if search_active {
-            return handle_search_key(state, key).await;
+            let state = state.clone();
+            return handle_search_key(app, &state, key).await;
         }
 
         // Determine interactive mode (full-screen alt buffer or application cursor)
@@ -170,6 +511,81 @@ pub async fn handle_connected_key<B: Backend + Write>(app: &mut App<B>, key: Key
                 let mut guard = state.lock().await;
                 guard.search.enter();
             }
+            // Open the snippet palette with F2: this connection's snippets, then global ones
+            KeyCode::F(2) => {
+                let snippets: Vec<_> = connection
+                    .snippets
+                    .iter()
+                    .chain(app.config.snippets())
+                    .cloned()
+                    .collect();
+                if snippets.is_empty() {
+                    app.info = Some("No snippets configured".to_string());
+                } else {
+                    *snippet_palette = Some(crate::SnippetPalette::new(snippets));
+                }
+            }
+            // Enter copy mode with F6: select from the scrollback with vi keys
+            KeyCode::F(6) => {
+                let guard = state.lock().await;
+                *copy_mode = crate::terminal::CopyMode::new(&guard);
+            }
+            // Open the URL picker with F7: links on screen and in the scrollback
+            KeyCode::F(7) => {
+                let links = state.lock().await.links();
+                if links.is_empty() {
+                    app.info = Some("No links in the terminal".to_string());
+                } else {
+                    *url_picker = Some(crate::terminal::UrlPicker::new(links));
+                }
+            }
+            // Open the export menu with F3: save the scrollback or screen
+            KeyCode::F(3) => {
+                *export_menu = Some(crate::terminal::ExportMenu::default());
+            }
+            // Start or stop recording the session with F4
+            KeyCode::F(4) => {
+                let mut guard = state.lock().await;
+                if let Some(path) = guard.stop_recording() {
+                    app.info = Some(format!("Recording saved to {}", path.display()));
+                } else {
+                    match guard.start_recording(&connection.display_name) {
+                        Ok(path) => app.info = Some(format!("Recording to {}", path.display())),
+                        Err(e) => app.error = Some(e),
+                    }
+                }
+            }
+            // Jump between the prompts marked by the shell with Shift+Up/Down
+            KeyCode::Up | KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                let mut guard = state.lock().await;
+                if guard.has_shell_integration() {
+                    guard.jump_to_prompt(key.code == KeyCode::Up);
+                } else if let Some(seq) = encode_key_event_to_ansi(false, &key) {
+                    drop(guard);
+                    ensure_scroll_to_bottom(state).await;
+                    if let Err(e) = client.write_all(&seq).await {
+                        app.error = Some(e);
+                    }
+                }
+            }
+            // Toggle "notify when idle" with F5; readline and zsh leave it unbound
+            KeyCode::F(5) => {
+                let mut guard = state.lock().await;
+                if guard.idle_watch().is_some() {
+                    guard.set_idle_watch(None);
+                    app.info = Some("Notify when idle: off".to_string());
+                } else {
+                    let after = app.config.idle_notify_after();
+                    guard.set_idle_watch(Some(after));
+                    drop(guard);
+                    crate::app::spawn_idle_poller(state);
+                    app.info = Some(format!(
+                        "Notify when idle: on\n\nYou'll be told when the shell's prompt returns, \
+                         or when output stops for {}s after a command",
+                        after.as_secs()
+                    ));
+                }
+            }
             KeyCode::Esc => {
                 let guard = state.lock().await;
                 let (in_alt, app_cursor) =
@@ -241,12 +657,24 @@ pub async fn handle_connected_key<B: Backend + Write>(app: &mut App<B>, key: Key
 }
 
 /// Handle key events while in search mode
-async fn handle_search_key(
+async fn handle_search_key<B: Backend + Write>(
+    app: &mut App<B>,
     state: &std::sync::Arc<tokio::sync::Mutex<crate::ui::TerminalState>>,
     key: KeyEvent,
 ) -> KeyFlow {
     let mut guard = state.lock().await;
 
+    // Alt+R/C/W toggle regex, case-sensitive and whole-word matching in either mode
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        match key.code {
+            KeyCode::Char('r') => guard.search.toggle_regex(),
+            KeyCode::Char('c') => guard.search.toggle_case_sensitive(),
+            KeyCode::Char('w') => guard.search.toggle_whole_word(),
+            _ => {}
+        }
+        return KeyFlow::Continue;
+    }
+
     // Check if we're in input mode or navigation mode
     if guard.search.is_inputting() {
         // INPUT MODE: typing the search query
@@ -263,6 +691,13 @@ async fn handle_search_key(
             KeyCode::Backspace => {
                 guard.search.pop_char();
             }
+            // Recall earlier queries
+            KeyCode::Up => {
+                guard.search.history_prev();
+            }
+            KeyCode::Down => {
+                guard.search.history_next();
+            }
             // Add character to search query (including n, p, q, etc.)
             KeyCode::Char(ch) => {
                 guard.search.push_char(ch);
@@ -295,6 +730,14 @@ async fn handle_search_key(
                 guard.search.pop_char();
                 guard.search.edit();
             }
+            // Copy every line with a match
+            KeyCode::Char('y') => {
+                let text = guard.matching_lines_text();
+                drop(guard);
+                if let Some(text) = text {
+                    app.copy_text_to_clipboard(text);
+                }
+            }
             _ => {}
         }
     }
@@ -320,4 +763,19 @@ mod tests {
             assert_eq!(seq, expected);
         }
     }
+
+    #[test]
+    fn test_only_text_reaches_the_snippet_filter() {
+        let key = |ch, modifiers| (KeyEvent::new(KeyCode::Char(ch), modifiers), ch);
+        let typed = |(key, ch): (KeyEvent, char)| is_filter_text(&key, ch);
+        assert!(typed(key('a', KeyModifiers::NONE)));
+        assert!(typed(key('A', KeyModifiers::SHIFT)));
+        assert!(!typed(key('a', KeyModifiers::CONTROL)));
+        assert!(!typed(key('a', KeyModifiers::ALT)));
+        assert!(!typed(key('a', KeyModifiers::CONTROL | KeyModifiers::ALT)));
+        assert_eq!(
+            typed(key('@', KeyModifiers::CONTROL | KeyModifiers::ALT)),
+            cfg!(windows)
+        );
+    }
 }
@@ -64,6 +64,14 @@ pub async fn handle_connection_list_key<B: Backend + Write>(
             // Open file explorer for the selected connection
             let selected_idx = app.current_selected();
             if let Some(conn) = app.config.connections().get(selected_idx).cloned() {
+                if !conn.kind.is_ssh() {
+                    app.set_info(format!(
+                        "The file explorer needs SSH, but {} is a {} connection",
+                        conn.display_name,
+                        conn.kind.label()
+                    ));
+                    return KeyFlow::Continue;
+                }
                 let _ = app.config.touch_last_used(&conn.id);
                 let return_from = crate::ConnectingSource::ConnectionList {
                     file_explorer: true,
@@ -74,6 +82,7 @@ pub async fn handle_connection_list_key<B: Backend + Write>(
                         conn.clone(),
                         cols,
                         rows,
+                        app.session_pool.clone(),
                     );
                 let connection_name = conn.display_name.clone();
                 app.go_to_connecting(
@@ -86,6 +95,29 @@ pub async fn handle_connection_list_key<B: Backend + Write>(
                 );
             }
         }
+        KeyCode::Char('l') | KeyCode::Char('L') => {
+            // Open a local shell through the "Local" pseudo-connection
+            let conn = crate::config::manager::Connection::local();
+            let (cols, rows) = app.ssh_terminal_size().unwrap_or((80, 24));
+            let (cancel_token, receiver) = crate::async_ssh_client::SshSession::initiate_connection(
+                conn.clone(),
+                cols,
+                rows,
+                app.session_pool.clone(),
+            );
+            let connection_name = conn.display_name.clone();
+            let return_from = crate::ConnectingSource::ConnectionList {
+                file_explorer: false,
+            };
+            app.go_to_connecting(
+                conn,
+                connection_name,
+                app.current_selected(),
+                return_from,
+                cancel_token,
+                receiver,
+            );
+        }
         KeyCode::Char('p') | KeyCode::Char('P') => {
             // Open port forwarding manager
             app.go_to_port_forwarding_list().await;
@@ -104,6 +136,7 @@ pub async fn handle_connection_list_key<B: Backend + Write>(
                         conn.clone(),
                         cols,
                         rows,
+                        app.session_pool.clone(),
                     );
                 let connection_name = conn.display_name.clone();
                 let return_from = crate::ConnectingSource::ConnectionList {
@@ -520,45 +520,59 @@ pub async fn handle_file_explorer_key<B: Backend + Write>(
                     && matches!(left_pane, FileExplorerPane::Local);
 
                 if is_dir {
-                    // On a remote directory: open SSH terminal there
-                    if is_local {
-                        app.info = Some("Cannot open terminal on local directory".to_string());
-                        app.mark_redraw();
-                        return KeyFlow::Continue;
-                    }
-
+                    // Open a terminal there: a local shell on a local directory,
+                    // otherwise a channel on the pane's SSH session
                     let target_path = current.path().to_string_lossy().into_owned();
-                    let (session_arc, conn_name) = match active_pane {
-                        ActivePane::Right => (ssh_session.clone(), connection_name.clone()),
-                        ActivePane::Left => match left_pane {
-                            FileExplorerPane::RemoteSsh {
-                                connection_name: cn,
-                                ..
-                            } => match left_session {
-                                Some(s) => (s.clone(), cn.clone()),
-                                None => {
-                                    app.info =
-                                        Some("Cannot open terminal: no SSH session".to_string());
-                                    app.mark_redraw();
-                                    return KeyFlow::Continue;
+                    let remote = if is_local {
+                        None
+                    } else {
+                        Some(match active_pane {
+                            ActivePane::Right => (ssh_session.clone(), ssh_connection.clone()),
+                            ActivePane::Left => match left_pane {
+                                FileExplorerPane::RemoteSsh { connection, .. } => {
+                                    match left_session {
+                                        Some(s) => (s.clone(), connection.clone()),
+                                        None => {
+                                            app.info = Some(
+                                                "Cannot open terminal: no SSH session".to_string(),
+                                            );
+                                            app.mark_redraw();
+                                            return KeyFlow::Continue;
+                                        }
+                                    }
                                 }
+                                FileExplorerPane::Local => unreachable!(),
                             },
-                            FileExplorerPane::Local => unreachable!(),
-                        },
+                        })
                     };
                     let return_to_idx = *return_to;
 
-                    // Open terminal channel on existing session
                     let (cols, rows) = app.ssh_terminal_size().unwrap_or((80, 24));
-                    let mut client = match crate::async_ssh_client::SshSession::open_terminal_on(
-                        &session_arc,
-                        cols,
-                        rows,
-                        Some(&target_path),
-                    )
-                    .await
-                    {
-                        Ok(c) => c,
+                    let opened = match remote {
+                        None => {
+                            let connection = crate::config::manager::Connection::local();
+                            crate::async_ssh_client::SshSession::open_local_terminal(
+                                &connection,
+                                cols,
+                                rows,
+                                Some(&target_path),
+                            )
+                            .map(|client| (client, connection))
+                        }
+                        Some((session_arc, terminal_connection)) => {
+                            crate::async_ssh_client::SshSession::open_terminal_on(
+                                &session_arc,
+                                &terminal_connection,
+                                cols,
+                                rows,
+                                Some(&target_path),
+                            )
+                            .await
+                            .map(|client| (client, terminal_connection))
+                        }
+                    };
+                    let (mut client, terminal_connection) = match opened {
+                        Ok(opened) => opened,
                         Err(e) => {
                             app.set_error(e);
                             return KeyFlow::Continue;
@@ -606,10 +620,7 @@ pub async fn handle_file_explorer_key<B: Backend + Write>(
                     };
 
                     // Set up terminal + read loop
-                    let scrollback = app.config.terminal_scrollback_lines();
-                    let state = std::sync::Arc::new(tokio::sync::Mutex::new(
-                        crate::ui::TerminalState::new_with_scrollback(rows, cols, scrollback),
-                    ));
+                    let state = app.new_terminal_state(&terminal_connection, rows, cols);
                     let reader = client.take_reader().expect("reader already taken");
                     let cancel_token = tokio_util::sync::CancellationToken::new();
                     let cancel_for_task = cancel_token.clone();
@@ -626,7 +637,7 @@ pub async fn handle_file_explorer_key<B: Backend + Write>(
                     });
 
                     app.go_to_connected_with_return(
-                        conn_name,
+                        terminal_connection,
                         client,
                         state,
                         return_to_idx,
@@ -955,6 +966,7 @@ pub async fn handle_file_explorer_key<B: Backend + Write>(
                         }
                     });
 
+                    let pool = app.session_pool.clone();
                     tokio::spawn(async move {
                         // Phase 1: Create destination directories
                         if !all_dirs_to_create.is_empty() {
@@ -962,8 +974,12 @@ pub async fn handle_file_explorer_key<B: Backend + Write>(
                                 match mode {
                                     crate::ui::ScpMode::Send => {
                                         // Dest is right SSH
-                                        create_remote_dirs(&ssh_connection, &all_dirs_to_create)
-                                            .await
+                                        create_remote_dirs(
+                                            &pool,
+                                            &ssh_connection,
+                                            &all_dirs_to_create,
+                                        )
+                                        .await
                                     }
                                     crate::ui::ScpMode::Receive => {
                                         // Dest is left SSH
@@ -972,8 +988,12 @@ pub async fn handle_file_explorer_key<B: Backend + Write>(
                                             connection,
                                         } = &left_pane_for_transfer
                                         {
-                                            create_remote_dirs(connection, &all_dirs_to_create)
-                                                .await
+                                            create_remote_dirs(
+                                                &pool,
+                                                connection,
+                                                &all_dirs_to_create,
+                                            )
+                                            .await
                                         } else {
                                             create_local_dirs(&all_dirs_to_create).await
                                         }
@@ -983,8 +1003,12 @@ pub async fn handle_file_explorer_key<B: Backend + Write>(
                                 match mode {
                                     crate::ui::ScpMode::Send => {
                                         // Dest is remote SSH
-                                        create_remote_dirs(&ssh_connection, &all_dirs_to_create)
-                                            .await
+                                        create_remote_dirs(
+                                            &pool,
+                                            &ssh_connection,
+                                            &all_dirs_to_create,
+                                        )
+                                        .await
                                     }
                                     crate::ui::ScpMode::Receive => {
                                         // Dest is local filesystem
@@ -1274,16 +1298,19 @@ pub async fn handle_file_explorer_key<B: Backend + Write>(
     }
 }
 
-/// Create directories on a remote host via a new SFTP session.
+/// Create directories on a remote host via an SFTP channel on the pooled session.
 async fn create_remote_dirs(
+    pool: &crate::async_ssh_client::SessionPool,
     connection: &crate::config::manager::Connection,
     dirs: &[String],
 ) -> crate::error::Result<()> {
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let (channel, _lease, _) = pool.open_session_channel(connection, None, &cancel).await?;
     let sftp = crate::async_ssh_client::SshSession::setup_sftp_session(
-        None,
+        Some(channel),
         connection,
         None,
-        &tokio_util::sync::CancellationToken::new(),
+        &cancel,
     )
     .await?;
     for dir in dirs {
@@ -1338,9 +1365,13 @@ async fn do_edit_file<B: Backend + Write>(
             .await
             .unwrap_or(false)
     } else {
-        crate::file_edit::check_remote_binary(remote_connection.as_ref(), file_path)
-            .await
-            .unwrap_or(false)
+        crate::file_edit::check_remote_binary(
+            &app.session_pool,
+            remote_connection.as_ref(),
+            file_path,
+        )
+        .await
+        .unwrap_or(false)
     };
     if is_binary {
         app.error = Some(crate::error::AppError::SftpError(
@@ -1364,7 +1395,7 @@ async fn do_edit_file<B: Backend + Write>(
     let result = if is_local {
         crate::file_edit::edit_local_file(file_path, &tick_tx).await
     } else if let Some(conn) = remote_connection {
-        crate::file_edit::edit_remote_file(file_path, &conn, &tick_tx).await
+        crate::file_edit::edit_remote_file(&app.session_pool, file_path, &conn, &tick_tx).await
     } else {
         Err(crate::error::AppError::SftpError(
             "Could not determine connection for remote file".to_string(),
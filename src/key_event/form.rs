@@ -8,8 +8,21 @@ use super::KeyFlow;
 use crate::config::manager::AuthMethod;
 use crate::config::manager::Connection;
 use crate::error::AppError;
+use crate::ui::connection::FocusField;
 use crate::{App, AppMode};
 
+fn handle_form_input(form: &mut crate::ui::ConnectionForm, key: KeyEvent) {
+    if form.focus == FocusField::Kind {
+        match key.code {
+            KeyCode::Char(' ') | KeyCode::Right => form.cycle_kind(true),
+            KeyCode::Left => form.cycle_kind(false),
+            _ => {}
+        }
+    } else if let Some(textarea) = form.focused_textarea_mut() {
+        textarea.input(Input::from(key));
+    }
+}
+
 pub async fn handle_form_new_key<B: Backend + Write>(app: &mut App<B>, key: KeyEvent) -> KeyFlow {
     match key.code {
         KeyCode::Esc => {
@@ -135,12 +148,16 @@ pub async fn handle_form_new_key<B: Backend + Write>(app: &mut App<B>, key: KeyE
 
                         let mut conn = Connection::new(
                             form.get_host_value().trim().to_string(),
-                            form.get_port_value()
-                                .parse::<u16>()
-                                .unwrap_or(app.config.default_port()),
+                            form.port_or_default(app.config.default_port()),
                             user,
                             auth_method,
                         );
+                        conn.kind = form.kind;
+                        // Telnet and raw TCP log in inside the session
+                        if !conn.kind.is_ssh() {
+                            conn.username.clear();
+                            conn.auth_method = AuthMethod::None;
+                        }
                         if !form.get_display_name_value().trim().is_empty() {
                             conn.set_display_name(form.get_display_name_value().trim().to_string());
                         }
@@ -151,6 +168,7 @@ pub async fn handle_form_new_key<B: Backend + Write>(app: &mut App<B>, key: KeyE
                                 conn.clone(),
                                 cols,
                                 rows,
+                                app.session_pool.clone(),
                             );
                         let connection_name = conn.display_name.clone();
                         let return_from = crate::ConnectingSource::FormNew {
@@ -173,10 +191,10 @@ pub async fn handle_form_new_key<B: Backend + Write>(app: &mut App<B>, key: KeyE
             }
         }
         _ => {
-            // Use textarea's built-in input handling for all other keys
+            // Space, Left and Right change the kind; other keys go to the
+            // focused text field
             if let AppMode::FormNew { form, .. } = &mut app.mode {
-                let textarea = form.focused_textarea_mut();
-                textarea.input(Input::from(key));
+                handle_form_input(form, key);
             }
         }
     }
@@ -221,6 +239,7 @@ pub async fn handle_form_edit_key<B: Backend + Write>(app: &mut App<B>, key: Key
                 let new_private_key_path = form.get_private_key_path_value().trim().to_string();
 
                 let mut updated = original.clone();
+                updated.kind = form.kind;
                 updated.host = form.get_host_value().trim().to_string();
                 let parsed_port = match form.get_port_value().parse::<u16>() {
                     Ok(p) => p,
@@ -231,7 +250,11 @@ pub async fn handle_form_edit_key<B: Backend + Write>(app: &mut App<B>, key: Key
                 };
                 updated.port = parsed_port;
                 updated.username = form.get_username_value().trim().to_string();
-                updated.auth_method = if new_private_key_path.is_empty() {
+                updated.auth_method = if !updated.kind.is_ssh() {
+                    // Telnet and raw TCP log in inside the session
+                    updated.username.clear();
+                    AuthMethod::None
+                } else if new_private_key_path.is_empty() {
                     AuthMethod::Password(new_password.into())
                 } else {
                     AuthMethod::PublicKey {
@@ -259,10 +282,10 @@ pub async fn handle_form_edit_key<B: Backend + Write>(app: &mut App<B>, key: Key
             }
         }
         _ => {
-            // Use textarea's built-in input handling for all other keys
+            // Space, Left and Right change the kind; other keys go to the
+            // focused text field
             if let AppMode::FormEdit { form, .. } = &mut app.mode {
-                let textarea = form.focused_textarea_mut();
-                textarea.input(Input::from(key));
+                handle_form_input(form, key);
             }
         }
     }
@@ -1,6 +1,8 @@
 use std::io::Write;
 
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::prelude::Backend;
 
 use crate::error::AppError;
@@ -90,23 +92,62 @@ pub async fn handle_key_event<B: Backend + Write>(app: &mut App<B>, key: KeyEven
     }
 }
 
+/// Bytes to send for a paste. With bracketed paste on, the text is wrapped
+/// in `ESC[200~`/`ESC[201~` and ESC bytes are removed from it, so no end
+/// marker (however it is nested) can end the paste early.
+pub(crate) fn paste_payload(data: &str, bracketed: bool) -> Vec<u8> {
+    if !bracketed {
+        return data.as_bytes().to_vec();
+    }
+    let body = data.replace('\x1b', "");
+    let mut out = Vec::with_capacity(body.len() + 12);
+    out.extend_from_slice(b"\x1b[200~");
+    out.extend_from_slice(body.as_bytes());
+    out.extend_from_slice(b"\x1b[201~");
+    out
+}
+
 /// Paste event handler; dispatches by AppMode
 pub async fn handle_paste_event<B: Backend + Write>(app: &mut App<B>, data: &str) {
     match &mut app.mode {
         AppMode::FormNew { form, .. } => {
-            let textarea = form.focused_textarea_mut();
-            textarea.insert_str(data);
+            if let Some(textarea) = form.focused_textarea_mut() {
+                textarea.insert_str(data);
+            }
         }
         AppMode::FormEdit { form, .. } => {
-            let textarea = form.focused_textarea_mut();
-            textarea.insert_str(data);
+            if let Some(textarea) = form.focused_textarea_mut() {
+                textarea.insert_str(data);
+            }
         }
         AppMode::Connected {
             name: _,
             client,
             state,
+            reconnect,
+            snippet_palette,
+            url_picker,
+            export_menu,
+            copy_mode,
+            pending_paste,
+            close_prompt,
+            zmodem_picker,
             ..
         } => {
+            if reconnect.is_some()
+                || *close_prompt
+                || zmodem_picker.is_some()
+                || pending_paste.is_some()
+                || url_picker.is_some()
+                || export_menu.is_some()
+                || copy_mode.is_some()
+            {
+                return;
+            }
+            if let Some(palette) = snippet_palette {
+                palette.push_str(data);
+                return;
+            }
             let mut guard = state.lock().await;
             if guard.search.is_inputting() {
                 guard.search.push_str(data);
@@ -115,7 +156,15 @@ pub async fn handle_paste_event<B: Backend + Write>(app: &mut App<B>, data: &str
             if guard.scrollback() > 0 {
                 guard.scroll_to_bottom();
             }
-            if let Err(e) = client.write_all(data.as_bytes()).await {
+            let bracketed = guard.bracketed_paste();
+            let in_shell = !guard.is_alternate_screen();
+            drop(guard);
+
+            if !bracketed && in_shell && data.trim_end().contains('\n') {
+                *pending_paste = Some(data.to_string());
+                return;
+            }
+            if let Err(e) = client.write_all(&paste_payload(data, bracketed)).await {
                 app.error = Some(e);
             }
         }
@@ -181,11 +230,75 @@ async fn handle_connecting_key<B: Backend + Write>(app: &mut App<B>, key: KeyEve
 }
 
 pub async fn handle_mouse_event<B: Backend + Write>(app: &mut App<B>, event: MouseEvent) {
-    let state = match &app.mode {
-        AppMode::Connected { state, .. } => state.clone(),
+    let (state, passthrough) = match &app.mode {
+        AppMode::Connected {
+            state,
+            reconnect,
+            snippet_palette,
+            url_picker,
+            export_menu,
+            copy_mode,
+            zmodem_picker,
+            ..
+        } => (
+            state.clone(),
+            reconnect.is_none()
+                && snippet_palette.is_none()
+                && url_picker.is_none()
+                && export_menu.is_none()
+                && copy_mode.is_none()
+                && zmodem_picker.is_none(),
+        ),
         _ => return,
     };
 
+    if let Some(point) = app.viewport_cell_at(event.column, event.row) {
+        match event.kind {
+            // Underline the link under the pointer
+            MouseEventKind::Moved => {
+                let mut guard = state.lock().await;
+                let link = guard.link_at(point.row, point.col);
+                guard.set_hovered_link(link);
+            }
+            // Ctrl+click opens it, even in programs that use the mouse
+            MouseEventKind::Down(MouseButton::Left)
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                let link = state.lock().await.link_at(point.row, point.col);
+                if let Some(link) = link {
+                    if link.hidden_target {
+                        // Show where the link really goes before opening it
+                        let mut picker =
+                            crate::terminal::UrlPicker::new(state.lock().await.links());
+                        picker.select_url(&link.url);
+                        if let AppMode::Connected { url_picker, .. } = &mut app.mode {
+                            *url_picker = Some(picker);
+                        }
+                    } else {
+                        app.open_link(&link.url);
+                    }
+                    return;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Programs that enabled mouse tracking (htop, vim, tmux, mc) get the
+    // events; holding Shift keeps the mouse for local selection
+    if passthrough
+        && !event.modifiers.contains(KeyModifiers::SHIFT)
+        && let Some(point) = app.viewport_cell_at(event.column, event.row)
+    {
+        let mut guard = state.lock().await;
+        if guard.mouse_grabbed() && guard.scrollback() == 0 {
+            guard.send_mouse_event(event.kind, point.row, point.col, event.modifiers);
+            drop(guard);
+            app.clear_selection();
+            return;
+        }
+    }
+
     match event.kind {
         MouseEventKind::Down(MouseButton::Middle) => {
             if let Some(text) = app.get_text_from_clipboard() {
@@ -514,6 +627,20 @@ fn classify_char(ch: char) -> CharKind {
 mod tests {
     use super::*;
 
+    #[test]
+    fn paste_payload_wraps_only_in_bracketed_mode() {
+        assert_eq!(paste_payload("ls\npwd", false), b"ls\npwd");
+        assert_eq!(
+            paste_payload("echo hi\x1b[201~rm -rf ~", true),
+            b"\x1b[200~echo hi[201~rm -rf ~\x1b[201~"
+        );
+        // Removing one end marker must not leave another behind
+        assert_eq!(
+            paste_payload("\x1b[20\x1b[201~1~rm -rf ~", true),
+            b"\x1b[200~[20[201~1~rm -rf ~\x1b[201~"
+        );
+    }
+
     #[test]
     fn classify_characters_follow_iterm_defaults() {
         assert_eq!(classify_char('a'), CharKind::Word);
@@ -65,13 +65,23 @@ async fn restore_after_scp_progress<B: Backend + Write>(
             app.go_to_connection_list_with_selected(current_selected);
         }
         crate::ScpReturnMode::Connected {
-            name,
+            connection,
             client,
             state,
             current_selected,
             cancel_token,
+            return_to_explorer,
         } => {
-            app.go_to_connected(name, client, state, current_selected, cancel_token);
+            // Stops a ZMODEM transfer that is still running
+            state.lock().await.cancel_zmodem();
+            app.go_to_connected_with_return(
+                connection,
+                client,
+                state,
+                current_selected,
+                cancel_token,
+                return_to_explorer.map(|mode| *mode),
+            );
         }
         crate::ScpReturnMode::FileExplorer {
             connection_name,
@@ -5,7 +5,10 @@ mod file_edit;
 mod filesystem;
 mod key_event;
 mod mode_state;
+mod persistent_session;
+mod player;
 mod search_state;
+mod snippet;
 mod ui;
 
 // New modules from refactoring
@@ -13,12 +16,14 @@ mod app;
 mod events;
 mod terminal;
 mod transfer;
+mod transport;
 mod utils;
+mod zmodem;
 
 // Re-export commonly used types
 pub use app::{
     ActivePane, App, AppMode, ConnectingSource, CopyDirection, CopyOperation, FileExplorerPane,
-    LeftExplorer, ScpReturnMode, create_search_textarea,
+    LeftExplorer, ReconnectState, ScpReturnMode, create_search_textarea,
 };
 pub use async_ssh_client::expand_tilde;
 pub use error::{AppError, Result};
@@ -27,7 +32,9 @@ pub use mode_state::{
     ConnectionSelectorState, DeleteConfirmationState, FormWithConnectionSelector,
     ListSelectionState, SourceSelectorState,
 };
+pub use player::play_recording;
 pub use search_state::SearchState;
+pub use snippet::{PlaceholderPrompt, SnippetPalette};
 pub use transfer::{
     ScpFileProgress, ScpFileResult, ScpProgress, ScpResult, ScpTransferProgress, ScpTransferSpec,
     TransferState,
@@ -37,6 +44,12 @@ pub use utils::{init_panic_hook, init_tracing};
 // Implement ByteProcessor for TerminalState
 impl async_ssh_client::ByteProcessor for ui::TerminalState {
     fn process_bytes(&mut self, bytes: &[u8]) {
+        let bytes = self.intercept_zmodem(bytes);
+        if bytes.is_empty() {
+            return;
+        }
+        self.record_output(bytes);
+        self.log_output(bytes);
         self.process_bytes(bytes);
     }
 }
@@ -2,13 +2,13 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use crossterm::event;
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 use tokio::{select, sync::mpsc, time};
 
-use termirs::{App, AppEvent, Result, TickControl, init_panic_hook, init_tracing};
+use termirs::{App, AppEvent, Result, TickControl, init_panic_hook, init_tracing, play_recording};
 
 /// A modern, async SSH terminal client
 #[derive(Parser, Debug)]
@@ -23,6 +23,18 @@ struct Args {
     /// This option requires --log to be enabled
     #[arg(long, value_name = "LEVEL", default_value = "info")]
     log_level: String,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Replay a session recording (asciicast v2 .cast file)
+    Play {
+        /// Recording to play
+        file: std::path::PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -39,6 +51,10 @@ async fn main() -> Result<()> {
 
     init_panic_hook();
 
+    if let Some(Command::Play { file }) = args.command {
+        return play_recording(&file);
+    }
+
     // Setup Crossterm terminal
     tracing::debug!("Initializing terminal backend");
     let stdout = std::io::stdout();
@@ -0,0 +1,131 @@
+//! Persistent sessions: the terminal runs inside tmux (or screen) on the
+//! host, so a dropped connection leaves the shell running and the next
+//! connection re-attaches to it.
+
+use crate::utils::shell_quote;
+
+/// Terminal multiplexer found on the host
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    pub fn label(self) -> &'static str {
+        match self {
+            Multiplexer::Tmux => "tmux",
+            Multiplexer::Screen => "screen",
+        }
+    }
+}
+
+/// A named multiplexer session on the host
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PersistentSession {
+    pub multiplexer: Multiplexer,
+    pub name: String,
+    /// Whether the probe found the session already running on the host
+    /// when we (re)connected, i.e. this connection picked up where an
+    /// earlier one left off. After a reconnect, `false` means the session
+    /// was gone and a new one was started.
+    pub reattached: bool,
+}
+
+/// Name of the multiplexer session of a connection
+pub fn session_name(connection_id: &str) -> String {
+    format!("termirs-{connection_id}")
+}
+
+/// Shell script run with `exec` before the terminal starts. It prints the
+/// multiplexer it found, then `alive` if the session already exists.
+pub fn probe_command(name: &str) -> String {
+    let name = shell_quote(name);
+    format!(
+        "if command -v tmux >/dev/null 2>&1; then echo tmux; \
+         tmux has-session -t ={name} 2>/dev/null && echo alive; \
+         elif command -v screen >/dev/null 2>&1; then echo screen; \
+         screen -ls {name} 2>/dev/null | grep -q '\\.'{name} && echo alive; \
+         fi; true"
+    )
+}
+
+/// Read the output of [`probe_command`]; `None` if neither tmux nor screen
+/// is installed
+pub fn parse_probe(name: &str, output: &str) -> Option<PersistentSession> {
+    let mut lines = output.lines().map(str::trim);
+    let multiplexer = match lines.next()? {
+        "tmux" => Multiplexer::Tmux,
+        "screen" => Multiplexer::Screen,
+        _ => return None,
+    };
+    Some(PersistentSession {
+        multiplexer,
+        name: name.to_string(),
+        reattached: lines.any(|line| line == "alive"),
+    })
+}
+
+impl PersistentSession {
+    /// Title bar status. Each (re)connect probes the host before attaching,
+    /// so once connected this is what the host reported. While reconnecting
+    /// the host can't be asked; the session was running when the connection
+    /// dropped, and that is all that is known.
+    pub fn status(&self, reconnecting: bool) -> &'static str {
+        if reconnecting {
+            "last seen running"
+        } else if self.reattached {
+            "re-attached"
+        } else {
+            "new session"
+        }
+    }
+
+    /// Command run instead of a login shell: attach to the session, creating
+    /// it if needed
+    pub fn attach_command(&self) -> String {
+        let name = shell_quote(&self.name);
+        match self.multiplexer {
+            Multiplexer::Tmux => format!("tmux new -A -s {name}"),
+            Multiplexer::Screen => format!("screen -xRR -S {name}"),
+        }
+    }
+
+    /// Command that ends the session and everything running in it
+    pub fn kill_command(&self) -> String {
+        let name = shell_quote(&self.name);
+        match self.multiplexer {
+            Multiplexer::Tmux => format!("tmux kill-session -t ={name}"),
+            Multiplexer::Screen => format!("screen -S {name} -X quit"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_probe() {
+        let name = session_name("abc");
+        assert_eq!(parse_probe(&name, ""), None);
+        assert_eq!(parse_probe(&name, "bash: tmux: not found\n"), None);
+
+        let session = parse_probe(&name, "tmux\nalive\n").unwrap();
+        assert_eq!(session.multiplexer, Multiplexer::Tmux);
+        assert!(session.reattached);
+        assert_eq!(session.status(false), "re-attached");
+        assert_eq!(session.status(true), "last seen running");
+        assert_eq!(session.attach_command(), "tmux new -A -s 'termirs-abc'");
+        assert_eq!(
+            session.kill_command(),
+            "tmux kill-session -t ='termirs-abc'"
+        );
+
+        let session = parse_probe(&name, "screen\n").unwrap();
+        assert_eq!(session.multiplexer, Multiplexer::Screen);
+        assert!(!session.reattached);
+        assert_eq!(session.status(false), "new session");
+        assert_eq!(session.attach_command(), "screen -xRR -S 'termirs-abc'");
+    }
+}
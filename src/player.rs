@@ -0,0 +1,288 @@
+//! `termirs play`: replays an asciicast recording through a `TerminalState`.
+
+use std::io::Stdout;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+use crate::config::manager::ConfigManager;
+use crate::config::theme::Theme;
+use crate::error::Result;
+use crate::terminal::{RecordedEvent, Recording};
+use crate::ui::{TerminalState, draw_terminal};
+
+/// How far Left/Right seek
+const SEEK_STEP: Duration = Duration::from_secs(5);
+/// Playback speeds cycled through with `+` and `-`
+const SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+const NORMAL_SPEED: usize = 2;
+/// Longest wait for input, so the position in the status line keeps moving
+const MAX_POLL: Duration = Duration::from_millis(100);
+
+/// Playback state: the recording and the terminal it has been applied to
+struct Player {
+    recording: Recording,
+    state: TerminalState,
+    /// Index of the next event to apply
+    next: usize,
+    position: Duration,
+    speed: usize,
+    paused: bool,
+}
+
+impl Player {
+    fn new(recording: Recording) -> Self {
+        let state = Self::initial_state(&recording);
+        Self {
+            recording,
+            state,
+            next: 0,
+            position: Duration::ZERO,
+            speed: NORMAL_SPEED,
+            paused: false,
+        }
+    }
+
+    fn initial_state(recording: &Recording) -> TerminalState {
+        let mut state = TerminalState::new(recording.header.height, recording.header.width);
+        // Nobody answers the recorded program, so its queries' replies, bells
+        // and clipboard writes are dropped instead of queued for the whole
+        // playback
+        drop(state.take_reply_receiver());
+        drop(state.take_alert_receiver());
+        drop(state.take_clipboard_receiver());
+        state
+    }
+
+    fn finished(&self) -> bool {
+        self.next >= self.recording.events.len()
+    }
+
+    /// Move forward by `elapsed` wall-clock time at the current speed
+    fn advance(&mut self, elapsed: Duration) {
+        if self.paused || self.finished() {
+            return;
+        }
+        let target = self.position + elapsed.mul_f64(SPEEDS[self.speed]);
+        self.seek(target);
+    }
+
+    /// Jump to `target`. Going backwards replays from the start, as terminal
+    /// output can't be undone.
+    fn seek(&mut self, target: Duration) {
+        let target = target.min(self.recording.duration());
+        if target < self.position {
+            self.state = Self::initial_state(&self.recording);
+            self.next = 0;
+        }
+        while let Some((time, event)) = self.recording.events.get(self.next) {
+            if *time > target {
+                break;
+            }
+            match event {
+                RecordedEvent::Output(data) => self.state.process_bytes(data.as_bytes()),
+                RecordedEvent::Resize { cols, rows } => self.state.resize(*rows, *cols),
+            }
+            self.next += 1;
+        }
+        self.position = target;
+    }
+
+    /// Wall-clock time until the next event is due
+    fn time_to_next_event(&self) -> Option<Duration> {
+        if self.paused {
+            return None;
+        }
+        let (time, _) = self.recording.events.get(self.next)?;
+        Some(
+            time.saturating_sub(self.position)
+                .div_f64(SPEEDS[self.speed]),
+        )
+    }
+}
+
+/// Restores the host terminal when playback ends, however it ends
+struct RawModeGuard;
+
+impl RawModeGuard {
+    fn enter() -> Result<Self> {
+        crossterm::terminal::enable_raw_mode()?;
+        crossterm::execute!(std::io::stdout(), crossterm::terminal::EnterAlternateScreen)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = crate::utils::restore_tui();
+    }
+}
+
+/// Play the recording at `path` until it is quit with `q` or `Esc`
+pub fn play_recording(path: &Path) -> Result<()> {
+    let recording = Recording::load(path)?;
+    // A broken config shouldn't stop playback; use the default theme
+    let theme = ConfigManager::new()
+        .map(|config| *config.theme())
+        .unwrap_or_default();
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    );
+    let title = match &recording.header.title {
+        Some(title) => format!("Replaying {title} ({name})"),
+        None => format!("Replaying {name}"),
+    };
+
+    let mut player = Player::new(recording);
+    let _guard = RawModeGuard::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+    terminal.clear()?;
+
+    let mut last_tick = Instant::now();
+    loop {
+        draw_player(&mut terminal, &mut player, &title, &theme)?;
+
+        let timeout = player
+            .time_to_next_event()
+            .map_or(MAX_POLL, |wait| wait.min(MAX_POLL));
+        if event::poll(timeout)?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char(' ') => {
+                    if player.finished() {
+                        player.seek(Duration::ZERO);
+                        player.paused = false;
+                    } else {
+                        player.paused = !player.paused;
+                    }
+                }
+                KeyCode::Right | KeyCode::Char('l') => player.seek(player.position + SEEK_STEP),
+                KeyCode::Left | KeyCode::Char('h') => {
+                    player.seek(player.position.saturating_sub(SEEK_STEP))
+                }
+                KeyCode::Home | KeyCode::Char('0') => player.seek(Duration::ZERO),
+                KeyCode::End | KeyCode::Char('$') => player.seek(player.recording.duration()),
+                KeyCode::Char('+') | KeyCode::Char('=') => {
+                    player.speed = (player.speed + 1).min(SPEEDS.len() - 1)
+                }
+                KeyCode::Char('-') => player.speed = player.speed.saturating_sub(1),
+                _ => {}
+            }
+        }
+
+        let now = Instant::now();
+        player.advance(now - last_tick);
+        last_tick = now;
+    }
+    Ok(())
+}
+
+fn draw_player(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    player: &mut Player,
+    title: &str,
+    theme: &Theme,
+) -> Result<()> {
+    let status = Line::from(vec![
+        Span::styled(
+            format!(
+                " {} {} / {}  {}x ",
+                if player.paused { "⏸" } else { "▶" },
+                format_time(player.position),
+                format_time(player.recording.duration()),
+                SPEEDS[player.speed]
+            ),
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            " Space: Pause | ←/→: Seek 5s | Home/End: Start/End | +/-: Speed | q: Quit",
+            Style::default()
+                .fg(theme.footer)
+                .add_modifier(Modifier::DIM),
+        ),
+    ]);
+
+    terminal.draw(|f| {
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(2), Constraint::Length(1)])
+            .split(f.area());
+        draw_terminal(sections[0], &mut player.state, title, f, None, None, theme);
+        f.render_widget(Paragraph::new(status), sections[1]);
+    })?;
+    Ok(())
+}
+
+/// `m:ss`
+fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen_text(state: &TerminalState) -> String {
+        let (rows, _) = state.screen_size();
+        (0..rows as i64)
+            .rev()
+            .filter_map(|rev_row| state.row_chars(rev_row))
+            .map(|chars| chars.iter().collect::<String>().trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_seek_and_speed() {
+        let recording = Recording::parse(concat!(
+            "{\"version\": 2, \"width\": 20, \"height\": 3}\n",
+            "[1.0, \"o\", \"one\\r\\n\"]\n",
+            "[2.0, \"o\", \"two\\r\\n\"]\n",
+            "[3.0, \"r\", \"30x4\"]\n",
+        ))
+        .unwrap();
+        let mut player = Player::new(recording);
+
+        player.advance(Duration::from_millis(1500));
+        assert_eq!(screen_text(&player.state).trim_end(), "one");
+        assert_eq!(
+            player.time_to_next_event(),
+            Some(Duration::from_millis(500))
+        );
+
+        player.speed = NORMAL_SPEED + 1;
+        assert_eq!(
+            player.time_to_next_event(),
+            Some(Duration::from_millis(250))
+        );
+        player.advance(Duration::from_millis(250));
+        assert_eq!(screen_text(&player.state).trim_end(), "one\ntwo");
+
+        // Seeking back replays from the start
+        player.seek(Duration::from_millis(1200));
+        assert_eq!(screen_text(&player.state).trim_end(), "one");
+
+        player.paused = true;
+        player.advance(Duration::from_secs(10));
+        assert_eq!(player.position, Duration::from_millis(1200));
+
+        player.seek(Duration::from_secs(60));
+        assert!(player.finished());
+        assert_eq!(player.position, Duration::from_secs(3));
+        assert_eq!(player.state.screen_size(), (4, 30));
+    }
+}
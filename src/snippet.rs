@@ -0,0 +1,279 @@
+use crate::config::manager::Snippet;
+
+/// State of the snippet palette opened from the connected terminal
+#[derive(Clone, Debug)]
+pub struct SnippetPalette {
+    snippets: Vec<Snippet>,
+    pub query: String,
+    pub selected: usize,
+    /// Set while the chosen snippet's placeholders are being filled in
+    pub prompt: Option<PlaceholderPrompt>,
+}
+
+/// Collects values for a snippet's `{{placeholder}}`s one at a time
+#[derive(Clone, Debug)]
+pub struct PlaceholderPrompt {
+    pub snippet_name: String,
+    command: String,
+    names: Vec<String>,
+    values: Vec<String>,
+    pub input: String,
+}
+
+impl SnippetPalette {
+    /// Create a palette over `snippets`, listed in the given order
+    pub fn new(snippets: Vec<Snippet>) -> Self {
+        Self {
+            snippets,
+            query: String::new(),
+            selected: 0,
+            prompt: None,
+        }
+    }
+
+    pub fn snippets(&self) -> &[Snippet] {
+        &self.snippets
+    }
+
+    /// Indices of the snippets matching the query, best match first
+    pub fn filtered(&self) -> Vec<usize> {
+        let mut scored: Vec<(i64, usize)> = self
+            .snippets
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, snippet)| {
+                let score = fuzzy_score(&self.query, &snippet.name)
+                    .max(fuzzy_score(&self.query, &snippet.command))?;
+                Some((score, idx))
+            })
+            .collect();
+        // Stable sort keeps config order among equal scores
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.into_iter().map(|(_, idx)| idx).collect()
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        match &mut self.prompt {
+            Some(prompt) => prompt.input.push(ch),
+            None => {
+                self.query.push(ch);
+                self.selected = 0;
+            }
+        }
+    }
+
+    pub fn push_str(&mut self, s: &str) {
+        for ch in s.chars().filter(|ch| !ch.is_control()) {
+            self.push_char(ch);
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        match &mut self.prompt {
+            Some(prompt) => {
+                prompt.input.pop();
+            }
+            None => {
+                self.query.pop();
+                self.selected = 0;
+            }
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        let len = self.filtered().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        let len = self.filtered().len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+
+    /// Confirm the current selection or placeholder value. Returns the
+    /// command once it is ready to send; `None` means more input is needed.
+    pub fn confirm(&mut self) -> Option<String> {
+        if let Some(prompt) = &mut self.prompt {
+            return prompt.submit();
+        }
+
+        let idx = *self.filtered().get(self.selected)?;
+        let snippet = &self.snippets[idx];
+        let names = placeholders(&snippet.command);
+        if names.is_empty() {
+            return Some(snippet.command.clone());
+        }
+
+        self.prompt = Some(PlaceholderPrompt {
+            snippet_name: snippet.name.clone(),
+            command: snippet.command.clone(),
+            names,
+            values: Vec::new(),
+            input: String::new(),
+        });
+        None
+    }
+
+    /// Leave the placeholder prompt and return to the list
+    pub fn cancel_prompt(&mut self) {
+        self.prompt = None;
+    }
+}
+
+impl PlaceholderPrompt {
+    /// Name of the placeholder currently being asked for
+    pub fn current_name(&self) -> &str {
+        &self.names[self.values.len()]
+    }
+
+    /// 1-based position of the current placeholder and the total count
+    pub fn progress(&self) -> (usize, usize) {
+        (self.values.len() + 1, self.names.len())
+    }
+
+    /// Accept the current input; returns the filled command after the last value
+    fn submit(&mut self) -> Option<String> {
+        self.values.push(std::mem::take(&mut self.input));
+        if self.values.len() < self.names.len() {
+            return None;
+        }
+
+        let values: Vec<(&str, &str)> = self
+            .names
+            .iter()
+            .map(String::as_str)
+            .zip(self.values.iter().map(String::as_str))
+            .collect();
+        Some(fill_placeholders(&self.command, &values))
+    }
+}
+
+/// Names of the `{{placeholder}}`s in `command`, in order of first appearance
+pub fn placeholders(command: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = command;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if !name.is_empty() && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+    names
+}
+
+/// Replace each `{{placeholder}}` with its value; unknown ones are left as is
+pub fn fill_placeholders(command: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(command.len());
+    let mut rest = command;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = after[..end].trim();
+        match values.iter().find(|(n, _)| *n == name) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Case-insensitive subsequence match. Consecutive characters and matches
+/// at word starts score higher; `None` means `text` doesn't match at all.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let mut score = 0;
+    let mut prev_matched = false;
+    let mut prev_char: Option<char> = None;
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+
+    for ch in text.chars() {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+        let matched = ch.to_lowercase().eq(std::iter::once(wanted));
+        if matched {
+            score += 1;
+            if prev_matched {
+                score += 5;
+            }
+            if prev_char.is_none_or(|c| !c.is_alphanumeric()) {
+                score += 3;
+            }
+            query_chars.next();
+        }
+        prev_matched = matched;
+        prev_char = Some(ch);
+    }
+
+    query_chars.peek().is_none().then_some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(name: &str, command: &str) -> Snippet {
+        Snippet {
+            name: name.to_string(),
+            command: command.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            placeholders("journalctl -u {{ service }} -n {{lines}} | grep {{service}}"),
+            vec!["service".to_string(), "lines".to_string()]
+        );
+        assert!(placeholders("echo {{}} {{unterminated").is_empty());
+        assert_eq!(
+            fill_placeholders(
+                "journalctl -u {{ service }} -n {{lines}} {{other}}",
+                &[("service", "nginx"), ("lines", "50")]
+            ),
+            "journalctl -u nginx -n 50 {{other}}"
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert!(fuzzy_score("rsv", "Restart service").is_some());
+        assert!(fuzzy_score("xyz", "Restart service").is_none());
+        assert!(fuzzy_score("tail", "Tail logs") > fuzzy_score("tail", "watch the api log"));
+    }
+
+    #[test]
+    fn test_palette_prompts_for_placeholders() {
+        let mut palette = SnippetPalette::new(vec![
+            snippet("Disk usage", "df -h"),
+            snippet("Tail logs", "journalctl -fu {{service}} -n {{lines}}"),
+        ]);
+
+        palette.push_str("tail");
+        assert_eq!(palette.filtered(), vec![1]);
+        assert_eq!(palette.confirm(), None);
+        assert_eq!(palette.prompt.as_ref().unwrap().current_name(), "service");
+
+        palette.push_str("nginx");
+        assert_eq!(palette.confirm(), None);
+        palette.push_str("20");
+        assert_eq!(
+            palette.confirm().as_deref(),
+            Some("journalctl -fu nginx -n 20")
+        );
+    }
+}
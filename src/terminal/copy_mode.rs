@@ -0,0 +1,374 @@
+//! Vi-like copy mode: a keyboard cursor moved through the scrollback to
+//! select and yank text.
+
+use wezterm_term::StableRowIndex;
+
+use crate::terminal::prompts::adjacent_prompt;
+use crate::terminal::selection::{
+    SelectionEndpoint, SelectionShape, make_selection_endpoint, order_selection_endpoints,
+};
+use crate::ui::TerminalState;
+
+/// Kind of visual selection, as started with `v`, `V` or Ctrl+V
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VisualMode {
+    Char,
+    Line,
+    Block,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    /// `w`: start of the next word
+    WordForward,
+    /// `b`: start of the current or previous word
+    WordBackward,
+    /// `0`
+    LineStart,
+    /// `$`: last non-blank column
+    LineEnd,
+    /// `gg`: oldest row in the scrollback
+    Top,
+    /// `G`: bottom row of the screen
+    Bottom,
+}
+
+/// State of copy mode in the connected terminal
+#[derive(Clone, Debug)]
+pub struct CopyMode {
+    /// Cursor position; rows count up from the bottom of the screen
+    pub cursor: SelectionEndpoint,
+    /// Where the visual selection was started, and its kind
+    visual: Option<(SelectionEndpoint, VisualMode)>,
+    /// The first `g` of `gg` was typed
+    pub pending_g: bool,
+    /// Bottom row of the screen when the rows above were last counted from it
+    bottom: StableRowIndex,
+}
+
+impl CopyMode {
+    /// Start at the terminal cursor, or at the bottom-left of the viewport
+    /// when scrolled back
+    pub fn new(state: &TerminalState) -> Option<Self> {
+        let (height, _) = state.screen_size();
+        let (row, col) = if state.scrollback() == 0 {
+            state.cursor_position()
+        } else {
+            (height.saturating_sub(1), 0)
+        };
+        Some(Self {
+            cursor: make_selection_endpoint(state, row, col)?,
+            visual: None,
+            pending_g: false,
+            bottom: state.bottom_stable_row(),
+        })
+    }
+
+    /// Keep the cursor and the selection on the same text after output
+    /// scrolled the screen up
+    pub fn follow_output(&mut self, state: &TerminalState) {
+        let bottom = state.bottom_stable_row();
+        let added = (bottom - self.bottom) as i64;
+        self.bottom = bottom;
+        if added == 0 {
+            return;
+        }
+        let max_rev = state.history_rows().saturating_sub(1) as i64;
+        let shift = |point: &mut SelectionEndpoint| {
+            point.rev_row = (point.rev_row + added).clamp(0, max_rev);
+        };
+        shift(&mut self.cursor);
+        if let Some((anchor, _)) = &mut self.visual {
+            shift(anchor);
+        }
+    }
+
+    pub fn visual(&self) -> Option<VisualMode> {
+        self.visual.map(|(_, mode)| mode)
+    }
+
+    /// Start a visual selection of this kind at the cursor; the same kind
+    /// again ends it, another kind switches to it
+    pub fn toggle_visual(&mut self, mode: VisualMode) {
+        self.visual = match self.visual {
+            Some((_, current)) if current == mode => None,
+            Some((anchor, _)) => Some((anchor, mode)),
+            None => Some((self.cursor, mode)),
+        };
+    }
+
+    pub fn clear_visual(&mut self) {
+        self.visual = None;
+    }
+
+    /// Endpoints and shape of the visual selection, if any
+    pub fn selection(&self) -> Option<(SelectionEndpoint, SelectionEndpoint, SelectionShape)> {
+        let (anchor, mode) = self.visual?;
+        Some(match mode {
+            VisualMode::Char => (anchor, self.cursor, SelectionShape::Linear),
+            VisualMode::Block => (anchor, self.cursor, SelectionShape::Block),
+            VisualMode::Line => {
+                let (top, bottom) = order_selection_endpoints(anchor, self.cursor);
+                (
+                    SelectionEndpoint {
+                        rev_row: top.rev_row,
+                        col: 0,
+                    },
+                    SelectionEndpoint {
+                        rev_row: bottom.rev_row,
+                        col: u16::MAX,
+                    },
+                    SelectionShape::Linear,
+                )
+            }
+        })
+    }
+
+    pub fn move_cursor(&mut self, motion: Motion, state: &TerminalState) {
+        let (_, width) = state.screen_size();
+        let max_rev = state.history_rows().saturating_sub(1) as i64;
+        let last_col = width.saturating_sub(1);
+        let cursor = &mut self.cursor;
+        match motion {
+            Motion::Left => cursor.col = cursor.col.saturating_sub(1),
+            Motion::Right => cursor.col = (cursor.col + 1).min(last_col),
+            Motion::Up => cursor.rev_row = (cursor.rev_row + 1).min(max_rev),
+            Motion::Down => cursor.rev_row = (cursor.rev_row - 1).max(0),
+            Motion::LineStart => cursor.col = 0,
+            Motion::LineEnd => {
+                cursor.col = state
+                    .row_chars(cursor.rev_row)
+                    .and_then(|chars| chars.iter().rposition(|c| !c.is_whitespace()))
+                    .map_or(0, |col| col as u16);
+            }
+            Motion::Top => {
+                *cursor = SelectionEndpoint {
+                    rev_row: max_rev,
+                    col: 0,
+                }
+            }
+            Motion::Bottom => *cursor = SelectionEndpoint { rev_row: 0, col: 0 },
+            Motion::WordForward => {
+                if let Some(next) = word_forward(state, *cursor) {
+                    *cursor = next;
+                }
+            }
+            Motion::WordBackward => {
+                if let Some(prev) = word_backward(state, *cursor, max_rev) {
+                    *cursor = prev;
+                }
+            }
+        }
+    }
+
+    /// Move to the previous (`up`) or next prompt marked by the shell
+    pub fn jump_to_prompt(&mut self, up: bool, state: &mut TerminalState) {
+        if let Some(rev_row) = adjacent_prompt(state.commands(), self.cursor.rev_row, up) {
+            self.cursor = SelectionEndpoint { rev_row, col: 0 };
+            self.scroll_into_view(state);
+        }
+    }
+
+    /// Select the lines of output of the command the cursor is in, or else
+    /// of the last command that finished. Returns false if the shell hasn't
+    /// marked any.
+    pub fn select_command_output(&mut self, state: &mut TerminalState) -> bool {
+        let (_, width) = state.screen_size();
+        let cursor = self.cursor.rev_row;
+        let commands = state.commands();
+        let output = commands
+            .iter()
+            .rev()
+            .find(|command| command.prompt >= cursor)
+            .and_then(|command| command.output)
+            .or_else(|| commands.iter().rev().find_map(|command| command.output));
+        let Some((first, last)) = output else {
+            return false;
+        };
+        self.visual = Some((first, VisualMode::Line));
+        self.cursor = SelectionEndpoint {
+            rev_row: last.rev_row,
+            col: last.col.min(width.saturating_sub(1)),
+        };
+        self.scroll_into_view(state);
+        true
+    }
+
+    /// Scroll just enough to bring the cursor into the viewport
+    pub fn scroll_into_view(&self, state: &mut TerminalState) {
+        let (height, _) = state.screen_size();
+        let bottom = state.scrollback() as i64;
+        let top = bottom + i64::from(height) - 1;
+        if self.cursor.rev_row < bottom {
+            state.scroll_by((self.cursor.rev_row - bottom) as i32);
+        } else if self.cursor.rev_row > top {
+            state.scroll_by((self.cursor.rev_row - top) as i32);
+        }
+    }
+}
+
+/// 0 for blanks, 1 for word characters, 2 for punctuation
+fn char_class(ch: char) -> u8 {
+    if ch.is_whitespace() {
+        0
+    } else if ch.is_alphanumeric() || ch == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+fn word_forward(state: &TerminalState, from: SelectionEndpoint) -> Option<SelectionEndpoint> {
+    let mut rev_row = from.rev_row;
+    let mut chars = state.row_chars(rev_row)?;
+    let mut col = from.col as usize;
+    let start_class = chars.get(col).map_or(0, |&ch| char_class(ch));
+    let mut past_word = start_class == 0;
+
+    loop {
+        col += 1;
+        if col >= chars.len() {
+            // Rows are separate words even when the text runs to the edge
+            if rev_row == 0 {
+                return None;
+            }
+            rev_row -= 1;
+            chars = state.row_chars(rev_row)?;
+            col = 0;
+            past_word = true;
+        }
+        let class = chars.get(col).map_or(0, |&ch| char_class(ch));
+        if class == 0 {
+            past_word = true;
+        } else if past_word || class != start_class {
+            return Some(SelectionEndpoint {
+                rev_row,
+                col: col as u16,
+            });
+        }
+    }
+}
+
+fn word_backward(
+    state: &TerminalState,
+    from: SelectionEndpoint,
+    max_rev: i64,
+) -> Option<SelectionEndpoint> {
+    let mut rev_row = from.rev_row;
+    let mut chars = state.row_chars(rev_row)?;
+    let mut col = from.col as usize;
+
+    // Step back over blanks to the previous word
+    loop {
+        if col == 0 {
+            if rev_row >= max_rev {
+                return None;
+            }
+            rev_row += 1;
+            chars = state.row_chars(rev_row)?;
+            col = chars.len();
+            if col == 0 {
+                continue;
+            }
+        }
+        col -= 1;
+        if chars.get(col).is_some_and(|&ch| char_class(ch) != 0) {
+            break;
+        }
+    }
+
+    // Then to its first character
+    let class = char_class(chars[col]);
+    while col > 0 && char_class(chars[col - 1]) == class {
+        col -= 1;
+    }
+    Some(SelectionEndpoint {
+        rev_row,
+        col: col as u16,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::selection::collect_selection_text;
+
+    fn state_with(text: &str) -> TerminalState {
+        let mut state = TerminalState::new(3, 20);
+        state.process_bytes(text.as_bytes());
+        state
+    }
+
+    #[test]
+    fn test_word_motions_cross_rows() {
+        // Rows from the bottom: 0 "", 1 "gamma.delta", 2 "beta", 3 "alpha  one"
+        let state = state_with("alpha  one\r\nbeta\r\ngamma.delta\r\n");
+        let mut copy = CopyMode::new(&state).unwrap();
+        copy.move_cursor(Motion::Top, &state);
+        assert_eq!(copy.cursor, SelectionEndpoint { rev_row: 3, col: 0 });
+
+        let mut stops = Vec::new();
+        for _ in 0..5 {
+            copy.move_cursor(Motion::WordForward, &state);
+            stops.push((copy.cursor.rev_row, copy.cursor.col));
+        }
+        assert_eq!(stops, vec![(3, 7), (2, 0), (1, 0), (1, 5), (1, 6)]);
+
+        copy.move_cursor(Motion::WordBackward, &state);
+        assert_eq!((copy.cursor.rev_row, copy.cursor.col), (1, 5));
+        copy.move_cursor(Motion::WordBackward, &state);
+        copy.move_cursor(Motion::WordBackward, &state);
+        assert_eq!((copy.cursor.rev_row, copy.cursor.col), (2, 0));
+
+        copy.move_cursor(Motion::LineEnd, &state);
+        assert_eq!(copy.cursor.col, 3);
+        copy.move_cursor(Motion::Bottom, &state);
+        assert_eq!(copy.cursor, SelectionEndpoint { rev_row: 0, col: 0 });
+    }
+
+    #[test]
+    fn test_visual_selections() {
+        let mut state = state_with("alpha one\r\nbravo two\r\ncharlie three\r\n");
+        let mut copy = CopyMode::new(&state).unwrap();
+        copy.move_cursor(Motion::Top, &state);
+        copy.scroll_into_view(&mut state);
+        assert_eq!(state.scrollback(), 1);
+
+        let yank = |copy: &CopyMode| {
+            let (anchor, tail, shape) = copy.selection().unwrap();
+            collect_selection_text(&state.terminal, anchor, tail, shape).unwrap()
+        };
+
+        copy.toggle_visual(VisualMode::Char);
+        copy.move_cursor(Motion::Down, &state);
+        copy.move_cursor(Motion::Right, &state);
+        assert_eq!(yank(&copy), "alpha one\nbr");
+
+        copy.toggle_visual(VisualMode::Line);
+        assert_eq!(yank(&copy).trim_end(), "alpha one\nbravo two");
+
+        copy.toggle_visual(VisualMode::Block);
+        assert_eq!(yank(&copy), "al\nbr");
+
+        copy.toggle_visual(VisualMode::Block);
+        assert!(copy.selection().is_none());
+    }
+
+    #[test]
+    fn test_selection_follows_new_output() {
+        let mut state = state_with("alpha one\r\nbravo two\r\n");
+        let mut copy = CopyMode::new(&state).unwrap();
+        copy.move_cursor(Motion::Top, &state);
+        copy.toggle_visual(VisualMode::Line);
+
+        state.process_bytes(b"charlie three\r\ndelta four\r\n");
+        copy.follow_output(&state);
+        let (anchor, tail, shape) = copy.selection().unwrap();
+        let text = collect_selection_text(&state.terminal, anchor, tail, shape).unwrap();
+        assert_eq!(text.trim_end(), "alpha one");
+    }
+}
@@ -0,0 +1,429 @@
+//! Export of the terminal's contents as plain text, text with ANSI colours
+//! or HTML, and the menu that picks what to export and where to.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+
+use crate::config::color_scheme::{Rgb, builtin_scheme};
+use crate::error::{AppError, Result};
+
+/// Which rows to export
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportScope {
+    /// The whole history, including the screen
+    #[default]
+    Scrollback,
+    /// The rows currently shown
+    Screen,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Text,
+    /// Text with SGR escape sequences, for `less -R` or `cat`
+    Ansi,
+    Html,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportDestination {
+    /// A new file in the downloads (or home) directory
+    #[default]
+    File,
+    /// A local file, then uploaded to the remote home directory over SFTP
+    Remote,
+    Clipboard,
+}
+
+impl ExportScope {
+    const ALL: [Self; 2] = [Self::Scrollback, Self::Screen];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Scrollback => "Scrollback",
+            Self::Screen => "Screen",
+        }
+    }
+}
+
+impl ExportFormat {
+    const ALL: [Self; 3] = [Self::Text, Self::Ansi, Self::Html];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Text => "Plain text",
+            Self::Ansi => "Text with ANSI colours",
+            Self::Html => "HTML",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Ansi => "ans",
+            Self::Html => "html",
+        }
+    }
+}
+
+impl ExportDestination {
+    const ALL: [Self; 3] = [Self::File, Self::Remote, Self::Clipboard];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::File => "Local file",
+            Self::Remote => "Local file + upload to host",
+            Self::Clipboard => "Clipboard",
+        }
+    }
+}
+
+/// The value after (`step` 1) or before (`step` -1) `current` in `all`, wrapping
+fn cycle<T: Copy + PartialEq>(all: &[T], current: T, step: isize) -> T {
+    let idx = all.iter().position(|&v| v == current).unwrap_or(0) as isize;
+    all[(idx + step).rem_euclid(all.len() as isize) as usize]
+}
+
+/// State of the export menu opened from the connected terminal
+#[derive(Clone, Debug, Default)]
+pub struct ExportMenu {
+    pub scope: ExportScope,
+    pub format: ExportFormat,
+    pub destination: ExportDestination,
+    /// Row being changed: scope, format or destination
+    pub selected: usize,
+}
+
+impl ExportMenu {
+    pub const ROWS: usize = 3;
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % Self::ROWS;
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = (self.selected + Self::ROWS - 1) % Self::ROWS;
+    }
+
+    /// Cycle the value of the selected row
+    pub fn change(&mut self, step: isize) {
+        match self.selected {
+            0 => self.scope = cycle(&ExportScope::ALL, self.scope, step),
+            1 => self.format = cycle(&ExportFormat::ALL, self.format, step),
+            _ => self.destination = cycle(&ExportDestination::ALL, self.destination, step),
+        }
+    }
+
+    /// `(label, value)` of each row
+    pub fn rows(&self) -> [(&'static str, &'static str); Self::ROWS] {
+        [
+            ("Export", self.scope.label()),
+            ("Format", self.format.label()),
+            ("To", self.destination.label()),
+        ]
+    }
+}
+
+/// Where an export of `connection_name` is saved: the downloads directory,
+/// or home if there is none
+pub fn export_path(
+    connection_name: &str,
+    format: ExportFormat,
+    now: DateTime<Local>,
+) -> Result<PathBuf> {
+    let dir = dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| AppError::ConfigError("Could not determine home directory".to_string()))?;
+    Ok(dir.join(format!(
+        "termirs_{}_{}.{}",
+        crate::utils::file_name_safe(connection_name),
+        now.format("%Y-%m-%d_%H-%M-%S"),
+        format.extension()
+    )))
+}
+
+/// Render terminal rows, as built for drawing, in `format`. `blank` is the
+/// style of empty cells; trailing blank cells and rows are left out.
+pub fn render(lines: &[Line<'static>], format: ExportFormat, blank: Style, title: &str) -> String {
+    let mut rows: Vec<Vec<(String, Style)>> = lines
+        .iter()
+        .map(|line| trimmed_spans(line, blank))
+        .collect();
+    while rows.last().is_some_and(|row| row.is_empty()) {
+        rows.pop();
+    }
+
+    match format {
+        ExportFormat::Text => {
+            let mut out = String::new();
+            for row in &rows {
+                for (text, _) in row {
+                    out.push_str(text);
+                }
+                out.push('\n');
+            }
+            out
+        }
+        ExportFormat::Ansi => {
+            let mut out = String::new();
+            for row in &rows {
+                for (text, style) in row {
+                    out.push_str(&sgr(*style));
+                    out.push_str(text);
+                }
+                if !row.is_empty() {
+                    out.push_str("\x1b[0m");
+                }
+                out.push('\n');
+            }
+            out
+        }
+        ExportFormat::Html => render_html(&rows, blank, title),
+    }
+}
+
+/// Spans of a row without the blank cells at its end
+fn trimmed_spans(line: &Line<'static>, blank: Style) -> Vec<(String, Style)> {
+    let mut spans: Vec<(String, Style)> = line
+        .spans
+        .iter()
+        .map(|span| (span.content.to_string(), span.style))
+        .collect();
+    let blank_bg = blank.bg.unwrap_or(Color::Reset);
+    while let Some((text, style)) = spans.last_mut() {
+        let invisible = style.bg.unwrap_or(Color::Reset) == blank_bg
+            && !style
+                .add_modifier
+                .intersects(Modifier::UNDERLINED | Modifier::REVERSED);
+        if !invisible {
+            break;
+        }
+        text.truncate(text.trim_end().len());
+        if !text.is_empty() {
+            break;
+        }
+        spans.pop();
+    }
+    spans
+}
+
+/// Escape sequence that resets attributes, then applies `style`
+fn sgr(style: Style) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (modifier, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+    if let Some(code) = style.fg.and_then(|c| sgr_color(c, 30, 90, 38)) {
+        codes.push(code);
+    }
+    if let Some(code) = style.bg.and_then(|c| sgr_color(c, 40, 100, 48)) {
+        codes.push(code);
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// SGR parameters for a colour: `normal`/`bright` are the bases of the 16
+/// ANSI colours, `extended` introduces 256-colour and RGB values
+fn sgr_color(color: Color, normal: u8, bright: u8, extended: u8) -> Option<String> {
+    Some(match color {
+        Color::Reset => return None,
+        Color::Indexed(idx) if idx >= 16 => format!("{extended};5;{idx}"),
+        Color::Rgb(r, g, b) => format!("{extended};2;{r};{g};{b}"),
+        ansi => {
+            let idx = match ansi {
+                Color::Indexed(idx) => idx,
+                named => ansi_index(named)?,
+            };
+            if idx < 8 {
+                (normal + idx).to_string()
+            } else {
+                (bright + idx - 8).to_string()
+            }
+        }
+    })
+}
+
+/// Index (0-15) of a named ANSI colour
+fn ansi_index(color: Color) -> Option<u8> {
+    Some(match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        _ => return None,
+    })
+}
+
+/// RGB value of a colour; ANSI colours use the xterm palette
+fn color_rgb(color: Color) -> Option<Rgb> {
+    let idx = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some(Rgb(r, g, b)),
+        Color::Indexed(idx) => idx,
+        named => ansi_index(named)?,
+    };
+    Some(match idx {
+        0..=15 => builtin_scheme("xterm")?.ansi[idx as usize],
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let idx = idx - 16;
+            Rgb(level(idx / 36), level(idx / 6 % 6), level(idx % 6))
+        }
+        _ => {
+            let gray = 8 + (idx - 232) * 10;
+            Rgb(gray, gray, gray)
+        }
+    })
+}
+
+fn render_html(rows: &[Vec<(String, Style)>], blank: Style, title: &str) -> String {
+    let xterm = builtin_scheme("xterm").expect("xterm scheme is built in");
+    let default_fg = blank.fg.and_then(color_rgb).unwrap_or(xterm.foreground);
+    let default_bg = blank.bg.and_then(color_rgb).unwrap_or(xterm.background);
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n\
+         <body style=\"margin: 0; background: {default_bg}\">\n\
+         <pre style=\"margin: 0; padding: 8px; color: {default_fg}; background: {default_bg}; \
+         font-family: monospace\">",
+        escape_html(title)
+    );
+    for row in rows {
+        for (text, style) in row {
+            let mut fg = style.fg.and_then(color_rgb).unwrap_or(default_fg);
+            let mut bg = style.bg.and_then(color_rgb).unwrap_or(default_bg);
+            if style.add_modifier.contains(Modifier::REVERSED) {
+                std::mem::swap(&mut fg, &mut bg);
+            }
+            let mut css = Vec::new();
+            if fg != default_fg {
+                css.push(format!("color: {fg}"));
+            }
+            if bg != default_bg {
+                css.push(format!("background: {bg}"));
+            }
+            for (modifier, rule) in [
+                (Modifier::BOLD, "font-weight: bold"),
+                (Modifier::DIM, "opacity: 0.6"),
+                (Modifier::ITALIC, "font-style: italic"),
+                (Modifier::UNDERLINED, "text-decoration: underline"),
+            ] {
+                if style.add_modifier.contains(modifier) {
+                    css.push(rule.to_string());
+                }
+            }
+            if css.is_empty() {
+                out.push_str(&escape_html(text));
+            } else {
+                let _ = write!(
+                    out,
+                    "<span style=\"{}\">{}</span>",
+                    css.join("; "),
+                    escape_html(text)
+                );
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::text::Span;
+
+    fn sample() -> Vec<Line<'static>> {
+        let plain = Style::default().fg(Color::Reset).bg(Color::Reset);
+        vec![
+            Line::from(vec![
+                Span::styled("$ ", plain),
+                Span::styled("ok", plain.fg(Color::Green).add_modifier(Modifier::BOLD)),
+                Span::styled(" <a&b>   ", plain),
+            ]),
+            Line::from(vec![Span::styled("     ", Style::default())]),
+            Line::from(vec![
+                Span::styled("x", plain.bg(Color::Indexed(196))),
+                Span::styled("  ", plain),
+            ]),
+            Line::from(vec![Span::styled("          ", Style::default())]),
+        ]
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let lines = sample();
+        assert_eq!(
+            render(&lines, ExportFormat::Text, Style::default(), "web"),
+            "$ ok <a&b>\n\nx\n"
+        );
+        assert_eq!(
+            render(&lines, ExportFormat::Ansi, Style::default(), "web"),
+            "\x1b[0m$ \x1b[0;1;32mok\x1b[0m <a&b>\x1b[0m\n\n\x1b[0;48;5;196mx\x1b[0m\n"
+        );
+
+        let html = render(&lines, ExportFormat::Html, Style::default(), "web");
+        assert!(html.contains("<title>web</title>"));
+        assert!(html.contains(
+            "$ <span style=\"color: #00cd00; font-weight: bold\">ok</span> &lt;a&amp;b&gt;\n"
+        ));
+        assert!(html.contains("<span style=\"background: #ff0000\">x</span>\n</pre>"));
+    }
+
+    #[test]
+    fn test_export_menu_cycles_values() {
+        let mut menu = ExportMenu::default();
+        menu.change(-1);
+        assert_eq!(menu.scope, ExportScope::Screen);
+        menu.select_next();
+        menu.change(1);
+        menu.change(1);
+        assert_eq!(menu.format, ExportFormat::Html);
+        menu.select_prev();
+        menu.select_prev();
+        menu.change(1);
+        assert_eq!(menu.destination, ExportDestination::Remote);
+    }
+}
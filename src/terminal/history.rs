@@ -0,0 +1,73 @@
+//! Scrollback kept across sessions: saved gzip-compressed when a session
+//! closes and shown above the next session's output.
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use crate::error::Result;
+
+/// Where the scrollback of the connection with `connection_id` is kept:
+/// `~/.local/share/termirs/history/<id>.ans.gz`
+pub fn history_path(connection_id: &str) -> Result<PathBuf> {
+    Ok(crate::utils::data_dir()?.join("history").join(format!(
+        "{}.ans.gz",
+        crate::utils::file_name_safe(connection_id)
+    )))
+}
+
+/// Compress `text` to `path`, replacing the previous history only once the
+/// new one is complete
+pub fn save_history(path: &Path, text: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    let mut encoder = GzEncoder::new(
+        BufWriter::new(File::create(&tmp_path)?),
+        Compression::default(),
+    );
+    encoder.write_all(text.as_bytes())?;
+    encoder.finish()?.flush()?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// The saved history and when it was saved, or `None` if there is none
+pub fn load_history(path: &Path) -> Result<Option<(String, DateTime<Local>)>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let saved = file.metadata()?.modified()?.into();
+    let mut text = String::new();
+    GzDecoder::new(BufReader::new(file)).read_to_string(&mut text)?;
+    Ok(Some((text, saved)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history").join("abc.ans.gz");
+        assert!(load_history(&path).unwrap().is_none());
+
+        let text = "\x1b[0;32m$\x1b[0m ls\n".repeat(200);
+        save_history(&path, &text).unwrap();
+        assert!(fs::metadata(&path).unwrap().len() < text.len() as u64 / 10);
+        save_history(&path, &text).unwrap();
+        assert!(!path.with_extension("tmp").exists());
+
+        let (loaded, _) = load_history(&path).unwrap().unwrap();
+        assert_eq!(loaded, text);
+    }
+}
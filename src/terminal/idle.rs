@@ -0,0 +1,127 @@
+//! "Notify when idle": notices when a command run in the terminal is done,
+//! so a long build can be left running while you do something else.
+
+use std::time::{Duration, Instant};
+
+use wezterm_term::StableRowIndex;
+
+/// How a command was seen to finish
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommandDone {
+    /// The shell printed its next prompt (OSC 133 shell integration)
+    Prompt,
+    /// No output arrived for the watch's idle time
+    Idle(Duration),
+}
+
+/// Watches one session for commands that take at least `after`. A command
+/// starts at Enter. With shell integration it is done once the shell prints
+/// a new prompt; otherwise, once its output has gone quiet for `after`, and
+/// typing anything else means you're there, so it stops watching.
+#[derive(Clone, Debug)]
+pub struct IdleWatch {
+    after: Duration,
+    /// When the running command started
+    started: Option<Instant>,
+    /// Row of the newest prompt when it started, with shell integration
+    prompt: Option<StableRowIndex>,
+}
+
+impl IdleWatch {
+    pub fn new(after: Duration) -> Self {
+        Self {
+            after,
+            started: None,
+            prompt: None,
+        }
+    }
+
+    pub fn after(&self) -> Duration {
+        self.after
+    }
+
+    /// Whether a command is being watched
+    pub fn running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// A key was sent to the session. `prompt` is the newest prompt's row.
+    pub fn key_pressed(&mut self, enter: bool, prompt: Option<StableRowIndex>, now: Instant) {
+        if enter {
+            self.started = Some(now);
+            self.prompt = prompt;
+        } else if self.prompt.is_none() {
+            // Programs reading input may be answered without Enter; only
+            // the prompt decides once the shell marks it
+            self.started = None;
+        }
+    }
+
+    /// Check the running command. `last_change` is when output last
+    /// arrived and `prompt` is the newest prompt's row.
+    pub fn poll(
+        &mut self,
+        last_change: Instant,
+        prompt: Option<StableRowIndex>,
+        now: Instant,
+    ) -> Option<CommandDone> {
+        let started = self.started?;
+        if prompt.is_some() {
+            if prompt == self.prompt {
+                return None;
+            }
+            self.started = None;
+            return (last_change.duration_since(started) >= self.after)
+                .then_some(CommandDone::Prompt);
+        }
+        // Quick commands print and go quiet right away; only report output
+        // that went on for a while
+        if now.duration_since(last_change) < self.after || last_change < started + self.after {
+            return None;
+        }
+        self.started = None;
+        Some(CommandDone::Idle(self.after))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_and_prompt() {
+        let secs = |n| Duration::from_secs(n);
+        let t0 = Instant::now();
+        let mut watch = IdleWatch::new(secs(10));
+
+        // A quick command: output stops right after Enter, and typing the
+        // next one stops the watch
+        watch.key_pressed(true, None, t0);
+        assert_eq!(watch.poll(t0 + secs(1), None, t0 + secs(20)), None);
+        watch.key_pressed(false, None, t0 + secs(30));
+        assert_eq!(watch.poll(t0 + secs(30), None, t0 + secs(50)), None);
+
+        // A build printing for a minute, then going quiet
+        watch.key_pressed(true, None, t0 + secs(100));
+        assert_eq!(watch.poll(t0 + secs(160), None, t0 + secs(165)), None);
+        assert_eq!(
+            watch.poll(t0 + secs(160), None, t0 + secs(170)),
+            Some(CommandDone::Idle(secs(10)))
+        );
+        assert_eq!(watch.poll(t0 + secs(160), None, t0 + secs(180)), None);
+
+        // With shell integration a new prompt decides, and typing into the
+        // command doesn't stop the watch
+        watch.key_pressed(true, Some(5), t0 + secs(200));
+        watch.key_pressed(false, Some(5), t0 + secs(210));
+        assert_eq!(watch.poll(t0 + secs(220), Some(5), t0 + secs(300)), None);
+        assert_eq!(
+            watch.poll(t0 + secs(230), Some(40), t0 + secs(231)),
+            Some(CommandDone::Prompt)
+        );
+        // A quick one isn't reported
+        watch.key_pressed(true, Some(40), t0 + secs(400));
+        assert_eq!(watch.poll(t0 + secs(402), Some(41), t0 + secs(403)), None);
+        assert!(!watch.running());
+    }
+}
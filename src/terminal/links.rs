@@ -0,0 +1,166 @@
+//! Links in terminal output: OSC 8 hyperlinks and URLs detected in the text.
+
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use grep_matcher::Matcher;
+use grep_regex::RegexMatcher;
+
+/// URLs recognised in plain text. Links wrapped onto the next row are not joined.
+const URL_PATTERN: &str = r#"\b(?:https?|ftp|file)://[^\s<>"'`]+"#;
+
+static URL_MATCHER: LazyLock<RegexMatcher> =
+    LazyLock::new(|| RegexMatcher::new(URL_PATTERN).expect("URL pattern is valid"));
+
+/// A link on one terminal row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TerminalLink {
+    /// Absolute row, counting scrollback (0 = oldest line)
+    pub row: usize,
+    pub start_col: u16,
+    /// Exclusive
+    pub end_col: u16,
+    pub url: String,
+    /// An OSC 8 hyperlink whose text isn't its target, so the target should
+    /// be shown before it is opened
+    pub hidden_target: bool,
+}
+
+/// Byte ranges of the URLs in `text`, without trailing punctuation
+pub fn find_urls(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let _ = URL_MATCHER.find_iter(text.as_bytes(), |mat| {
+        let url = &text[mat.start()..mat.end()];
+        let end = mat.start() + trimmed_url_len(url);
+        if !text[mat.start()..end].ends_with("://") {
+            ranges.push(mat.start()..end);
+        }
+        true
+    });
+    ranges
+}
+
+/// Length of `url` once sentence punctuation and unbalanced closing
+/// brackets are dropped from the end, e.g. `(see https://x.org/a).`
+fn trimmed_url_len(url: &str) -> usize {
+    let mut url = url;
+    while let Some(last) = url.chars().last() {
+        let trim = match last {
+            '.' | ',' | ';' | ':' | '!' | '?' => true,
+            ')' => url.matches('(').count() < url.matches(')').count(),
+            ']' => url.matches('[').count() < url.matches(']').count(),
+            _ => false,
+        };
+        if !trim {
+            break;
+        }
+        url = &url[..url.len() - last.len_utf8()];
+    }
+    url.len()
+}
+
+/// State of the URL picker opened from the connected terminal
+#[derive(Clone, Debug)]
+pub struct UrlPicker {
+    links: Vec<TerminalLink>,
+    pub selected: usize,
+}
+
+impl UrlPicker {
+    /// Create a picker over `links` (oldest first, as returned by
+    /// `TerminalState::links`). The most recent link is listed first and
+    /// repeated URLs are shown once.
+    pub fn new(links: Vec<TerminalLink>) -> Self {
+        let mut unique: Vec<TerminalLink> = Vec::with_capacity(links.len());
+        for link in links.into_iter().rev() {
+            if !unique.iter().any(|seen| seen.url == link.url) {
+                unique.push(link);
+            }
+        }
+        Self {
+            links: unique,
+            selected: 0,
+        }
+    }
+
+    pub fn links(&self) -> &[TerminalLink] {
+        &self.links
+    }
+
+    pub fn selected_link(&self) -> Option<&TerminalLink> {
+        self.links.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.links.is_empty() {
+            self.selected = (self.selected + 1) % self.links.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.links.is_empty() {
+            self.selected = (self.selected + self.links.len() - 1) % self.links.len();
+        }
+    }
+
+    /// Select the entry for `url`, if it is listed
+    pub fn select_url(&mut self, url: &str) {
+        if let Some(index) = self.links.iter().position(|link| link.url == url) {
+            self.selected = index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(text: &str) -> Vec<&str> {
+        find_urls(text).into_iter().map(|r| &text[r]).collect()
+    }
+
+    #[test]
+    fn test_find_urls() {
+        assert_eq!(
+            urls("GET https://example.com/a?b=1 200, see (http://x.org/wiki/Rust_(lang)). done"),
+            vec!["https://example.com/a?b=1", "http://x.org/wiki/Rust_(lang)"]
+        );
+        assert_eq!(
+            urls("<file:///tmp/report.html>"),
+            vec!["file:///tmp/report.html"]
+        );
+        assert!(urls("no links here, just https:// and example.com").is_empty());
+    }
+
+    #[test]
+    fn test_url_picker_lists_recent_links_once() {
+        let link = |row, url: &str| TerminalLink {
+            row,
+            start_col: 0,
+            end_col: url.len() as u16,
+            url: url.to_string(),
+            hidden_target: false,
+        };
+        let mut picker = UrlPicker::new(vec![
+            link(0, "https://a.example"),
+            link(1, "https://b.example"),
+            link(2, "https://a.example"),
+        ]);
+        let listed: Vec<_> = picker
+            .links()
+            .iter()
+            .map(|l| (l.row, l.url.as_str()))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![(2, "https://a.example"), (1, "https://b.example")]
+        );
+
+        picker.select_prev();
+        assert_eq!(picker.selected_link().unwrap().url, "https://b.example");
+        picker.select_next();
+        assert_eq!(picker.selected, 0);
+        picker.select_url("https://b.example");
+        assert_eq!(picker.selected, 1);
+    }
+}
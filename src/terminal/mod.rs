@@ -1,6 +1,27 @@
+pub mod copy_mode;
+pub mod export;
+pub mod history;
+pub mod idle;
+pub mod links;
+pub mod modes;
+pub mod prompts;
+pub mod recording;
 pub mod selection;
+pub mod session_log;
+pub mod zmodem_picker;
 
+pub use copy_mode::{CopyMode, Motion, VisualMode};
+pub use export::{ExportDestination, ExportFormat, ExportMenu, ExportScope, export_path};
+pub use history::{history_path, load_history, save_history};
+pub use idle::{CommandDone, IdleWatch};
+pub use links::{TerminalLink, UrlPicker, find_urls};
+pub use modes::PrivateModeScanner;
+pub use prompts::{ShellCommand, adjacent_prompt, commands_from_zones};
+pub use recording::{RecordedEvent, Recording, SessionRecorder, recording_path};
 pub use selection::{
     LastMouseClick, MouseClickClass, SelectionAutoScroll, SelectionEndpoint,
-    SelectionScrollDirection, TerminalPoint, compute_selection_for_view, make_selection_endpoint,
+    SelectionScrollDirection, SelectionShape, TerminalPoint, compute_selection_for_view,
+    make_selection_endpoint,
 };
+pub use session_log::SessionLogger;
+pub use zmodem_picker::ZmodemPicker;
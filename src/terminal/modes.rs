@@ -0,0 +1,108 @@
+/// DEC private mode number for bracketed paste
+const BRACKETED_PASTE_MODE: &str = "2004";
+/// Longest CSI parameter string we bother to buffer
+const MAX_PARAMS_LEN: usize = 64;
+
+#[derive(Clone, Debug, Default)]
+enum ScanState {
+    #[default]
+    Ground,
+    Escape,
+    Csi {
+        private: bool,
+        params: String,
+    },
+}
+
+/// Follows DECSET/DECRST (`CSI ? Pm h` / `CSI ? Pm l`) in the output stream
+/// to know whether the remote program enabled bracketed paste. Sequences
+/// split across reads are handled because the scanner keeps its state.
+#[derive(Clone, Debug, Default)]
+pub struct PrivateModeScanner {
+    state: ScanState,
+    bracketed_paste: bool,
+}
+
+impl PrivateModeScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bracketed_paste(&self) -> bool {
+        self.bracketed_paste
+    }
+
+    pub fn scan(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.state = match std::mem::take(&mut self.state) {
+                ScanState::Ground => match byte {
+                    0x1b => ScanState::Escape,
+                    _ => ScanState::Ground,
+                },
+                ScanState::Escape => match byte {
+                    b'[' => ScanState::Csi {
+                        private: false,
+                        params: String::new(),
+                    },
+                    // RIS (full reset) turns every mode off
+                    b'c' => {
+                        self.bracketed_paste = false;
+                        ScanState::Ground
+                    }
+                    0x1b => ScanState::Escape,
+                    _ => ScanState::Ground,
+                },
+                ScanState::Csi {
+                    private,
+                    mut params,
+                } => match byte {
+                    b'?' if params.is_empty() && !private => ScanState::Csi {
+                        private: true,
+                        params,
+                    },
+                    b'0'..=b'9' | b';' if params.len() < MAX_PARAMS_LEN => {
+                        params.push(byte as char);
+                        ScanState::Csi { private, params }
+                    }
+                    b'h' | b'l' => {
+                        if private && params.split(';').any(|p| p == BRACKETED_PASTE_MODE) {
+                            self.bracketed_paste = byte == b'h';
+                        }
+                        ScanState::Ground
+                    }
+                    0x1b => ScanState::Escape,
+                    // Any other final byte ends the sequence
+                    0x40..=0x7e => ScanState::Ground,
+                    // Intermediate bytes or overlong parameters: keep waiting for the final byte
+                    _ => ScanState::Csi { private, params },
+                },
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracks_bracketed_paste() {
+        let mut scanner = PrivateModeScanner::new();
+        assert!(!scanner.bracketed_paste());
+
+        scanner.scan(b"prompt$ \x1b[?2004h");
+        assert!(scanner.bracketed_paste());
+
+        // Split across reads and combined with other modes
+        scanner.scan(b"\x1b[?1049;20");
+        scanner.scan(b"04l");
+        assert!(!scanner.bracketed_paste());
+
+        // Non-private SM with the same number is a different mode
+        scanner.scan(b"\x1b[2004h");
+        assert!(!scanner.bracketed_paste());
+
+        scanner.scan(b"\x1b[?2004h\x1bc");
+        assert!(!scanner.bracketed_paste());
+    }
+}
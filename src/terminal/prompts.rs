@@ -0,0 +1,113 @@
+//! Commands in the scrollback, from the prompt, input and output zones the
+//! shell marks with OSC 133 (shell integration).
+
+use wezterm_term::SemanticType;
+
+use super::selection::SelectionEndpoint;
+
+/// A command run at a marked prompt. Rows count up from the bottom of the
+/// screen, like [`SelectionEndpoint::rev_row`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShellCommand {
+    /// Row of the prompt
+    pub prompt: i64,
+    /// First and last cell of the output; `None` while the command is the
+    /// one at the current prompt, or when it printed nothing
+    pub output: Option<(SelectionEndpoint, SelectionEndpoint)>,
+}
+
+/// Group zones, oldest first, into commands. Output before the first
+/// prompt doesn't belong to a command; output after the last prompt is
+/// still being written.
+pub fn commands_from_zones(
+    zones: impl IntoIterator<Item = (SemanticType, SelectionEndpoint, SelectionEndpoint)>,
+) -> Vec<ShellCommand> {
+    let mut commands: Vec<ShellCommand> = Vec::new();
+    let mut output: Option<(SelectionEndpoint, SelectionEndpoint)> = None;
+    for (kind, start, end) in zones {
+        match kind {
+            SemanticType::Prompt => {
+                if let Some(last) = commands.last_mut()
+                    && last.prompt != start.rev_row
+                {
+                    last.output = output;
+                }
+                output = None;
+                // A prompt split into zones (e.g. a right prompt) is still one
+                if commands
+                    .last()
+                    .is_none_or(|last| last.prompt != start.rev_row)
+                {
+                    commands.push(ShellCommand {
+                        prompt: start.rev_row,
+                        output: None,
+                    });
+                }
+            }
+            SemanticType::Input => {}
+            SemanticType::Output if !commands.is_empty() => {
+                output = Some((output.map_or(start, |(first, _)| first), end));
+            }
+            SemanticType::Output => {}
+        }
+    }
+    commands
+}
+
+/// Row of the closest prompt above (`up`) or below `rev_row`
+pub fn adjacent_prompt(commands: &[ShellCommand], rev_row: i64, up: bool) -> Option<i64> {
+    let prompts = commands.iter().map(|command| command.prompt);
+    if up {
+        prompts.filter(|&row| row > rev_row).min()
+    } else {
+        prompts.filter(|&row| row < rev_row).max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rev_row: i64, col: u16) -> SelectionEndpoint {
+        SelectionEndpoint { rev_row, col }
+    }
+
+    #[test]
+    fn test_commands_from_zones() {
+        let commands = commands_from_zones([
+            // Login banner, then `make` printing two rows, then `cd` printing
+            // nothing, then the current prompt
+            (SemanticType::Output, at(9, 0), at(8, 79)),
+            (SemanticType::Prompt, at(7, 0), at(7, 1)),
+            (SemanticType::Input, at(7, 2), at(7, 5)),
+            (SemanticType::Output, at(6, 0), at(5, 79)),
+            (SemanticType::Prompt, at(4, 0), at(4, 1)),
+            (SemanticType::Prompt, at(4, 70), at(4, 79)),
+            (SemanticType::Input, at(4, 2), at(4, 8)),
+            (SemanticType::Prompt, at(3, 0), at(3, 1)),
+            (SemanticType::Output, at(2, 0), at(0, 79)),
+        ]);
+        assert_eq!(
+            commands,
+            vec![
+                ShellCommand {
+                    prompt: 7,
+                    output: Some((at(6, 0), at(5, 79))),
+                },
+                ShellCommand {
+                    prompt: 4,
+                    output: None,
+                },
+                ShellCommand {
+                    prompt: 3,
+                    output: None,
+                },
+            ]
+        );
+
+        assert_eq!(adjacent_prompt(&commands, 5, true), Some(7));
+        assert_eq!(adjacent_prompt(&commands, 5, false), Some(4));
+        assert_eq!(adjacent_prompt(&commands, 7, true), None);
+        assert_eq!(adjacent_prompt(&commands, 3, false), None);
+    }
+}
@@ -0,0 +1,288 @@
+//! Session recordings in asciicast v2 format: a JSON header line followed by
+//! one `[seconds, code, data]` line per event, playable with `termirs play`
+//! or asciinema.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+
+/// First line of an asciicast v2 file
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AsciicastHeader {
+    pub version: u8,
+    pub width: u16,
+    pub height: u16,
+    /// Unix time the recording started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+/// A recorded event the player acts on. Input and marker events are skipped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecordedEvent {
+    /// `o`: output from the remote side
+    Output(String),
+    /// `r`: the terminal was resized to `COLSxROWS`
+    Resize { cols: u16, rows: u16 },
+}
+
+/// Writes the output of a live session to an asciicast file
+pub struct SessionRecorder {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    started: Instant,
+    /// Start of a UTF-8 sequence split across two data chunks
+    partial: Vec<u8>,
+}
+
+impl SessionRecorder {
+    /// Create `path` (and its directory) and write the header. An existing
+    /// file is never replaced: `<name> (1).cast` and so on are tried instead;
+    /// [`Self::path`] is the one that was created.
+    pub fn create(path: &Path, cols: u16, rows: u16, title: Option<String>) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let (path, file) = create_unique(path)?;
+        let mut writer = BufWriter::new(file);
+        let header = AsciicastHeader {
+            version: 2,
+            width: cols,
+            height: rows,
+            timestamp: Some(Local::now().timestamp()),
+            title,
+            env: BTreeMap::new(),
+        };
+        let header = serde_json::to_string(&header).map_err(std::io::Error::other)?;
+        writeln!(writer, "{header}")?;
+        writer.flush()?;
+        Ok(Self {
+            writer,
+            path,
+            started: Instant::now(),
+            partial: Vec::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an output event. Bytes that end mid-character are held back
+    /// until the rest of the character arrives.
+    pub fn record_output(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        self.partial.extend_from_slice(bytes);
+        let complete = match std::str::from_utf8(&self.partial) {
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            _ => self.partial.len(),
+        };
+        if complete == 0 {
+            return Ok(());
+        }
+        let text = String::from_utf8_lossy(&self.partial[..complete]).into_owned();
+        self.partial.drain(..complete);
+        self.write_event("o", &text)
+    }
+
+    pub fn record_resize(&mut self, cols: u16, rows: u16) -> std::io::Result<()> {
+        self.write_event("r", &format!("{cols}x{rows}"))
+    }
+
+    fn write_event(&mut self, code: &str, data: &str) -> std::io::Result<()> {
+        // Microsecond precision, like asciinema
+        let time = self.started.elapsed().as_micros() as f64 / 1_000_000.0;
+        let line = serde_json::to_string(&(time, code, data)).map_err(std::io::Error::other)?;
+        writeln!(self.writer, "{line}")?;
+        // Flushed per event so the file is complete up to a crash
+        self.writer.flush()
+    }
+}
+
+/// A recording loaded for playback
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording {
+    pub header: AsciicastHeader,
+    /// Events with their time since the start, in order
+    pub events: Vec<(Duration, RecordedEvent)>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let invalid = |line: usize, reason: String| {
+            AppError::ValidationError(format!("Invalid recording (line {line}): {reason}"))
+        };
+
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+        let (_, header) = lines
+            .next()
+            .ok_or_else(|| AppError::ValidationError("Recording is empty".to_string()))?;
+        let header: AsciicastHeader =
+            serde_json::from_str(header).map_err(|e| invalid(1, e.to_string()))?;
+        if header.version != 2 {
+            return Err(invalid(
+                1,
+                format!("unsupported asciicast version {}", header.version),
+            ));
+        }
+
+        let mut events = Vec::new();
+        let mut last = Duration::ZERO;
+        for (idx, line) in lines {
+            let (time, code, data): (f64, String, String) =
+                serde_json::from_str(line).map_err(|e| invalid(idx + 1, e.to_string()))?;
+            // Keep events in order even if the clock went backwards
+            let time = Duration::try_from_secs_f64(time)
+                .map_err(|e| invalid(idx + 1, e.to_string()))?
+                .max(last);
+            last = time;
+            let event = match code.as_str() {
+                "o" => RecordedEvent::Output(data),
+                "r" => {
+                    let size = data
+                        .split_once('x')
+                        .and_then(|(cols, rows)| Some((cols.parse().ok()?, rows.parse().ok()?)));
+                    let Some((cols, rows)) = size else {
+                        return Err(invalid(idx + 1, format!("bad resize '{data}'")));
+                    };
+                    RecordedEvent::Resize { cols, rows }
+                }
+                _ => continue,
+            };
+            events.push((time, event));
+        }
+        Ok(Self { header, events })
+    }
+
+    /// Time of the last event
+    pub fn duration(&self) -> Duration {
+        self.events.last().map_or(Duration::ZERO, |(time, _)| *time)
+    }
+}
+
+/// Create a new file at `path`, or at `<stem> (n).<ext>` for the first `n`
+/// that doesn't exist yet
+fn create_unique(path: &Path) -> std::io::Result<(PathBuf, File)> {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let mut copy = 0;
+    loop {
+        let candidate = if copy == 0 {
+            path.to_path_buf()
+        } else {
+            path.with_file_name(format!("{stem} ({copy}){extension}"))
+        };
+        match File::options()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(file) => return Ok((candidate, file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => copy += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Where a recording of `connection_name` started at `started` is saved:
+/// `~/.local/share/termirs/recordings/<connection>/<date>_<time>.cast`
+pub fn recording_path(connection_name: &str, started: DateTime<Local>) -> Result<PathBuf> {
+    Ok(crate::utils::data_dir()?
+        .join("recordings")
+        .join(crate::utils::file_name_safe(connection_name))
+        .join(format!("{}.cast", started.format("%Y-%m-%d_%H-%M-%S"))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("session.cast");
+        let mut recorder = SessionRecorder::create(&path, 80, 24, Some("web".to_string())).unwrap();
+        recorder.record_output(b"hello \"world\"\r\n").unwrap();
+        // "é" split across two chunks
+        recorder.record_output(&[b'c', b'a', b'f', 0xc3]).unwrap();
+        recorder.record_output(&[0xa9, b'\n']).unwrap();
+        recorder.record_resize(100, 30).unwrap();
+        drop(recorder);
+
+        let recording = Recording::load(&path).unwrap();
+        assert_eq!(recording.header.width, 80);
+        assert_eq!(recording.header.height, 24);
+        assert_eq!(recording.header.title.as_deref(), Some("web"));
+        let events: Vec<_> = recording.events.into_iter().map(|(_, e)| e).collect();
+        assert_eq!(
+            events,
+            vec![
+                RecordedEvent::Output("hello \"world\"\r\n".to_string()),
+                RecordedEvent::Output("caf".to_string()),
+                RecordedEvent::Output("é\n".to_string()),
+                RecordedEvent::Resize {
+                    cols: 100,
+                    rows: 30
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recordings_started_in_the_same_second_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("2026-03-01_09-30-00.cast");
+        let first = SessionRecorder::create(&path, 80, 24, None).unwrap();
+        let second = SessionRecorder::create(&path, 80, 24, None).unwrap();
+        assert_eq!(first.path(), path);
+        assert_eq!(
+            second.path(),
+            dir.path().join("2026-03-01_09-30-00 (1).cast")
+        );
+    }
+
+    #[test]
+    fn test_parse_asciinema_file() {
+        let text = concat!(
+            "{\"version\": 2, \"width\": 40, \"height\": 10, \"env\": {\"SHELL\": \"/bin/zsh\"}}\n",
+            "[0.5, \"o\", \"$ \"]\n",
+            "[1.25, \"i\", \"l\"]\n",
+            "[2.0, \"m\", \"\"]\n",
+            "[3.0, \"o\", \"ls\\r\\n\"]\n",
+        );
+        let recording = Recording::parse(text).unwrap();
+        assert_eq!(recording.events.len(), 2);
+        assert_eq!(recording.duration(), Duration::from_secs(3));
+
+        assert!(Recording::parse("").is_err());
+        assert!(Recording::parse("{\"version\": 1, \"width\": 1, \"height\": 1}").is_err());
+        assert!(
+            Recording::parse("{\"version\": 2, \"width\": 1, \"height\": 1}\n[1, \"r\", \"big\"]")
+                .is_err()
+        );
+    }
+}
@@ -16,6 +16,16 @@ pub struct SelectionEndpoint {
     pub col: u16,
 }
 
+/// How the text between two endpoints is selected
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionShape {
+    /// From one endpoint to the other, wrapping across rows
+    #[default]
+    Linear,
+    /// The rectangle with the endpoints in opposite corners
+    Block,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SelectionScrollDirection {
     Up,
@@ -110,6 +120,7 @@ pub fn compute_selection_for_view(
     state: &TerminalState,
     width: u16,
     force_nonempty: bool,
+    shape: SelectionShape,
 ) -> Option<crate::ui::TerminalSelection> {
     let (anchor, tail) = match (anchor, tail) {
         (Some(a), Some(b)) => (a, b),
@@ -134,12 +145,17 @@ pub fn compute_selection_for_view(
     let start_row = rev_to_view_row(state, clamped_top)?;
     let end_row = rev_to_view_row(state, clamped_bottom)?;
 
-    let start_col = if top.rev_row == clamped_top {
+    let block = shape == SelectionShape::Block;
+    let start_col = if block {
+        top.col.min(bottom.col).min(width.saturating_sub(1))
+    } else if top.rev_row == clamped_top {
         top.col.min(width.saturating_sub(1))
     } else {
         0
     };
-    let end_col = if bottom.rev_row == clamped_bottom {
+    let end_col = if block {
+        top.col.max(bottom.col).saturating_add(1).min(width)
+    } else if bottom.rev_row == clamped_bottom {
         bottom.col.saturating_add(1).min(width)
     } else {
         width
@@ -154,6 +170,7 @@ pub fn compute_selection_for_view(
         start_col,
         end_row,
         end_col,
+        block,
     })
 }
 
@@ -174,11 +191,12 @@ pub fn make_selection_endpoint(
     })
 }
 
+/// Text between the endpoints, including rows scrolled out of the viewport
 pub fn collect_selection_text(
     terminal: &WezTerminal,
-    scrollback_offset: usize,
     anchor: SelectionEndpoint,
     tail: SelectionEndpoint,
+    shape: SelectionShape,
 ) -> Option<String> {
     let screen = terminal.screen();
     let height = screen.physical_rows as u16;
@@ -188,68 +206,56 @@ pub fn collect_selection_text(
     }
 
     let (top, bottom) = order_selection_endpoints(anchor, tail);
-    let mut current_rev = top.rev_row;
+    let block = shape == SelectionShape::Block;
+    let (left, right) = (
+        top.col.min(bottom.col),
+        top.col.max(bottom.col).saturating_add(1),
+    );
     let mut result = String::new();
 
     // Get all lines for efficient access
     let total_lines = screen.scrollback_rows();
-    let phys_rows = screen.physical_rows;
     let all_lines = screen.lines_in_phys_range(0..total_lines);
 
-    while current_rev >= bottom.rev_row {
-        if current_rev < 0 {
-            break;
-        }
-
-        let view_row = match rev_to_view_row_on_terminal(terminal, scrollback_offset, current_rev) {
-            Some(row) => row,
-            None => {
-                if current_rev == bottom.rev_row {
-                    break;
-                }
-                current_rev -= 1;
-                continue;
-            }
+    for rev_row in (bottom.rev_row..=top.rev_row).rev() {
+        // Rows count up from the bottom of the screen; ones trimmed from
+        // the scrollback (or below the screen) are skipped
+        let Some(line) = usize::try_from(total_lines as i64 - 1 - rev_row)
+            .ok()
+            .and_then(|row| all_lines.get(row))
+        else {
+            continue;
         };
 
-        let mut start_col = if current_rev == top.rev_row {
-            top.col
-        } else {
-            0
-        };
-        let mut end_col = if current_rev == bottom.rev_row {
-            bottom.col.saturating_add(1)
+        let (start_col, end_col) = if block {
+            (left, right)
         } else {
-            width
+            (
+                if rev_row == top.rev_row { top.col } else { 0 },
+                if rev_row == bottom.rev_row {
+                    bottom.col.saturating_add(1)
+                } else {
+                    width
+                },
+            )
         };
+        let (start_col, end_col) = (start_col.min(width), end_col.min(width));
 
-        start_col = start_col.min(width);
-        end_col = end_col.min(width);
-
-        // Calculate the absolute line index based on view_row and scrollback
-        let start_row = total_lines
-            .saturating_sub(phys_rows)
-            .saturating_sub(scrollback_offset);
-        let abs_row = start_row + view_row as usize;
-
-        if end_col > start_col && abs_row < all_lines.len() {
-            let segment = extract_line_segment_wez(&all_lines[abs_row], start_col, end_col, width);
+        if end_col > start_col {
+            let segment = extract_line_segment_wez(line, start_col, end_col, width);
             result.push_str(&segment);
         }
 
-        if current_rev == bottom.rev_row {
+        if rev_row == bottom.rev_row {
             break;
         }
 
-        // Only add newline if the line was not soft-wrapped
-        if abs_row < all_lines.len() && !all_lines[abs_row].last_cell_was_wrapped() {
+        // Only add newline if the line was not soft-wrapped, dropping the
+        // blank cells that pad it to the screen width
+        if block || !line.last_cell_was_wrapped() {
+            result.truncate(result.trim_end_matches(' ').len());
             result.push('\n');
         }
-
-        if current_rev == i64::MIN {
-            break;
-        }
-        current_rev -= 1;
     }
 
     Some(result)
@@ -308,7 +314,14 @@ mod tests {
             rev_row: 10,
             col: 3,
         };
-        let result = compute_selection_for_view(Some(endpoint), Some(endpoint), &state, 10, false);
+        let result = compute_selection_for_view(
+            Some(endpoint),
+            Some(endpoint),
+            &state,
+            10,
+            false,
+            SelectionShape::Linear,
+        );
         assert!(result.is_none());
     }
 
@@ -319,7 +332,14 @@ mod tests {
             rev_row: -1,
             col: 0,
         };
-        let result = compute_selection_for_view(Some(endpoint), Some(endpoint), &state, 10, false);
+        let result = compute_selection_for_view(
+            Some(endpoint),
+            Some(endpoint),
+            &state,
+            10,
+            false,
+            SelectionShape::Linear,
+        );
         assert!(result.is_none());
     }
 
@@ -330,8 +350,15 @@ mod tests {
         // This test checks that overlapping selection works
         let anchor = SelectionEndpoint { rev_row: 4, col: 4 };
         let tail = SelectionEndpoint { rev_row: 2, col: 5 };
-        let selection = compute_selection_for_view(Some(anchor), Some(tail), &state, 10, false)
-            .expect("selection should be visible");
+        let selection = compute_selection_for_view(
+            Some(anchor),
+            Some(tail),
+            &state,
+            10,
+            false,
+            SelectionShape::Linear,
+        )
+        .expect("selection should be visible");
         assert_eq!(selection.start_row, 0);
         assert_eq!(selection.end_col, 6);
     }
@@ -345,10 +372,35 @@ mod tests {
 
         let anchor = make_selection_endpoint(&state, 0, 0).unwrap();
         let tail = make_selection_endpoint(&state, 1, 6).unwrap();
-        let text = collect_selection_text(&state.terminal, state.scrollback(), anchor, tail)
+        let text = collect_selection_text(&state.terminal, anchor, tail, SelectionShape::Linear)
             .expect("text available");
 
         assert!(text.contains("first line"));
         assert!(text.contains("second"));
     }
+
+    #[test]
+    fn block_selection_copies_columns_beyond_viewport() {
+        let mut state = TerminalState::new(3, 20);
+        state.process_bytes(b"alpha one\r\nbravo two\r\ncharlie three\r\ndelta four\r\n");
+
+        // "alpha" has scrolled out of the 3-row viewport
+        let anchor = SelectionEndpoint { rev_row: 4, col: 1 };
+        let tail = SelectionEndpoint { rev_row: 1, col: 3 };
+        let text = collect_selection_text(&state.terminal, anchor, tail, SelectionShape::Block)
+            .expect("text available");
+        assert_eq!(text, "lph\nrav\nhar\nelt");
+
+        let selection = compute_selection_for_view(
+            Some(anchor),
+            Some(tail),
+            &state,
+            20,
+            false,
+            SelectionShape::Block,
+        )
+        .expect("selection should be visible");
+        assert_eq!((selection.start_row, selection.end_row), (0, 1));
+        assert_eq!((selection.start_col, selection.end_col), (1, 4));
+    }
 }
@@ -0,0 +1,329 @@
+//! Plain-text session logs: the remote output with escape sequences
+//! stripped, one file per day split further by size.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDate};
+
+use crate::config::manager::SessionLogOptions;
+use crate::error::Result;
+
+/// Size limit of a log file when `max_size_mb` is unset
+const DEFAULT_MAX_SIZE_MB: u64 = 10;
+/// Longest line held back waiting for its newline; full-screen programs
+/// like top position the cursor instead of ending lines
+const MAX_LINE_LEN: usize = 4096;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum StripState {
+    #[default]
+    Ground,
+    /// After ESC
+    Escape,
+    /// In a CSI sequence, up to its final byte
+    Csi,
+    /// In an OSC, DCS, APC, PM or SOS string, up to BEL or ST
+    ControlString,
+    /// ESC inside a string, usually the start of ST
+    StringEscape,
+    /// After `ESC (` and the like, which take one more byte
+    Charset,
+}
+
+/// Removes escape sequences from a byte stream, keeping text and control
+/// characters. Sequences split across chunks are handled.
+#[derive(Clone, Debug, Default)]
+pub struct AnsiStripper {
+    state: StripState,
+}
+
+impl AnsiStripper {
+    /// Feed one byte; returns it unless it is part of an escape sequence
+    pub fn feed(&mut self, byte: u8) -> Option<u8> {
+        use StripState::*;
+        let (state, keep) = match (self.state, byte) {
+            (_, 0x1b) if self.state != ControlString => (Escape, false),
+            (Ground, _) => (Ground, true),
+            (Escape, b'[') => (Csi, false),
+            (Escape, b']' | b'P' | b'_' | b'^' | b'X') => (ControlString, false),
+            (Escape, b'(' | b')' | b'*' | b'+' | b'#' | b'%' | b' ') => (Charset, false),
+            (Escape | Charset, _) => (Ground, false),
+            (Csi, 0x40..=0x7e) => (Ground, false),
+            (Csi, _) => (Csi, false),
+            (ControlString, 0x07) => (Ground, false),
+            (ControlString, 0x1b) => (StringEscape, false),
+            (ControlString, _) => (ControlString, false),
+            (StringEscape, b'\\') => (Ground, false),
+            (StringEscape, _) => (ControlString, false),
+        };
+        self.state = state;
+        keep.then_some(byte)
+    }
+}
+
+/// Writes what the remote side sends to a connection's log directory
+pub struct SessionLogger {
+    dir: PathBuf,
+    timestamps: bool,
+    /// Bytes after which a new file is started; `None` for no limit
+    max_bytes: Option<u64>,
+    stripper: AnsiStripper,
+    /// Text of the line being received
+    line: Vec<u8>,
+    /// When the first byte of `line` arrived
+    line_started: Option<DateTime<Local>>,
+    /// A carriage return ended the text so far; more text overwrites it
+    carriage_return: bool,
+    file: Option<LogFile>,
+}
+
+struct LogFile {
+    writer: BufWriter<File>,
+    date: NaiveDate,
+    /// 0 for `<date>.log`, n for `<date>.<n>.log`
+    part: u32,
+    size: u64,
+}
+
+impl SessionLogger {
+    /// Log to `~/.local/share/termirs/logs/<connection>`
+    pub fn new(connection_name: &str, options: &SessionLogOptions) -> Result<Self> {
+        let dir = crate::utils::data_dir()?
+            .join("logs")
+            .join(crate::utils::file_name_safe(connection_name));
+        Self::with_dir(dir, options)
+    }
+
+    pub fn with_dir(dir: PathBuf, options: &SessionLogOptions) -> Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let max_mb = options.max_size_mb.unwrap_or(DEFAULT_MAX_SIZE_MB);
+        Ok(Self {
+            dir,
+            timestamps: options.timestamps,
+            max_bytes: (max_mb > 0).then_some(max_mb * 1024 * 1024),
+            stripper: AnsiStripper::default(),
+            line: Vec::new(),
+            line_started: None,
+            carriage_return: false,
+            file: None,
+        })
+    }
+
+    /// Append output from the remote side. Complete lines are written out;
+    /// the rest waits for its newline. Text after a carriage return replaces
+    /// the line (progress bars), and a line is written out anyway once it
+    /// reaches `MAX_LINE_LEN`.
+    pub fn log_output(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        self.log_output_at(bytes, Local::now())
+    }
+
+    fn log_output_at(&mut self, bytes: &[u8], now: DateTime<Local>) -> std::io::Result<()> {
+        for &byte in bytes {
+            let Some(byte) = self.stripper.feed(byte) else {
+                continue;
+            };
+            match byte {
+                b'\n' => self.write_line(now)?,
+                b'\r' => self.carriage_return = true,
+                // Backspace: drop the last character, with its UTF-8 continuation bytes
+                0x08 => {
+                    while let Some(last) = self.line.pop() {
+                        if last & 0xc0 != 0x80 {
+                            break;
+                        }
+                    }
+                }
+                b'\t' | 0x20..=0x7e | 0x80..=0xff => {
+                    if std::mem::take(&mut self.carriage_return) {
+                        self.line.clear();
+                    }
+                    self.line_started.get_or_insert(now);
+                    self.line.push(byte);
+                    // Split after an ASCII byte, never inside a character
+                    if self.line.len() >= MAX_LINE_LEN && byte.is_ascii() {
+                        self.write_line(now)?;
+                    }
+                }
+                // Other control characters carry no text
+                _ => {}
+            }
+        }
+        if let Some(file) = &mut self.file {
+            file.writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write out a line that hasn't seen its newline yet, e.g. when the
+    /// session ends at a prompt
+    pub fn flush_partial_line(&mut self) -> std::io::Result<()> {
+        if !self.line.is_empty() {
+            self.write_line(Local::now())?;
+        }
+        if let Some(file) = &mut self.file {
+            file.writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write the current line to the file for the day it started on
+    fn write_line(&mut self, now: DateTime<Local>) -> std::io::Result<()> {
+        self.carriage_return = false;
+        let started = self.line_started.take().unwrap_or(now);
+        let mut text = Vec::with_capacity(self.line.len() + 24);
+        if self.timestamps {
+            write!(text, "[{}] ", started.format("%Y-%m-%d %H:%M:%S"))?;
+        }
+        text.append(&mut self.line);
+        text.push(b'\n');
+
+        let file = self.file_for(started.date_naive(), text.len() as u64)?;
+        file.writer.write_all(&text)?;
+        file.size += text.len() as u64;
+        Ok(())
+    }
+
+    /// The file to append `len` bytes to on `date`, starting a new one on a
+    /// new day or when the current one would grow past the limit
+    fn file_for(&mut self, date: NaiveDate, len: u64) -> std::io::Result<&mut LogFile> {
+        let next_part = match &self.file {
+            Some(file) if file.date != date => Some(0),
+            Some(file) if self.over_limit(file.size, len) => Some(file.part + 1),
+            Some(_) => None,
+            None => Some(0),
+        };
+        if let Some(mut part) = next_part {
+            if let Some(mut old) = self.file.take() {
+                old.writer.flush()?;
+            }
+            // Continue an earlier session's file for the day if it has room
+            loop {
+                let path = log_file_path(&self.dir, date, part);
+                let size = fs::metadata(&path).map_or(0, |m| m.len());
+                if !self.over_limit(size, len) {
+                    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+                    self.file = Some(LogFile {
+                        writer: BufWriter::new(file),
+                        date,
+                        part,
+                        size,
+                    });
+                    break;
+                }
+                part += 1;
+            }
+        }
+        Ok(self.file.as_mut().expect("log file was just opened"))
+    }
+
+    /// Whether adding `len` bytes to a non-empty file of `size` exceeds the limit
+    fn over_limit(&self, size: u64, len: u64) -> bool {
+        self.max_bytes
+            .is_some_and(|max| size > 0 && size + len > max)
+    }
+}
+
+impl Drop for SessionLogger {
+    fn drop(&mut self) {
+        let _ = self.flush_partial_line();
+    }
+}
+
+fn log_file_path(dir: &Path, date: NaiveDate, part: u32) -> PathBuf {
+    let date = date.format("%Y-%m-%d");
+    if part == 0 {
+        dir.join(format!("{date}.log"))
+    } else {
+        dir.join(format!("{date}.{part}.log"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn strip(input: &[u8]) -> String {
+        let mut stripper = AnsiStripper::default();
+        let kept: Vec<u8> = input.iter().filter_map(|&b| stripper.feed(b)).collect();
+        String::from_utf8(kept).unwrap()
+    }
+
+    #[test]
+    fn test_strip_escape_sequences() {
+        assert_eq!(
+            strip(
+                b"\x1b[1;31mred\x1b[0m \x1b]0;title\x07text\x1b]8;;http://x\x1b\\link\x1b(B\x1b=!"
+            ),
+            "red textlink!"
+        );
+        // Split across feeds
+        let mut stripper = AnsiStripper::default();
+        let kept: Vec<u8> = [b"a\x1b[3".as_slice(), b"8;5;1", b"2mb"]
+            .concat()
+            .into_iter()
+            .filter_map(|b| stripper.feed(b))
+            .collect();
+        assert_eq!(kept, b"ab");
+    }
+
+    #[test]
+    fn test_log_lines_timestamps_and_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = SessionLogOptions {
+            timestamps: true,
+            max_size_mb: None,
+        };
+        let mut logger = SessionLogger::with_dir(dir.path().to_path_buf(), &options).unwrap();
+        logger.max_bytes = Some(60);
+        let day1 = Local.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap();
+        let day2 = Local.with_ymd_and_hms(2026, 3, 2, 0, 0, 5).unwrap();
+
+        logger
+            .log_output_at(b"\x1b[32m$\x1b[0m lx\x08s\r\nfile.txt\r\n", day1)
+            .unwrap();
+        logger.log_output_at(b"third line\r\n", day1).unwrap();
+        logger.log_output_at(b"next day\r\n$ ", day2).unwrap();
+        drop(logger);
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(
+            read("2026-03-01.log"),
+            "[2026-03-01 09:30:00] $ ls\n[2026-03-01 09:30:00] file.txt\n"
+        );
+        assert_eq!(
+            read("2026-03-01.1.log"),
+            "[2026-03-01 09:30:00] third line\n"
+        );
+        assert!(read("2026-03-02.log").starts_with("[2026-03-02 00:00:05] next day\n"));
+        assert!(read("2026-03-02.log").ends_with("] $ \n"));
+    }
+
+    #[test]
+    fn test_carriage_returns_and_endless_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = SessionLogOptions {
+            timestamps: false,
+            max_size_mb: None,
+        };
+        let mut logger = SessionLogger::with_dir(dir.path().to_path_buf(), &options).unwrap();
+        let now = Local.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap();
+
+        logger
+            .log_output_at(b"copying  10%\rcopying  60%\rcopying 100%\r\n", now)
+            .unwrap();
+        // top redraws with cursor movements and never ends a line
+        for _ in 0..MAX_LINE_LEN / 8 + 1 {
+            logger.log_output_at(b"\x1b[Hload 1.0", now).unwrap();
+        }
+        assert!(logger.line.len() < MAX_LINE_LEN);
+        drop(logger);
+
+        let log = fs::read_to_string(dir.path().join("2026-03-01.log")).unwrap();
+        let mut lines = log.lines();
+        assert_eq!(lines.next(), Some("copying 100%"));
+        assert_eq!(lines.next().map(str::len), Some(MAX_LINE_LEN));
+    }
+}
@@ -0,0 +1,73 @@
+//! Picks the local side of a ZMODEM transfer started on the host: the file
+//! to send to `rz`, or the directory to save what `sz` sends.
+
+use std::sync::Arc;
+
+use ratatui_async_explorer::{FileExplorer, Input, LocalFileSystem};
+
+use crate::error::{AppError, Result};
+use crate::zmodem::{Direction, Job};
+
+/// State of the picker opened from the connected terminal
+pub struct ZmodemPicker {
+    pub direction: Direction,
+    pub explorer: FileExplorer<LocalFileSystem>,
+}
+
+impl ZmodemPicker {
+    /// Uploads start in the working directory, downloads in the downloads
+    /// (or home) directory
+    pub async fn new(direction: Direction) -> Result<Self> {
+        let dir = match direction {
+            Direction::Upload => std::env::current_dir().ok().or_else(dirs::home_dir),
+            Direction::Download => dirs::download_dir().or_else(dirs::home_dir),
+        }
+        .unwrap_or_else(std::env::temp_dir);
+        let explorer = FileExplorer::with_fs(
+            Arc::new(LocalFileSystem),
+            dir.to_string_lossy().into_owned(),
+        )
+        .await
+        .map_err(|e| AppError::ZmodemError(format!("Failed to open '{}': {e}", dir.display())))?;
+        Ok(Self {
+            direction,
+            explorer,
+        })
+    }
+
+    pub async fn navigate(&mut self, input: Input) -> Result<()> {
+        self.explorer
+            .handle(input)
+            .await
+            .map_err(|e| AppError::ZmodemError(format!("Navigation error: {e}")))?;
+        Ok(())
+    }
+
+    /// Enter on the selection: opens a directory, or picks the file to upload
+    pub async fn choose(&mut self) -> Result<Option<Job>> {
+        let current = self.explorer.current();
+        if current.is_dir() {
+            self.navigate(Input::Right).await?;
+            return Ok(None);
+        }
+        Ok(match self.direction {
+            Direction::Upload => Some(Job::Upload(current.path().to_path_buf())),
+            Direction::Download => None,
+        })
+    }
+
+    /// Save the download in the directory being shown
+    pub fn save_here(&self) -> Option<Job> {
+        match self.direction {
+            Direction::Download => Some(Job::Download(self.explorer.cwd().to_path_buf())),
+            Direction::Upload => None,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self.direction {
+            Direction::Upload => "Upload to rz",
+            Direction::Download => "Save files from sz",
+        }
+    }
+}
@@ -1,9 +1,13 @@
 /// Result summary of SFTP transfer operations
 #[derive(Debug, Clone)]
 pub enum ScpResult {
+    /// A file joined the transfer after it started (ZMODEM downloads)
+    FileAdded(ScpFileProgress),
     Progress(ScpTransferProgress),
     Completed(Vec<ScpFileResult>),
-    Error { error: String },
+    Error {
+        error: String,
+    },
 }
 
 /// Outcome for a single file within a batch transfer
@@ -76,6 +80,8 @@ impl ScpFileProgress {
 #[derive(Clone, Debug)]
 pub struct ScpProgress {
     pub connection_name: String,
+    /// Shown in the popup's title
+    pub protocol: &'static str,
     pub start_time: std::time::Instant,
     pub files: Vec<ScpFileProgress>,
     pub completed: bool,
@@ -99,6 +105,7 @@ impl ScpProgress {
     pub fn new(connection_name: String, files: Vec<ScpFileProgress>) -> Self {
         Self {
             connection_name,
+            protocol: "SFTP",
             start_time: std::time::Instant::now(),
             files,
             completed: false,
@@ -109,6 +116,12 @@ impl ScpProgress {
         }
     }
 
+    pub fn add_file(&mut self, file: ScpFileProgress) {
+        self.files.push(file);
+        // More is coming; the transfer isn't done after all
+        self.all_files_done_at = None;
+    }
+
     /// Check if all files have finished transferring (100% or failed)
     pub fn all_files_finished(&self) -> bool {
         self.files
@@ -0,0 +1,183 @@
+//! Local shell sessions: the user's shell in a local PTY.
+
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+
+use futures::future::BoxFuture;
+use portable_pty::{Child, CommandBuilder, MasterPty, PtySize, native_pty_system};
+use tracing::{debug, info};
+
+use super::{ChunkReader, TerminalTransport};
+use crate::async_ssh_client::DEFAULT_TERM;
+use crate::config::manager::Connection;
+use crate::error::{AppError, Result};
+
+fn pty_error(e: impl std::fmt::Display) -> AppError {
+    AppError::LocalTerminalError(e.to_string())
+}
+
+fn pty_size(cols: u16, rows: u16) -> PtySize {
+    PtySize {
+        rows,
+        cols,
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+/// The writing side of a local shell
+pub struct LocalPty {
+    master: Mutex<Box<dyn MasterPty + Send>>,
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+    child: Arc<Mutex<Box<dyn Child + Send + Sync>>>,
+}
+
+impl LocalPty {
+    /// Start the user's shell (`$SHELL`, or the platform default) in a new
+    /// PTY, in `start_dir` or the home directory
+    pub fn spawn(
+        connection: &Connection,
+        cols: u16,
+        rows: u16,
+        start_dir: Option<&str>,
+    ) -> Result<(Self, ChunkReader)> {
+        let pair = native_pty_system()
+            .openpty(pty_size(cols, rows))
+            .map_err(pty_error)?;
+
+        let mut cmd = CommandBuilder::new_default_prog();
+        let term = connection.term.as_deref().unwrap_or(DEFAULT_TERM);
+        cmd.env("TERM", term);
+        for (name, value) in &connection.env {
+            cmd.env(name, value);
+        }
+        let start_dir = start_dir
+            .or(connection.start_dir.as_deref())
+            .map(crate::expand_tilde)
+            .or_else(dirs::home_dir);
+        if let Some(dir) = start_dir {
+            cmd.cwd(dir);
+        }
+
+        info!(
+            "Starting local shell with size {} cols x {} rows",
+            cols, rows
+        );
+        let child = pair.slave.spawn_command(cmd).map_err(pty_error)?;
+        // Only the child keeps the slave side open, so reads end when it exits
+        drop(pair.slave);
+
+        let mut reader = pair.master.try_clone_reader().map_err(pty_error)?;
+        let writer = pair.master.take_writer().map_err(pty_error)?;
+
+        // PTY reads block, so they get a thread of their own
+        let (tx, chunks) = ChunkReader::channel();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 8192];
+            loop {
+                match reader.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if tx.blocking_send(Ok(buf[..n].to_vec())).is_err() {
+                            break;
+                        }
+                    }
+                    // EIO once the shell has exited and the slave is closed,
+                    // which ends the session like any other exit
+                    Err(e) => {
+                        debug!("Local shell read ended: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok((
+            Self {
+                master: Mutex::new(pair.master),
+                writer: Arc::new(Mutex::new(writer)),
+                child: Arc::new(Mutex::new(child)),
+            },
+            chunks,
+        ))
+    }
+
+    /// Kill the shell if it is still running and reap it. This blocks
+    /// until the shell has exited, so it runs off the async runtime.
+    fn kill(child: &Mutex<Box<dyn Child + Send + Sync>>) -> Result<()> {
+        let mut child = child.lock().unwrap_or_else(|e| e.into_inner());
+        if child.try_wait()?.is_none() {
+            debug!("Killing local shell");
+            child.kill()?;
+            let _ = child.wait();
+        }
+        Ok(())
+    }
+}
+
+impl TerminalTransport for LocalPty {
+    fn write_all<'a>(&'a self, data: &'a [u8]) -> BoxFuture<'a, Result<()>> {
+        let writer = self.writer.clone();
+        let data = data.to_vec();
+        Box::pin(async move {
+            // A shell that isn't reading its input can block the write
+            tokio::task::spawn_blocking(move || {
+                let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+                writer.write_all(&data)?;
+                writer.flush()
+            })
+            .await
+            .map_err(pty_error)?
+            .map_err(|e| AppError::ChannelClosedError(e.to_string()))
+        })
+    }
+
+    fn resize(&self, cols: u16, rows: u16) -> BoxFuture<'_, Result<()>> {
+        let master = self.master.lock().unwrap_or_else(|e| e.into_inner());
+        let result = master.resize(pty_size(cols, rows)).map_err(pty_error);
+        Box::pin(async move { result })
+    }
+
+    fn close(&self) -> BoxFuture<'_, Result<()>> {
+        let child = self.child.clone();
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || Self::kill(&child))
+                .await
+                .map_err(pty_error)?
+        })
+    }
+}
+
+impl Drop for LocalPty {
+    fn drop(&mut self) {
+        // Drop may run outside the runtime, so the shell is reaped on a
+        // thread of its own
+        let child = self.child.clone();
+        std::thread::spawn(move || {
+            let _ = Self::kill(&child);
+        });
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_shell_round_trip() {
+        let mut connection = Connection::local();
+        connection
+            .env
+            .insert("TERMIRS_TEST".to_string(), "hello".to_string());
+        let (pty, mut reader) = LocalPty::spawn(&connection, 80, 24, Some("/")).unwrap();
+        pty.write_all(b"echo \"$TERMIRS_TEST from $(pwd)\"; exit\n")
+            .await
+            .unwrap();
+
+        let mut output = Vec::new();
+        while let Some(chunk) = reader.recv().await {
+            output.extend_from_slice(&chunk.unwrap());
+        }
+        assert!(String::from_utf8_lossy(&output).contains("hello from /"));
+    }
+}
@@ -0,0 +1,47 @@
+//! Transports behind a terminal session. SSH channels are handled in
+//! `async_ssh_client`; the others (local PTYs, telnet and raw TCP) deliver
+//! their output through a [`ChunkReader`].
+
+pub mod local;
+pub mod tcp;
+pub mod telnet;
+
+use futures::future::BoxFuture;
+use tokio::sync::mpsc;
+
+use crate::error::Result;
+
+pub use local::LocalPty;
+pub use tcp::TcpTransport;
+
+/// Chunks of output that may wait for the read loop
+const READ_QUEUE: usize = 64;
+
+/// The input side of a terminal session
+pub trait TerminalTransport: Send + Sync {
+    /// Send input to the remote side
+    fn write_all<'a>(&'a self, data: &'a [u8]) -> BoxFuture<'a, Result<()>>;
+
+    /// Report a new terminal size, if the protocol has a way to
+    fn resize(&self, cols: u16, rows: u16) -> BoxFuture<'_, Result<()>>;
+
+    /// End the session
+    fn close(&self) -> BoxFuture<'_, Result<()>>;
+}
+
+/// Output of a session, produced by a reader task or thread. The channel
+/// closes when the session ends; an error means the connection was lost.
+pub struct ChunkReader {
+    rx: mpsc::Receiver<std::io::Result<Vec<u8>>>,
+}
+
+impl ChunkReader {
+    fn channel() -> (mpsc::Sender<std::io::Result<Vec<u8>>>, Self) {
+        let (tx, rx) = mpsc::channel(READ_QUEUE);
+        (tx, Self { rx })
+    }
+
+    pub async fn recv(&mut self) -> Option<std::io::Result<Vec<u8>>> {
+        self.rx.recv().await
+    }
+}
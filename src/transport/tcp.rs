@@ -0,0 +1,168 @@
+//! Telnet and raw TCP sessions, e.g. network devices and console servers.
+
+use std::sync::{Arc, Mutex};
+
+use futures::future::BoxFuture;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::net::tcp::OwnedWriteHalf;
+use tracing::{debug, info};
+
+use super::telnet::Telnet;
+use super::{ChunkReader, TerminalTransport};
+use crate::async_ssh_client::DEFAULT_TERM;
+use crate::config::manager::{Connection, ConnectionKind};
+use crate::error::{AppError, Result};
+
+/// The input side of a telnet or raw TCP session
+pub struct TcpTransport {
+    writer: Arc<tokio::sync::Mutex<OwnedWriteHalf>>,
+    /// Protocol state for telnet; `None` for raw TCP
+    telnet: Option<Arc<Mutex<Telnet>>>,
+}
+
+impl TcpTransport {
+    /// Connect to the connection's host and port. Telnet negotiation is
+    /// answered by the reader task as requests arrive.
+    pub async fn connect(
+        connection: &Connection,
+        cols: u16,
+        rows: u16,
+    ) -> Result<(Self, ChunkReader)> {
+        info!(
+            "Opening {} session to {}",
+            connection.kind.label(),
+            connection.host_port()
+        );
+        let stream = TcpStream::connect((connection.host.as_str(), connection.port)).await?;
+        let _ = stream.set_nodelay(true);
+        let (mut reader, writer) = stream.into_split();
+        let writer = Arc::new(tokio::sync::Mutex::new(writer));
+        let telnet = (connection.kind == ConnectionKind::Telnet).then(|| {
+            let term = connection.term.as_deref().unwrap_or(DEFAULT_TERM);
+            Arc::new(Mutex::new(Telnet::new(term, cols, rows)))
+        });
+
+        let (tx, chunks) = ChunkReader::channel();
+        let reply_writer = writer.clone();
+        let reply_telnet = telnet.clone();
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; 8192];
+            loop {
+                let n = match reader.read(&mut buf).await {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        break;
+                    }
+                };
+                let data = match &reply_telnet {
+                    Some(telnet) => {
+                        let mut replies = Vec::new();
+                        let data = telnet
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .receive(&buf[..n], &mut replies);
+                        if !replies.is_empty()
+                            && let Err(e) = reply_writer.lock().await.write_all(&replies).await
+                        {
+                            debug!("Failed to answer telnet negotiation: {}", e);
+                        }
+                        data
+                    }
+                    None => buf[..n].to_vec(),
+                };
+                if !data.is_empty() && tx.send(Ok(data)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok((Self { writer, telnet }, chunks))
+    }
+
+    async fn send(&self, data: &[u8]) -> Result<()> {
+        self.writer
+            .lock()
+            .await
+            .write_all(data)
+            .await
+            .map_err(|e| AppError::ChannelClosedError(e.to_string()))
+    }
+}
+
+impl TerminalTransport for TcpTransport {
+    fn write_all<'a>(&'a self, data: &'a [u8]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            match &self.telnet {
+                Some(telnet) => {
+                    let encoded = telnet
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .encode_input(data);
+                    self.send(&encoded).await
+                }
+                None => self.send(data).await,
+            }
+        })
+    }
+
+    fn resize(&self, cols: u16, rows: u16) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            let message = self.telnet.as_ref().and_then(|telnet| {
+                telnet
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .resize(cols, rows)
+            });
+            match message {
+                Some(message) => self.send(&message).await,
+                None => Ok(()),
+            }
+        })
+    }
+
+    fn close(&self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            self.writer.lock().await.shutdown().await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_raw_tcp_round_trip() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 5];
+            socket.read_exact(&mut buf).await.unwrap();
+            socket.write_all(b"got ").await.unwrap();
+            socket.write_all(&buf).await.unwrap();
+        });
+
+        let mut connection = Connection::new(
+            "127.0.0.1".to_string(),
+            port,
+            String::new(),
+            crate::config::manager::AuthMethod::None,
+        );
+        connection.kind = ConnectionKind::Tcp;
+        let (transport, mut reader) = TcpTransport::connect(&connection, 80, 24).await.unwrap();
+        transport.write_all(b"hello").await.unwrap();
+
+        let mut output = Vec::new();
+        while let Some(chunk) = reader.recv().await {
+            output.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(output, b"got hello");
+        server.await.unwrap();
+    }
+}
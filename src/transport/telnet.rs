@@ -0,0 +1,271 @@
+//! The telnet protocol (RFC 854) as far as terminal sessions need it:
+//! commands are separated from the data, and the server may turn on
+//! echo, suppress-go-ahead, binary mode, terminal type and window size.
+//! Every other option is refused.
+
+use tracing::debug;
+
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+const OPT_BINARY: u8 = 0;
+const OPT_ECHO: u8 = 1;
+const OPT_SGA: u8 = 3;
+const OPT_TTYPE: u8 = 24;
+const OPT_NAWS: u8 = 31;
+
+/// `IS` and `SEND` of the terminal type subnegotiation (RFC 1091)
+const TTYPE_IS: u8 = 0;
+const TTYPE_SEND: u8 = 1;
+
+/// Options we agree to perform when the server sends DO
+/// Longest subnegotiation kept; the ones we answer are a few bytes, and a
+/// longer one is discarded rather than buffered without limit
+const MAX_SUBNEGOTIATION: usize = 4096;
+
+const LOCAL_OPTIONS: [u8; 4] = [OPT_BINARY, OPT_SGA, OPT_TTYPE, OPT_NAWS];
+/// Options we let the server perform when it sends WILL
+const REMOTE_OPTIONS: [u8; 3] = [OPT_BINARY, OPT_ECHO, OPT_SGA];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ParseState {
+    #[default]
+    Data,
+    Iac,
+    /// After IAC and one of WILL, WONT, DO or DONT
+    Negotiate(u8),
+    Subnegotiation,
+    SubnegotiationIac,
+}
+
+/// Telnet state of one connection
+#[derive(Clone, Debug)]
+pub struct Telnet {
+    state: ParseState,
+    term: String,
+    size: (u16, u16),
+    /// Options we perform, by option code
+    local: [bool; 256],
+    /// Options the server performs
+    remote: [bool; 256],
+    subnegotiation: Vec<u8>,
+    /// The current subnegotiation outgrew `MAX_SUBNEGOTIATION`
+    subnegotiation_overflow: bool,
+}
+
+impl Telnet {
+    pub fn new(term: &str, cols: u16, rows: u16) -> Self {
+        Self {
+            state: ParseState::Data,
+            term: term.to_string(),
+            size: (cols, rows),
+            local: [false; 256],
+            remote: [false; 256],
+            subnegotiation: Vec::new(),
+            subnegotiation_overflow: false,
+        }
+    }
+
+    /// Take the commands out of bytes from the server and return the data
+    /// left for the terminal. Answers to the server's requests are appended
+    /// to `replies`.
+    pub fn receive(&mut self, input: &[u8], replies: &mut Vec<u8>) -> Vec<u8> {
+        let mut data = Vec::with_capacity(input.len());
+        for &byte in input {
+            self.state = match (self.state, byte) {
+                (ParseState::Data, IAC) => ParseState::Iac,
+                (ParseState::Data, _) => {
+                    data.push(byte);
+                    ParseState::Data
+                }
+                (ParseState::Iac, IAC) => {
+                    data.push(IAC);
+                    ParseState::Data
+                }
+                (ParseState::Iac, WILL | WONT | DO | DONT) => ParseState::Negotiate(byte),
+                (ParseState::Iac, SB) => {
+                    self.subnegotiation.clear();
+                    self.subnegotiation_overflow = false;
+                    ParseState::Subnegotiation
+                }
+                // NOP, GA and the other commands carry nothing for us
+                (ParseState::Iac, _) => ParseState::Data,
+                (ParseState::Negotiate(command), option) => {
+                    self.negotiate(command, option, replies);
+                    ParseState::Data
+                }
+                (ParseState::Subnegotiation, IAC) => ParseState::SubnegotiationIac,
+                (ParseState::Subnegotiation, _) => {
+                    self.push_subnegotiation(byte);
+                    ParseState::Subnegotiation
+                }
+                (ParseState::SubnegotiationIac, SE) => {
+                    if self.subnegotiation_overflow {
+                        debug!("Discarding an oversized telnet subnegotiation");
+                    } else {
+                        self.subnegotiate(replies);
+                    }
+                    self.subnegotiation.clear();
+                    ParseState::Data
+                }
+                (ParseState::SubnegotiationIac, _) => {
+                    self.push_subnegotiation(byte);
+                    ParseState::Subnegotiation
+                }
+            };
+        }
+        data
+    }
+
+    fn push_subnegotiation(&mut self, byte: u8) {
+        if self.subnegotiation.len() < MAX_SUBNEGOTIATION {
+            self.subnegotiation.push(byte);
+        } else {
+            self.subnegotiation_overflow = true;
+        }
+    }
+
+    /// Answer a request, but only when it changes an option's state so
+    /// that the two sides can't loop
+    fn negotiate(&mut self, command: u8, option: u8, replies: &mut Vec<u8>) {
+        let idx = option as usize;
+        match command {
+            DO if LOCAL_OPTIONS.contains(&option) => {
+                if !self.local[idx] {
+                    self.local[idx] = true;
+                    replies.extend_from_slice(&[IAC, WILL, option]);
+                    if option == OPT_NAWS {
+                        replies.extend(self.window_size());
+                    }
+                }
+            }
+            DO => replies.extend_from_slice(&[IAC, WONT, option]),
+            DONT if self.local[idx] => {
+                self.local[idx] = false;
+                replies.extend_from_slice(&[IAC, WONT, option]);
+            }
+            WILL if REMOTE_OPTIONS.contains(&option) => {
+                if !self.remote[idx] {
+                    self.remote[idx] = true;
+                    replies.extend_from_slice(&[IAC, DO, option]);
+                }
+            }
+            WILL => replies.extend_from_slice(&[IAC, DONT, option]),
+            WONT if self.remote[idx] => {
+                self.remote[idx] = false;
+                replies.extend_from_slice(&[IAC, DONT, option]);
+            }
+            _ => {}
+        }
+    }
+
+    fn subnegotiate(&mut self, replies: &mut Vec<u8>) {
+        if self.subnegotiation == [OPT_TTYPE, TTYPE_SEND] && self.local[OPT_TTYPE as usize] {
+            replies.extend_from_slice(&[IAC, SB, OPT_TTYPE, TTYPE_IS]);
+            replies.extend_from_slice(self.term.as_bytes());
+            replies.extend_from_slice(&[IAC, SE]);
+        }
+    }
+
+    /// Record a new terminal size; returns the NAWS message to send if the
+    /// server asked for window sizes
+    pub fn resize(&mut self, cols: u16, rows: u16) -> Option<Vec<u8>> {
+        self.size = (cols, rows);
+        self.local[OPT_NAWS as usize].then(|| self.window_size())
+    }
+
+    /// `IAC SB NAWS <cols> <rows> IAC SE`, with IAC bytes in the size doubled
+    fn window_size(&self) -> Vec<u8> {
+        let (cols, rows) = self.size;
+        let mut message = vec![IAC, SB, OPT_NAWS];
+        for byte in [cols.to_be_bytes(), rows.to_be_bytes()].concat() {
+            message.push(byte);
+            if byte == IAC {
+                message.push(IAC);
+            }
+        }
+        message.extend_from_slice(&[IAC, SE]);
+        message
+    }
+
+    /// Input for the server: IAC doubled, and a bare CR sent as CR NUL
+    pub fn encode_input(&self, data: &[u8]) -> Vec<u8> {
+        let binary = self.local[OPT_BINARY as usize];
+        let mut encoded = Vec::with_capacity(data.len());
+        for (idx, &byte) in data.iter().enumerate() {
+            encoded.push(byte);
+            match byte {
+                IAC => encoded.push(IAC),
+                b'\r' if !binary && data.get(idx + 1) != Some(&b'\n') => encoded.push(0),
+                _ => {}
+            }
+        }
+        encoded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiation() {
+        let mut telnet = Telnet::new("xterm-256color", 80, 24);
+        let mut replies = Vec::new();
+        let data = telnet.receive(
+            &[
+                b'h', IAC, WILL, OPT_ECHO, IAC, DO, OPT_NAWS, b'i', IAC, IAC, IAC, DO, 39, IAC,
+                WILL, OPT_ECHO,
+            ],
+            &mut replies,
+        );
+        assert_eq!(data, [b'h', b'i', IAC]);
+        assert_eq!(
+            replies,
+            [
+                IAC, DO, OPT_ECHO, // accepted once, not again
+                IAC, WILL, OPT_NAWS, IAC, SB, OPT_NAWS, 0, 80, 0, 24, IAC, SE, //
+                IAC, WONT, 39,
+            ]
+        );
+
+        // A command split across reads
+        replies.clear();
+        telnet.receive(&[IAC, DO], &mut replies);
+        telnet.receive(&[OPT_TTYPE, IAC, SB, OPT_TTYPE], &mut replies);
+        telnet.receive(&[TTYPE_SEND, IAC, SE], &mut replies);
+        let mut expected = vec![IAC, WILL, OPT_TTYPE, IAC, SB, OPT_TTYPE, TTYPE_IS];
+        expected.extend_from_slice(b"xterm-256color");
+        expected.extend_from_slice(&[IAC, SE]);
+        assert_eq!(replies, expected);
+
+        assert_eq!(
+            telnet.resize(255, 50),
+            Some(vec![IAC, SB, OPT_NAWS, 0, 255, 255, 0, 50, IAC, SE])
+        );
+
+        // An endless subnegotiation is capped and then dropped unanswered
+        replies.clear();
+        telnet.receive(&[IAC, SB, OPT_TTYPE, TTYPE_SEND], &mut replies);
+        telnet.receive(&vec![b'x'; 3 * MAX_SUBNEGOTIATION], &mut replies);
+        assert!(telnet.subnegotiation.len() <= MAX_SUBNEGOTIATION);
+        let data = telnet.receive(&[IAC, SE, b'o', b'k'], &mut replies);
+        assert_eq!(data, b"ok");
+        assert!(replies.is_empty());
+    }
+
+    #[test]
+    fn test_encode_input() {
+        let mut telnet = Telnet::new("vt100", 80, 24);
+        // No window size until the server asks for it
+        assert_eq!(telnet.resize(100, 30), None);
+        assert_eq!(telnet.encode_input(b"ls\r"), b"ls\r\0");
+        assert_eq!(telnet.encode_input(b"a\r\nb"), b"a\r\nb");
+        assert_eq!(telnet.encode_input(&[1, IAC, 2]), [1, IAC, IAC, 2]);
+    }
+}
@@ -4,10 +4,12 @@ use ratatui::style::Style;
 use ratatui::widgets::{Cell, Row};
 use tui_textarea::TextArea;
 
-use crate::config::manager::{AuthMethod, Connection};
+use crate::config::manager::{AuthMethod, Connection, ConnectionKind};
+use crate::config::theme::Theme;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FocusField {
+    Kind,
     Host,
     Port,
     Username,
@@ -18,6 +20,7 @@ pub enum FocusField {
 
 #[derive(Clone, Debug)]
 pub struct ConnectionForm {
+    pub kind: ConnectionKind,
     pub host: TextArea<'static>,
     pub port: TextArea<'static>,
     pub username: TextArea<'static>,
@@ -41,7 +44,7 @@ impl ConnectionForm {
         host.set_cursor_line_style(Style::default());
 
         let mut port = TextArea::default();
-        port.set_placeholder_text("22");
+        port.set_placeholder_text(port_placeholder(ConnectionKind::Ssh));
         port.set_cursor_line_style(Style::default());
 
         let mut username = TextArea::default();
@@ -62,6 +65,7 @@ impl ConnectionForm {
         display_name.set_cursor_line_style(Style::default());
 
         Self {
+            kind: ConnectionKind::Ssh,
             host,
             port,
             username,
@@ -74,35 +78,56 @@ impl ConnectionForm {
     }
 
     pub fn next(&mut self) {
+        let ssh = self.kind.is_ssh();
         self.focus = match self.focus {
+            FocusField::Kind => FocusField::Host,
             FocusField::Host => FocusField::Port,
-            FocusField::Port => FocusField::Username,
+            FocusField::Port if ssh => FocusField::Username,
+            FocusField::Port => FocusField::DisplayName,
             FocusField::Username => FocusField::Password,
             FocusField::Password => FocusField::PrivateKeyPath,
             FocusField::PrivateKeyPath => FocusField::DisplayName,
-            FocusField::DisplayName => FocusField::Host,
+            FocusField::DisplayName => FocusField::Kind,
         };
     }
 
     pub fn prev(&mut self) {
+        let ssh = self.kind.is_ssh();
         self.focus = match self.focus {
-            FocusField::Host => FocusField::DisplayName,
+            FocusField::Kind => FocusField::DisplayName,
+            FocusField::Host => FocusField::Kind,
             FocusField::Port => FocusField::Host,
             FocusField::Username => FocusField::Port,
             FocusField::Password => FocusField::Username,
             FocusField::PrivateKeyPath => FocusField::Password,
-            FocusField::DisplayName => FocusField::PrivateKeyPath,
+            FocusField::DisplayName if ssh => FocusField::PrivateKeyPath,
+            FocusField::DisplayName => FocusField::Port,
         };
     }
 
-    pub fn focused_textarea_mut(&mut self) -> &mut TextArea<'static> {
+    /// Switch to the next (or previous) kind of connection. Telnet and raw
+    /// TCP have no username or authentication fields.
+    pub fn cycle_kind(&mut self, forward: bool) {
+        let kinds = [
+            ConnectionKind::Ssh,
+            ConnectionKind::Telnet,
+            ConnectionKind::Tcp,
+        ];
+        let current = kinds.iter().position(|k| *k == self.kind).unwrap_or(0);
+        let step = if forward { 1 } else { kinds.len() - 1 };
+        self.kind = kinds[(current + step) % kinds.len()];
+        self.port.set_placeholder_text(port_placeholder(self.kind));
+    }
+
+    pub fn focused_textarea_mut(&mut self) -> Option<&mut TextArea<'static>> {
         match self.focus {
-            FocusField::Host => &mut self.host,
-            FocusField::Port => &mut self.port,
-            FocusField::Username => &mut self.username,
-            FocusField::Password => &mut self.password,
-            FocusField::PrivateKeyPath => &mut self.private_key_path,
-            FocusField::DisplayName => &mut self.display_name,
+            FocusField::Kind => None, // Handled by kind selector
+            FocusField::Host => Some(&mut self.host),
+            FocusField::Port => Some(&mut self.port),
+            FocusField::Username => Some(&mut self.username),
+            FocusField::Password => Some(&mut self.password),
+            FocusField::PrivateKeyPath => Some(&mut self.private_key_path),
+            FocusField::DisplayName => Some(&mut self.display_name),
         }
     }
 
@@ -134,16 +159,30 @@ impl ConnectionForm {
         if self.get_host_value().trim().is_empty() {
             return Err("Host is required".into());
         }
-        if self.get_username_value().trim().is_empty() {
+        // Telnet and raw TCP hosts ask for a login in the session itself
+        if self.kind.is_ssh() && self.get_username_value().trim().is_empty() {
             return Err("Username is required".into());
         }
         let port_str = self.get_port_value().trim();
+        if port_str.is_empty() && self.kind == ConnectionKind::Tcp {
+            return Err("Port is required for raw TCP".into());
+        }
         if !port_str.is_empty() && port_str.parse::<u16>().is_err() {
             return Err("Port must be a number".into());
         }
         Ok(())
     }
 
+    /// Port used when the field is left empty: the settings' default for
+    /// SSH, 23 for telnet. Raw TCP has no standard port, so it must be given.
+    pub fn port_or_default(&self, ssh_default: u16) -> u16 {
+        match self.get_port_value().trim().parse::<u16>() {
+            Ok(port) => port,
+            Err(_) if self.kind == ConnectionKind::Telnet => 23,
+            Err(_) => ssh_default,
+        }
+    }
+
     fn from_connection(conn: &Connection) -> Self {
         let mut host = TextArea::default();
         host.set_placeholder_text("Enter hostname or IP address");
@@ -151,7 +190,7 @@ impl ConnectionForm {
         host.insert_str(&conn.host);
 
         let mut port = TextArea::default();
-        port.set_placeholder_text("22");
+        port.set_placeholder_text(port_placeholder(conn.kind));
         port.set_cursor_line_style(Style::default());
         port.insert_str(conn.port.to_string());
 
@@ -192,6 +231,7 @@ impl ConnectionForm {
         display_name.insert_str(conn.display_name.clone());
 
         Self {
+            kind: conn.kind,
             host,
             port,
             username,
@@ -204,6 +244,14 @@ impl ConnectionForm {
     }
 }
 
+fn port_placeholder(kind: ConnectionKind) -> &'static str {
+    match kind {
+        ConnectionKind::Telnet => "23",
+        ConnectionKind::Tcp => "Enter port (required)",
+        _ => "22",
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ConnectionListItem<'a> {
     pub name: &'a str,
@@ -222,6 +270,7 @@ pub fn draw_connection_list(
     search: &crate::SearchState,
     frame: &mut ratatui::Frame<'_>,
     choose_connection_mode: bool,
+    theme: &Theme,
 ) {
     // Build the list items
     let items: Vec<ConnectionListItem> = conns
@@ -236,7 +285,9 @@ pub fn draw_connection_list(
                 .with_timezone(&Local)
                 .format("%Y-%m-%d %H:%M")
                 .to_string(),
+            // Telnet and TCP hosts log in in-band, so show the kind instead
             auth_method: match &c.auth_method {
+                _ if !c.kind.is_ssh() => c.kind.label(),
                 AuthMethod::Password(_) => "password",
                 AuthMethod::PublicKey { .. } => "public key",
                 AuthMethod::AutoLoadKey => "auto-load key",
@@ -255,7 +306,7 @@ pub fn draw_connection_list(
         }
     } else {
         ConnectionTableComponentWithMode {
-            hints: "Enter: Connect   K/↑: Up   J/↓: Down   N: New   I: File Explorer   P: Port Forward   D: Delete   E: Edit   /: Search",
+            hints: "Enter: Connect   K/↑: Up   J/↓: Down   N: New   L: Local Shell   I: File Explorer   P: Port Forward   D: Delete   E: Edit   /: Search",
         }
     };
 
@@ -270,7 +321,7 @@ pub fn draw_connection_list(
     };
 
     // Use the generic table renderer
-    super::table_renderer::draw_table_list(area, &component, items, &state, frame, title);
+    super::table_renderer::draw_table_list(area, &component, items, &state, frame, title, theme);
 }
 
 // Helper component that allows customizing footer hints
@@ -342,6 +393,7 @@ mod tests {
     fn test_connection_form() {
         let conn = Connection {
             id: "1".to_string(),
+            kind: Default::default(),
             created_at: Utc::now(),
             last_used: None,
             public_key: None,
@@ -350,9 +402,50 @@ mod tests {
             port: 22,
             username: "test".to_string(),
             auth_method: AuthMethod::Password("test".to_string().into()),
+            auto_reconnect: false,
+            startup_command: None,
+            env: Default::default(),
+            remote_command: None,
+            start_dir: None,
+            term: None,
+            color_scheme: None,
+            snippets: Vec::new(),
+            record_sessions: false,
+            session_log: None,
+            persist_scrollback: false,
+            persistent_session: false,
+            notify_when_idle: false,
+            ssh_options: Default::default(),
         };
 
         let form = ConnectionForm::from_connection(&conn);
         assert_eq!(form.get_password_value(), "test");
     }
+
+    #[test]
+    fn test_telnet_and_tcp_forms_skip_login_fields() {
+        let mut form = ConnectionForm::new();
+        form.host.insert_str("switch-1");
+        assert_eq!(form.validate(), Err("Username is required".to_string()));
+
+        form.cycle_kind(true);
+        assert_eq!(form.kind, ConnectionKind::Telnet);
+        assert_eq!(form.validate(), Ok(()));
+        assert_eq!(form.port_or_default(22), 23);
+        form.focus = FocusField::Port;
+        form.next();
+        assert_eq!(form.focus, FocusField::DisplayName);
+        form.prev();
+        assert_eq!(form.focus, FocusField::Port);
+
+        form.cycle_kind(true);
+        assert_eq!(form.kind, ConnectionKind::Tcp);
+        assert!(form.validate().is_err());
+        form.port.insert_str("7001");
+        assert_eq!(form.validate(), Ok(()));
+        form.cycle_kind(true);
+        assert_eq!(form.kind, ConnectionKind::Ssh);
+        form.cycle_kind(false);
+        assert_eq!(form.kind, ConnectionKind::Tcp);
+    }
 }
@@ -0,0 +1,75 @@
+//! Export menu overlay for the connected terminal.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::config::theme::Theme;
+use crate::terminal::ExportMenu;
+
+/// Draw the export menu: what to export, in which format and where to
+pub fn draw_export_menu(f: &mut Frame, area: Rect, menu: &ExportMenu, theme: &Theme) {
+    let popup_w = 60.min(area.width.saturating_sub(4));
+    let popup_h = (ExportMenu::ROWS as u16 + 4).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(popup_w)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_h)) / 2,
+        width: popup_w,
+        height: popup_h,
+    };
+
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(Span::styled(
+            " Export ",
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )));
+    f.render_widget(block, popup);
+
+    let inner = popup.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let lines: Vec<Line> = menu
+        .rows()
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (label, value))| {
+            let value_style = if idx == menu.selected {
+                Style::default()
+                    .bg(theme.selection_bg)
+                    .fg(theme.selection_fg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            Line::from(vec![
+                Span::styled(format!("{label:>7}: "), Style::default().fg(theme.muted)),
+                Span::styled(format!("◀ {value} ▶"), value_style),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), sections[0]);
+
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            "↑↓: Select | ←→: Change | Enter: Export | Esc: Close",
+            Style::default()
+                .fg(theme.footer)
+                .add_modifier(Modifier::DIM),
+        )),
+        sections[1],
+    );
+}
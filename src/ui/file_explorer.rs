@@ -11,6 +11,7 @@ use std::collections::HashSet;
 use std::path::PathBuf;
 
 use crate::config::manager::Connection;
+use crate::config::theme::Theme;
 use crate::filesystem::SftpFileSystem;
 use crate::{ActivePane, CopyOperation, FileExplorerPane, LeftExplorer, SearchState};
 
@@ -27,6 +28,7 @@ pub fn draw_file_explorer(
     copy_buffer: &[CopyOperation],
     search: &SearchState,
     use_icons: bool,
+    theme: &Theme,
 ) {
     // Main layout: header, content, footer
     let main_layout = Layout::default()
@@ -39,7 +41,7 @@ pub fn draw_file_explorer(
         .split(area);
 
     // Render header
-    draw_header(f, main_layout[0], connection_name, copy_buffer);
+    draw_header(f, main_layout[0], connection_name, copy_buffer, theme);
 
     // Split content area into left and right panes
     let panes = Layout::default()
@@ -66,6 +68,7 @@ pub fn draw_file_explorer(
                 matches!(active_pane, ActivePane::Left),
                 copy_buffer,
                 use_icons,
+                theme,
             );
         }
         LeftExplorer::Remote(explorer) => {
@@ -77,6 +80,7 @@ pub fn draw_file_explorer(
                 matches!(active_pane, ActivePane::Left),
                 copy_buffer,
                 use_icons,
+                theme,
             );
         }
     }
@@ -90,14 +94,21 @@ pub fn draw_file_explorer(
         matches!(active_pane, ActivePane::Right),
         copy_buffer,
         use_icons,
+        theme,
     );
 
     // Render footer
-    draw_footer(f, main_layout[2], copy_buffer, search);
+    draw_footer(f, main_layout[2], copy_buffer, search, theme);
 }
 
 /// Draw the header showing connection name and copy status
-fn draw_header(f: &mut Frame, area: Rect, connection_name: &str, copy_buffer: &[CopyOperation]) {
+fn draw_header(
+    f: &mut Frame,
+    area: Rect,
+    connection_name: &str,
+    copy_buffer: &[CopyOperation],
+    theme: &Theme,
+) {
     let header_text = if let Some(first) = copy_buffer.first() {
         let direction = match first.direction {
             crate::CopyDirection::LeftToRight => "Left → Right",
@@ -124,11 +135,11 @@ fn draw_header(f: &mut Frame, area: Rect, connection_name: &str, copy_buffer: &[
 
     let header_style = if copy_buffer.is_empty() {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme.title)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.warning)
             .add_modifier(Modifier::BOLD)
     };
 
@@ -142,6 +153,7 @@ fn draw_header(f: &mut Frame, area: Rect, connection_name: &str, copy_buffer: &[
 }
 
 /// Draw a single pane (local or remote)
+#[allow(clippy::too_many_arguments)]
 fn draw_pane<F: ratatui_async_explorer::FileSystem>(
     f: &mut Frame,
     area: Rect,
@@ -150,6 +162,7 @@ fn draw_pane<F: ratatui_async_explorer::FileSystem>(
     is_active: bool,
     copy_buffer: &[CopyOperation],
     use_icons: bool,
+    theme: &Theme,
 ) {
     // Build HashSet of selected paths from copy_buffer
     let selected_paths: HashSet<PathBuf> = copy_buffer
@@ -161,9 +174,9 @@ fn draw_pane<F: ratatui_async_explorer::FileSystem>(
     explorer.set_selected_paths(selected_paths);
 
     let border_style = if is_active {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme.border)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.muted)
     };
 
     let title_text = format!(" {} | {} ", title, explorer.cwd().display());
@@ -193,20 +206,26 @@ fn draw_pane<F: ratatui_async_explorer::FileSystem>(
     if is_active {
         explorer.set_theme(
             ratatui_async_explorer::Theme::new()
-                .with_item_style(Style::default().fg(Color::White))
-                .with_dir_style(Style::default().fg(Color::LightBlue))
-                .with_highlight_dir_style(Style::default().fg(Color::LightBlue).bg(Color::Cyan))
-                .with_highlight_item_style(Style::default().fg(Color::White).bg(Color::Cyan))
+                .with_item_style(Style::default().fg(theme.text))
+                .with_dir_style(Style::default().fg(theme.directory))
+                .with_highlight_dir_style(
+                    Style::default().fg(theme.directory).bg(theme.selection_bg),
+                )
+                .with_highlight_item_style(
+                    Style::default()
+                        .fg(theme.selection_fg)
+                        .bg(theme.selection_bg),
+                )
                 .with_icons(icon_display),
         );
     } else {
         // Don't highlight the items and directories
         explorer.set_theme(
             ratatui_async_explorer::Theme::new()
-                .with_item_style(Style::default().fg(Color::White))
-                .with_dir_style(Style::default().fg(Color::LightBlue))
-                .with_highlight_dir_style(Style::default().fg(Color::LightBlue))
-                .with_highlight_item_style(Style::default().fg(Color::White))
+                .with_item_style(Style::default().fg(theme.text))
+                .with_dir_style(Style::default().fg(theme.directory))
+                .with_highlight_dir_style(Style::default().fg(theme.directory))
+                .with_highlight_item_style(Style::default().fg(theme.text))
                 .with_icons(icon_display),
         );
     }
@@ -240,20 +259,26 @@ fn draw_pane<F: ratatui_async_explorer::FileSystem>(
 }
 
 /// Draw the footer showing available keybindings
-fn draw_footer(f: &mut Frame, area: Rect, copy_buffer: &[CopyOperation], search: &SearchState) {
+fn draw_footer(
+    f: &mut Frame,
+    area: Rect,
+    copy_buffer: &[CopyOperation],
+    search: &SearchState,
+    theme: &Theme,
+) {
     if search.is_on() {
         // Search mode: show search input with placeholder
         let mut spans = vec![Span::styled(
             "Search: ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )];
 
         if search.query().is_empty() {
             spans.push(Span::styled(
                 "Type to filter files",
-                Style::default().fg(Color::DarkGray).dim(),
+                Style::default().fg(theme.muted).dim(),
             ));
         } else {
             spans.push(Span::raw(search.query()));
@@ -268,13 +293,13 @@ fn draw_footer(f: &mut Frame, area: Rect, copy_buffer: &[CopyOperation], search:
             Span::styled(
                 "Searched: ",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(search.query(), Style::default().fg(Color::Yellow)),
+            Span::styled(search.query(), Style::default().fg(theme.warning)),
             Span::styled(
                 "   Press Esc to clear",
-                Style::default().fg(Color::DarkGray).dim(),
+                Style::default().fg(theme.muted).dim(),
             ),
         ]))
         .style(Style::default().bg(Color::Reset));
@@ -296,7 +321,7 @@ fn draw_footer(f: &mut Frame, area: Rect, copy_buffer: &[CopyOperation], search:
                 .to_string()
         };
 
-        super::render_normal_footer(f, area, &hint_text);
+        super::render_normal_footer(f, area, &hint_text, theme);
     }
 }
 
@@ -311,6 +336,7 @@ pub fn draw_connection_selector_popup(
     include_local_option: bool,
     title: &str,
     search: &SearchState,
+    theme: &Theme,
 ) {
     use ratatui::widgets::{Clear, List, ListItem, ListState};
 
@@ -337,7 +363,7 @@ pub fn draw_connection_selector_popup(
         .title(Line::from(Span::styled(
             title,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )));
 
@@ -352,7 +378,7 @@ pub fn draw_connection_selector_popup(
     if include_local_option {
         items.push(ListItem::new(Line::from(Span::styled(
             "📁 Local Filesystem",
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text),
         ))));
     }
 
@@ -361,7 +387,7 @@ pub fn draw_connection_selector_popup(
             let display = format!("🌐 {} ({}@{})", conn.display_name, conn.username, conn.host);
             items.push(ListItem::new(Line::from(Span::styled(
                 display,
-                Style::default().fg(Color::White),
+                Style::default().fg(theme.text),
             ))));
         }
     }
@@ -392,8 +418,8 @@ pub fn draw_connection_selector_popup(
     let list = List::new(items)
         .highlight_style(
             Style::default()
-                .bg(Color::Cyan)
-                .fg(Color::Black)
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("▶ ");
@@ -415,7 +441,7 @@ pub fn draw_connection_selector_popup(
     let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
         .begin_symbol(None)
         .end_symbol(None)
-        .style(Style::default().fg(Color::Cyan));
+        .style(Style::default().fg(theme.border));
     f.render_stateful_widget(scrollbar, list_area, &mut scrollbar_state);
 
     // Render footer content (instructions or search)
@@ -424,14 +450,14 @@ pub fn draw_connection_selector_popup(
             let mut spans = vec![Span::styled(
                 "Search: ",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             )];
 
             if search.query().is_empty() {
                 spans.push(Span::styled(
                     "Type to filter connections",
-                    Style::default().fg(Color::DarkGray).dim(),
+                    Style::default().fg(theme.muted).dim(),
                 ));
             } else {
                 spans.push(Span::raw(search.query()));
@@ -445,27 +471,29 @@ pub fn draw_connection_selector_popup(
                 Span::styled(
                     "↑↓",
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.title)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(": Navigate  "),
                 Span::styled(
                     "Enter",
                     Style::default()
-                        .fg(Color::Green)
+                        .fg(theme.success)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(": Select  "),
                 Span::styled(
                     "/",
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(theme.warning)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(": Search  "),
                 Span::styled(
                     "Esc",
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    Style::default()
+                        .fg(theme.error)
+                        .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(": Cancel"),
             ]))
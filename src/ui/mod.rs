@@ -1,17 +1,23 @@
 pub mod connection;
+pub mod export;
 pub mod file_explorer;
 pub mod popup;
 pub mod port_forwarding;
 pub mod scp;
+pub mod snippet;
 pub mod table;
 pub mod table_renderer;
 pub mod terminal;
+pub mod url_picker;
+pub mod zmodem_picker;
 
 pub use connection::{ConnectionForm, draw_connection_list};
+pub use export::draw_export_menu;
 pub use file_explorer::{draw_connection_selector_popup, draw_file_explorer};
 pub use popup::{
-    DeleteConfirmationConfig, draw_connecting_popup, draw_connection_form_popup,
-    draw_delete_confirmation_popup, draw_error_popup, draw_info_popup,
+    DeleteConfirmationConfig, draw_close_session_popup, draw_connecting_popup,
+    draw_connection_form_popup, draw_delete_confirmation_popup, draw_error_popup, draw_info_popup,
+    draw_paste_confirmation_popup,
 };
 pub use port_forwarding::{
     PortForwardingForm, draw_port_forwarding_form_popup, draw_port_forwarding_list,
@@ -19,12 +25,19 @@ pub use port_forwarding::{
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
 };
 pub use scp::{ScpMode, draw_scp_progress_popup};
-pub use terminal::{TerminalSelection, TerminalState, draw_terminal};
+pub use snippet::draw_snippet_palette;
+pub use terminal::{
+    TerminalAlert, TerminalSelection, TerminalState, draw_terminal, terminal_text_area,
+};
+pub use url_picker::draw_url_picker;
+pub use zmodem_picker::draw_zmodem_picker;
+
+use crate::config::theme::Theme;
 
 /// Helper function to create a rect with only top margin
 ///
@@ -46,7 +59,7 @@ pub fn rect_with_top_margin(rect: ratatui::layout::Rect, top_margin: u16) -> rat
 /// Render normal mode footer with hints and version.
 ///
 /// Layout: 80% hints (left-aligned) + 20% version (right-aligned)
-pub fn render_normal_footer(frame: &mut Frame<'_>, footer_area: Rect, hints: &str) {
+pub fn render_normal_footer(frame: &mut Frame<'_>, footer_area: Rect, hints: &str, theme: &Theme) {
     let footer_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(80), Constraint::Percentage(20)])
@@ -55,7 +68,7 @@ pub fn render_normal_footer(frame: &mut Frame<'_>, footer_area: Rect, hints: &st
     let left = Paragraph::new(Line::from(Span::styled(
         hints,
         Style::default()
-            .fg(Color::White)
+            .fg(theme.footer)
             .add_modifier(Modifier::DIM),
     )))
     .alignment(Alignment::Left);
@@ -63,7 +76,7 @@ pub fn render_normal_footer(frame: &mut Frame<'_>, footer_area: Rect, hints: &st
     let right = Paragraph::new(Line::from(Span::styled(
         format!("TermiRs v{}", env!("CARGO_PKG_VERSION")),
         Style::default()
-            .fg(Color::White)
+            .fg(theme.footer)
             .add_modifier(Modifier::DIM),
     )))
     .alignment(Alignment::Right);
@@ -4,6 +4,8 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use tui_textarea::TextArea;
 
+use crate::config::manager::ConnectionKind;
+use crate::config::theme::Theme;
 use crate::ui::connection::{ConnectionForm, FocusField};
 
 /// Configuration for delete confirmation popups
@@ -60,6 +62,7 @@ pub fn draw_delete_confirmation_popup(
     area: Rect,
     config: &DeleteConfirmationConfig,
     item_name: &str,
+    theme: &Theme,
 ) {
     // Calculate popup dimensions
     let popup_w = (area.width as f32 * config.width_percent) as u16;
@@ -81,10 +84,12 @@ pub fn draw_delete_confirmation_popup(
     // Create bordered block with title
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red))
+        .border_style(Style::default().fg(theme.error))
         .title(Span::styled(
             config.title,
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
         ));
 
     // Calculate inner area for content (just use block inner, no extra margin)
@@ -109,7 +114,7 @@ pub fn draw_delete_confirmation_popup(
         Paragraph::new(warning)
             .style(
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
             )
             .alignment(Alignment::Center),
@@ -118,12 +123,10 @@ pub fn draw_delete_confirmation_popup(
 
     // Render item name
     let item = vec![
-        Span::styled(config.item_label, Style::default().fg(Color::Gray)),
+        Span::styled(config.item_label, Style::default().fg(theme.label)),
         Span::styled(
             item_name,
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
     ];
     frame.render_widget(
@@ -134,7 +137,7 @@ pub fn draw_delete_confirmation_popup(
     // Render confirmation message
     frame.render_widget(
         Paragraph::new("This action cannot be undone.")
-            .style(Style::default().fg(Color::Red))
+            .style(Style::default().fg(theme.error))
             .alignment(Alignment::Center),
         layout[3],
     );
@@ -143,13 +146,15 @@ pub fn draw_delete_confirmation_popup(
     let hints = vec![
         Span::styled(
             "Y",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" - Delete   "),
         Span::styled(
             "N",
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.success)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" - Cancel   "),
@@ -163,8 +168,129 @@ pub fn draw_delete_confirmation_popup(
     frame.render_widget(Paragraph::new("").block(block), popup);
 }
 
+/// Confirmation shown before a multi-line paste is sent to a shell that
+/// hasn't enabled bracketed paste, where each line would run as it arrives
+pub fn draw_paste_confirmation_popup(
+    area: Rect,
+    line_count: usize,
+    frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
+) {
+    let popup_w = (area.width as f32 * 0.45) as u16;
+    let popup_h = 7u16.min(area.height.saturating_sub(2));
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(popup_w)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_h)) / 2,
+        width: popup_w,
+        height: popup_h,
+    };
+
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning))
+        .title(Span::styled(
+            "Paste",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let body = Paragraph::new(vec![
+        Line::from(Span::styled(
+            format!("⚠️  Paste {line_count} lines into the shell?"),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            "Bracketed paste is off, so each line runs as soon as it is sent.",
+            Style::default().fg(theme.text),
+        )),
+        Line::from(Span::raw("")),
+        Line::from(vec![
+            Span::styled(
+                "Y",
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" - Paste   "),
+            Span::styled(
+                "N",
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" - Cancel"),
+        ]),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(ratatui::widgets::Wrap { trim: true })
+    .block(block);
+    frame.render_widget(body, popup);
+}
+
+/// Asks whether closing the terminal should leave its tmux or screen session
+/// running on the host
+pub fn draw_close_session_popup(
+    area: Rect,
+    multiplexer: &str,
+    session_name: &str,
+    frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
+) {
+    let popup_w = (area.width as f32 * 0.45) as u16;
+    let popup_h = 7u16.min(area.height.saturating_sub(2));
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(popup_w)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_h)) / 2,
+        width: popup_w,
+        height: popup_h,
+    };
+
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning))
+        .title(Span::styled(
+            "Close Session",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let key = |label: &'static str, color: Color| {
+        Span::styled(
+            label,
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )
+    };
+    let body = Paragraph::new(vec![
+        Line::from(Span::styled(
+            format!("The terminal runs in {multiplexer} session {session_name}."),
+            Style::default().fg(theme.text),
+        )),
+        Line::from(Span::styled(
+            "Detach to keep it running for the next connection, or kill it.",
+            Style::default().fg(theme.text),
+        )),
+        Line::from(Span::raw("")),
+        Line::from(vec![
+            key("D", theme.success),
+            Span::raw(" - Detach   "),
+            key("K", theme.error),
+            Span::raw(" - Kill   "),
+            key("Esc", theme.muted),
+            Span::raw(" - Cancel"),
+        ]),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(ratatui::widgets::Wrap { trim: true })
+    .block(block);
+    frame.render_widget(body, popup);
+}
+
 // Error popup renderer
-pub fn draw_error_popup(area: Rect, message: &str, frame: &mut ratatui::Frame<'_>) {
+pub fn draw_error_popup(area: Rect, message: &str, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
     let popup_w = (area.width as f32 * 0.45) as u16;
     let inner_w = popup_w.saturating_sub(2).max(1);
     let estimated_lines: u16 = message
@@ -190,18 +316,20 @@ pub fn draw_error_popup(area: Rect, message: &str, frame: &mut ratatui::Frame<'_
         .borders(Borders::ALL)
         .title(Line::from(Span::styled(
             "Error",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
         )));
     let body = Paragraph::new(vec![
         Line::from(Span::styled(
             message.to_string(),
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.error),
         )),
         Line::from(Span::raw("")),
         Line::from(Span::styled(
             "Press Enter or Esc to dismiss",
             Style::default()
-                .fg(Color::White)
+                .fg(theme.footer)
                 .add_modifier(Modifier::DIM),
         )),
     ])
@@ -211,7 +339,7 @@ pub fn draw_error_popup(area: Rect, message: &str, frame: &mut ratatui::Frame<'_
 }
 
 // Info popup renderer
-pub fn draw_info_popup(area: Rect, message: &str, frame: &mut ratatui::Frame<'_>) {
+pub fn draw_info_popup(area: Rect, message: &str, frame: &mut ratatui::Frame<'_>, theme: &Theme) {
     let popup_w = (area.width as f32 * 0.45) as u16;
     let inner_w = popup_w.saturating_sub(2).max(1);
     let estimated_lines: u16 = message
@@ -237,20 +365,18 @@ pub fn draw_info_popup(area: Rect, message: &str, frame: &mut ratatui::Frame<'_>
         .borders(Borders::ALL)
         .title(Line::from(Span::styled(
             "Info",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.info).add_modifier(Modifier::BOLD),
         )));
     let body = Paragraph::new(vec![
         Line::from(Span::styled(
             message.to_string(),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.info),
         )),
         Line::from(Span::raw("")),
         Line::from(Span::styled(
             "Press Enter or Esc to dismiss",
             Style::default()
-                .fg(Color::White)
+                .fg(theme.footer)
                 .add_modifier(Modifier::DIM),
         )),
     ])
@@ -260,7 +386,12 @@ pub fn draw_info_popup(area: Rect, message: &str, frame: &mut ratatui::Frame<'_>
 }
 
 // Connecting popup renderer (shows cancellation hint at bottom)
-pub fn draw_connecting_popup(area: Rect, message: &str, frame: &mut ratatui::Frame<'_>) {
+pub fn draw_connecting_popup(
+    area: Rect,
+    message: &str,
+    frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
+) {
     let popup_w = (area.width as f32 * 0.45) as u16;
     let inner_w = popup_w.saturating_sub(2).max(1);
     let estimated_lines: u16 = message
@@ -287,19 +418,19 @@ pub fn draw_connecting_popup(area: Rect, message: &str, frame: &mut ratatui::Fra
         .title(Line::from(Span::styled(
             "Info",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )));
     let body = Paragraph::new(vec![
         Line::from(Span::styled(
             message.to_string(),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme.title),
         )),
         Line::from(Span::raw("")),
         Line::from(Span::styled(
             "Press ESC to cancel",
             Style::default()
-                .fg(Color::White)
+                .fg(theme.footer)
                 .add_modifier(Modifier::DIM),
         )),
     ])
@@ -313,8 +444,9 @@ pub fn draw_connection_form_popup(
     form: &ConnectionForm,
     new: bool,
     frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
 ) {
-    draw_connection_form_popup_with_mode(area, form, new, frame);
+    draw_connection_form_popup_with_mode(area, form, new, frame, theme);
 }
 
 fn draw_connection_form_popup_with_mode(
@@ -322,11 +454,12 @@ fn draw_connection_form_popup_with_mode(
     form: &ConnectionForm,
     new: bool,
     frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
 ) {
     let title = if new {
-        "New SSH Connection / Import from SSH Config"
+        "New Connection / Import from SSH Config"
     } else {
-        "Edit SSH Connection"
+        "Edit Connection"
     };
 
     // Responsive popup sizing
@@ -351,7 +484,7 @@ fn draw_connection_form_popup_with_mode(
         .title(Line::from(Span::styled(
             title,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )));
 
@@ -360,6 +493,7 @@ fn draw_connection_form_popup_with_mode(
 
     // Create responsive layout based on available space
     let layout = create_responsive_form_layout(inner);
+    render_kind_selector(frame, layout[0], form, theme);
 
     // Helper function to render text areas with responsive styling
     let mut render_textarea = |idx: usize, label: &str, textarea: &TextArea, focused: bool| {
@@ -370,7 +504,7 @@ fn draw_connection_form_popup_with_mode(
         let mut widget = textarea.clone();
         let mut field_block = Block::default().borders(Borders::ALL).title(label);
         if focused {
-            field_block = field_block.border_style(Style::default().fg(Color::Cyan));
+            field_block = field_block.border_style(Style::default().fg(theme.border));
         } else {
             // Hide cursor when not focused
             widget.set_cursor_style(Style::default().bg(Color::Reset));
@@ -380,8 +514,9 @@ fn draw_connection_form_popup_with_mode(
         frame.render_widget(&widget, layout[idx]);
     };
 
-    // Render form fields based on available layout space
-    let field_configs = [
+    // Render form fields based on available layout space; telnet and raw TCP
+    // have no login fields
+    let mut field_configs = vec![
         ("Host", &form.host, form.focus == FocusField::Host),
         ("Port", &form.port, form.focus == FocusField::Port),
         (
@@ -405,19 +540,22 @@ fn draw_connection_form_popup_with_mode(
             form.focus == FocusField::DisplayName,
         ),
     ];
+    if !form.kind.is_ssh() {
+        field_configs.drain(2..5);
+    }
 
     for (idx, (label, textarea, focused)) in field_configs.iter().enumerate() {
-        render_textarea(idx, label, textarea, *focused);
+        render_textarea(idx + 1, label, textarea, *focused);
     }
 
     // Try to show error if space available
-    let error_idx = field_configs.len();
+    let error_idx = field_configs.len() + 1;
     if let Some(error) = &form.error
         && error_idx < layout.len()
     {
         let error_paragraph = Paragraph::new(Line::from(Span::styled(
             error,
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.error),
         )));
         frame.render_widget(error_paragraph, layout[error_idx]);
     }
@@ -433,9 +571,9 @@ fn draw_connection_form_popup_with_mode(
         height: 1,
     };
     let instructions = if new {
-        create_responsive_instructions_with_import(instructions_area.width)
+        create_responsive_instructions_with_import(instructions_area.width, theme)
     } else {
-        create_responsive_instructions(instructions_area.width)
+        create_responsive_instructions(instructions_area.width, theme)
     };
     frame.render_widget(instructions, instructions_area);
 }
@@ -469,12 +607,50 @@ fn calculate_responsive_popup_size(area: Rect) -> (u16, u16) {
         height.saturating_sub(4)
     } else {
         // Normal height: use ideal size
-        23u16.min(height.saturating_sub(4))
+        24u16.min(height.saturating_sub(4))
     };
 
     (popup_w.max(30), popup_h.max(12)) // Ensure minimum usable size
 }
 
+fn render_kind_selector(
+    frame: &mut ratatui::Frame<'_>,
+    area: Rect,
+    form: &ConnectionForm,
+    theme: &Theme,
+) {
+    let focused = form.focus == FocusField::Kind;
+    let highlight = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = vec![Span::styled(
+        "Type: ",
+        if focused { highlight } else { Style::default() },
+    )];
+    for (kind, label) in [
+        (ConnectionKind::Ssh, "SSH  "),
+        (ConnectionKind::Telnet, "Telnet  "),
+        (ConnectionKind::Tcp, "Raw TCP"),
+    ] {
+        let selected = form.kind == kind;
+        spans.push(Span::styled(
+            format!("[{}] {label}", if selected { "✓" } else { " " }),
+            if selected {
+                highlight
+            } else {
+                Style::default()
+            },
+        ));
+    }
+    if focused {
+        spans.push(Span::styled(
+            "  (Space to change)",
+            Style::default().fg(theme.muted),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
 // Create responsive layout based on available space
 fn create_responsive_form_layout(inner: Rect) -> Vec<Rect> {
     let available_height = inner.height;
@@ -484,6 +660,7 @@ fn create_responsive_form_layout(inner: Rect) -> Vec<Rect> {
         Layout::default()
             .direction(Direction::Vertical)
             .constraints([
+                Constraint::Length(1), // type
                 Constraint::Length(2), // host
                 Constraint::Length(2), // port
                 Constraint::Length(2), // username
@@ -498,6 +675,7 @@ fn create_responsive_form_layout(inner: Rect) -> Vec<Rect> {
         Layout::default()
             .direction(Direction::Vertical)
             .constraints([
+                Constraint::Length(1), // type
                 Constraint::Length(2), // host
                 Constraint::Length(2), // port
                 Constraint::Length(2), // username
@@ -513,6 +691,7 @@ fn create_responsive_form_layout(inner: Rect) -> Vec<Rect> {
         Layout::default()
             .direction(Direction::Vertical)
             .constraints([
+                Constraint::Length(1), // type
                 Constraint::Length(3), // host
                 Constraint::Length(3), // port
                 Constraint::Length(3), // username
@@ -529,27 +708,29 @@ fn create_responsive_form_layout(inner: Rect) -> Vec<Rect> {
 }
 
 // Create responsive instructions based on available width
-fn create_responsive_instructions(width: u16) -> Paragraph<'static> {
+fn create_responsive_instructions(width: u16, theme: &Theme) -> Paragraph<'static> {
     if width < 40 {
         // Very narrow: minimal instructions
         Paragraph::new(Line::from(vec![
             Span::styled(
                 "Tab",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" "),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" "),
             Span::styled(
                 "Esc",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
         ]))
     } else if width < 60 {
@@ -558,20 +739,22 @@ fn create_responsive_instructions(width: u16) -> Paragraph<'static> {
             Span::styled(
                 "Tab",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Nav  "),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Save  "),
             Span::styled(
                 "Esc",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Cancel"),
         ]))
@@ -581,47 +764,51 @@ fn create_responsive_instructions(width: u16) -> Paragraph<'static> {
             Span::styled(
                 "Tab/Shift+Tab",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Navigate  "),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Save  "),
             Span::styled(
                 "Esc",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Cancel"),
         ]))
     }
 }
 
-fn create_responsive_instructions_with_import(width: u16) -> Paragraph<'static> {
+fn create_responsive_instructions_with_import(width: u16, theme: &Theme) -> Paragraph<'static> {
     if width < 50 {
         // Very narrow: minimal instructions
         Paragraph::new(Line::from(vec![
             Span::styled(
                 "Ctrl+L",
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" "),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" "),
             Span::styled(
                 "Esc",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
         ]))
     } else if width < 80 {
@@ -630,20 +817,22 @@ fn create_responsive_instructions_with_import(width: u16) -> Paragraph<'static>
             Span::styled(
                 "Ctrl+L",
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Load  "),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Save  "),
             Span::styled(
                 "Esc",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Cancel"),
         ]))
@@ -653,27 +842,29 @@ fn create_responsive_instructions_with_import(width: u16) -> Paragraph<'static>
             Span::styled(
                 "Ctrl+L",
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Load from SSH Config  "),
             Span::styled(
                 "Tab",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Navigate  "),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Save  "),
             Span::styled(
                 "Esc",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Cancel"),
         ]))
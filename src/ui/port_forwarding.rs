@@ -6,6 +6,7 @@ use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row};
 use tui_textarea::TextArea;
 
 use crate::config::manager::{Connection, PortForward, PortForwardStatus, PortForwardType};
+use crate::config::theme::Theme;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FocusField {
@@ -335,7 +336,9 @@ pub struct PortForwardingListItem<'a> {
 }
 
 /// Table component implementation for PortForwardingList
-pub struct PortForwardingTableComponent;
+pub struct PortForwardingTableComponent {
+    theme: Theme,
+}
 
 impl super::table::TableListComponent<7> for PortForwardingTableComponent {
     type Item<'a> = PortForwardingListItem<'a>;
@@ -364,10 +367,10 @@ impl super::table::TableListComponent<7> for PortForwardingTableComponent {
         use ratatui::text::Span;
 
         let status_color = match item.status_icon {
-            "●" => Color::Green,
-            "○" => Color::Gray,
-            "✗" => Color::Red,
-            _ => Color::White,
+            "●" => self.theme.success,
+            "○" => self.theme.label,
+            "✗" => self.theme.error,
+            _ => self.theme.text,
         };
 
         Row::new(vec![
@@ -406,6 +409,7 @@ pub fn draw_port_forwarding_list(
     selected_index: usize,
     search: &crate::SearchState,
     frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
 ) {
     // Build the list items
     let items: Vec<PortForwardingListItem> = port_forwards
@@ -446,13 +450,21 @@ pub fn draw_port_forwarding_list(
         .collect();
 
     // Create the component
-    let component = PortForwardingTableComponent;
+    let component = PortForwardingTableComponent { theme: *theme };
 
     // Create state from current values
     let state = super::table::TableListState::from_parts(selected_index, search.clone());
 
     // Use the generic table renderer
-    super::table_renderer::draw_table_list(area, &component, items, &state, frame, "Port Forwards");
+    super::table_renderer::draw_table_list(
+        area,
+        &component,
+        items,
+        &state,
+        frame,
+        "Port Forwards",
+        theme,
+    );
 }
 
 pub fn draw_port_forwarding_form_popup(
@@ -461,6 +473,7 @@ pub fn draw_port_forwarding_form_popup(
     connections: &[Connection],
     is_new: bool,
     frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
 ) -> Rect {
     let title = if is_new {
         "New Port Forward"
@@ -490,7 +503,7 @@ pub fn draw_port_forwarding_form_popup(
         .title(Line::from(Span::styled(
             title,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )));
 
@@ -605,13 +618,13 @@ pub fn draw_port_forwarding_form_popup(
 
         if *field_type == FocusField::ForwardType {
             // Render forward type selector
-            render_forward_type_selector(frame, layout[idx], form, *focused);
+            render_forward_type_selector(frame, layout[idx], form, *focused, theme);
         } else if let Some(textarea) = textarea_opt {
             // Render text area field
             let mut widget = (*textarea).clone();
             let mut field_block = Block::default().borders(Borders::ALL).title(*label);
             if *focused {
-                field_block = field_block.border_style(Style::default().fg(Color::Cyan));
+                field_block = field_block.border_style(Style::default().fg(theme.border));
             } else {
                 // Hide cursor when not focused
                 widget.set_cursor_style(Style::default().bg(Color::Reset));
@@ -630,19 +643,19 @@ pub fn draw_port_forwarding_form_popup(
             let mut field_block = Block::default().borders(Borders::ALL).title("Connection");
 
             if *focused {
-                field_block = field_block.border_style(Style::default().fg(Color::Cyan));
+                field_block = field_block.border_style(Style::default().fg(theme.border));
             }
 
             let connection_paragraph = Paragraph::new(Line::from(Span::styled(
                 connection_text,
                 if *focused {
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.title)
                         .add_modifier(Modifier::BOLD)
                 } else if !form.connection_id.is_empty() {
                     Style::default()
                 } else {
-                    Style::default().fg(Color::DarkGray)
+                    Style::default().fg(theme.muted)
                 },
             )))
             .block(field_block);
@@ -658,7 +671,7 @@ pub fn draw_port_forwarding_form_popup(
     {
         let error_paragraph = Paragraph::new(Line::from(Span::styled(
             error,
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.error),
         )));
         frame.render_widget(error_paragraph, layout[error_idx]);
     }
@@ -673,7 +686,7 @@ pub fn draw_port_forwarding_form_popup(
         width: popup.width.saturating_sub(4),
         height: 1,
     };
-    let instructions = create_responsive_instructions(instructions_area.width);
+    let instructions = create_responsive_instructions(instructions_area.width, theme);
     frame.render_widget(instructions, instructions_area);
 
     // Return the Connection field position (first field, index 0)
@@ -727,6 +740,7 @@ fn render_forward_type_selector(
     area: Rect,
     form: &PortForwardingForm,
     focused: bool,
+    theme: &Theme,
 ) {
     let marker_fn = |typ: PortForwardType| {
         if form.forward_type == typ { "✓" } else { " " }
@@ -737,7 +751,7 @@ fn render_forward_type_selector(
             "Forward Type: ",
             if focused {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
@@ -747,7 +761,7 @@ fn render_forward_type_selector(
             format!("[{}] Local →  ", marker_fn(PortForwardType::Local)),
             if matches!(form.forward_type, PortForwardType::Local) {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
@@ -757,7 +771,7 @@ fn render_forward_type_selector(
             format!("[{}] Remote ←  ", marker_fn(PortForwardType::Remote)),
             if matches!(form.forward_type, PortForwardType::Remote) {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
@@ -767,7 +781,7 @@ fn render_forward_type_selector(
             format!("[{}] Dynamic ⇄", marker_fn(PortForwardType::Dynamic)),
             if matches!(form.forward_type, PortForwardType::Dynamic) {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
@@ -822,27 +836,29 @@ fn create_responsive_form_layout_with_count(inner: Rect, field_count: usize) ->
 }
 
 // Create responsive instructions based on available width
-fn create_responsive_instructions(width: u16) -> Paragraph<'static> {
+fn create_responsive_instructions(width: u16, theme: &Theme) -> Paragraph<'static> {
     if width < 40 {
         // Very narrow: minimal instructions
         Paragraph::new(Line::from(vec![
             Span::styled(
                 "Tab",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" "),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" "),
             Span::styled(
                 "Esc",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
         ]))
     } else if width < 60 {
@@ -851,20 +867,22 @@ fn create_responsive_instructions(width: u16) -> Paragraph<'static> {
             Span::styled(
                 "Tab",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Nav  "),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Save  "),
             Span::styled(
                 "Esc",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Cancel"),
         ]))
@@ -874,20 +892,22 @@ fn create_responsive_instructions(width: u16) -> Paragraph<'static> {
             Span::styled(
                 "Tab/Shift+Tab",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Navigate  "),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Save  "),
             Span::styled(
                 "Esc",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::raw(": Cancel"),
         ]))
@@ -5,6 +5,7 @@ use ratatui::widgets::{Block, Borders, Clear, Gauge, Paragraph};
 use std::borrow::Cow;
 
 use crate::ScpProgress;
+use crate::config::theme::Theme;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ScpMode {
@@ -17,6 +18,7 @@ pub fn draw_scp_progress_popup(
     area: Rect,
     progress: &mut ScpProgress,
     frame: &mut ratatui::Frame<'_>,
+    theme: &Theme,
 ) {
     let file_count = progress.files.len();
     let popup_w = (area.width as f32 * 0.45) as u16;
@@ -84,9 +86,9 @@ pub fn draw_scp_progress_popup(
     let outer = Block::default()
         .borders(Borders::ALL)
         .title(Line::from(Span::styled(
-            "SFTP Transfers",
+            format!("{} Transfers", progress.protocol),
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         )));
     frame.render_widget(outer, popup);
@@ -115,8 +117,8 @@ pub fn draw_scp_progress_popup(
 
     // Connection info
     let connection_info = Paragraph::new(Line::from(vec![
-        Span::styled("Connection: ", Style::default().fg(Color::Gray)),
-        Span::styled(&progress.connection_name, Style::default().fg(Color::Cyan)),
+        Span::styled("Connection: ", Style::default().fg(theme.label)),
+        Span::styled(&progress.connection_name, Style::default().fg(theme.title)),
     ]));
     frame.render_widget(connection_info, layout[layout_idx]);
     layout_idx += 1;
@@ -126,7 +128,7 @@ pub fn draw_scp_progress_popup(
         let above_count = progress.scroll_offset;
         let indicator = Paragraph::new(Line::from(Span::styled(
             format!("  ... {above_count} more above ..."),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
         frame.render_widget(indicator, layout[layout_idx]);
         layout_idx += 1;
@@ -155,29 +157,31 @@ pub fn draw_scp_progress_popup(
 
         let (status_label, status_style) = match &file.state {
             crate::TransferState::Pending => {
-                (Cow::Borrowed("Pending"), Style::default().fg(Color::Gray))
+                (Cow::Borrowed("Pending"), Style::default().fg(theme.label))
             }
             crate::TransferState::InProgress => (
                 Cow::Borrowed("In Progress"),
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.progress)
                     .add_modifier(Modifier::BOLD),
             ),
             crate::TransferState::Completed => (
                 Cow::Borrowed("Completed"),
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             crate::TransferState::Failed(err) => (
                 Cow::Owned(format!("Failed ({err})")),
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
             ),
         };
 
         let header = Paragraph::new(Line::from(vec![
             Span::styled(format!("{status_label:<12}"), status_style),
-            Span::styled(&file.display_name, Style::default().fg(Color::White)),
+            Span::styled(&file.display_name, Style::default().fg(theme.text)),
         ]));
         frame.render_widget(header, file_chunks[0]);
 
@@ -186,13 +190,13 @@ pub fn draw_scp_progress_popup(
             ScpMode::Receive => (&file.remote_path, &file.local_path),
         };
         let from_line = Paragraph::new(Line::from(vec![
-            Span::styled("From: ", Style::default().fg(Color::Gray)),
-            Span::styled(from_path.as_str(), Style::default().fg(Color::White)),
+            Span::styled("From: ", Style::default().fg(theme.label)),
+            Span::styled(from_path.as_str(), Style::default().fg(theme.text)),
         ]));
         frame.render_widget(from_line, file_chunks[1]);
         let to_line = Paragraph::new(Line::from(vec![
-            Span::styled("  To: ", Style::default().fg(Color::Gray)),
-            Span::styled(to_path.as_str(), Style::default().fg(Color::White)),
+            Span::styled("  To: ", Style::default().fg(theme.label)),
+            Span::styled(to_path.as_str(), Style::default().fg(theme.text)),
         ]));
         frame.render_widget(to_line, file_chunks[2]);
 
@@ -205,20 +209,20 @@ pub fn draw_scp_progress_popup(
                     format_bytes(file.transferred_bytes),
                     format_bytes(total)
                 ),
-                Style::default().fg(Color::White),
+                Style::default().fg(theme.text),
             )
         } else {
             Span::styled(
                 format!("{} transferred", format_bytes(file.transferred_bytes)),
-                Style::default().fg(Color::White),
+                Style::default().fg(theme.text),
             )
         };
 
         let gauge_color = match file.state {
             crate::TransferState::Pending
             | crate::TransferState::InProgress
-            | crate::TransferState::Completed => Color::Cyan,
-            crate::TransferState::Failed(_) => Color::Red,
+            | crate::TransferState::Completed => theme.progress,
+            crate::TransferState::Failed(_) => theme.error,
         };
 
         let gauge = Gauge::default()
@@ -239,7 +243,7 @@ pub fn draw_scp_progress_popup(
         let below_count = file_count - (progress.scroll_offset + visible_count);
         let indicator = Paragraph::new(Line::from(Span::styled(
             format!("  ... {below_count} more below ..."),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
         frame.render_widget(indicator, layout[layout_idx]);
         layout_idx += 1;
@@ -285,7 +289,7 @@ pub fn draw_scp_progress_popup(
         }
         let time_info = Paragraph::new(Line::from(Span::styled(
             elapsed_text,
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme.label),
         )));
         frame.render_widget(time_info, time_area);
     }
@@ -0,0 +1,132 @@
+//! Snippet palette overlay for the connected terminal.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::config::theme::Theme;
+use crate::snippet::SnippetPalette;
+
+/// Draw the snippet palette: a filterable list, or the placeholder prompt
+/// once a snippet with placeholders has been chosen
+pub fn draw_snippet_palette(f: &mut Frame, area: Rect, palette: &SnippetPalette, theme: &Theme) {
+    let filtered = palette.filtered();
+
+    let popup_w = (area.width as f32 * 0.6) as u16;
+    let popup_h = (filtered.len().max(1) as u16 + 5).min(area.height.saturating_sub(4));
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(popup_w)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_h)) / 2,
+        width: popup_w,
+        height: popup_h,
+    };
+
+    f.render_widget(Clear, popup);
+    let title = match &palette.prompt {
+        Some(prompt) => format!(" {} ", prompt.snippet_name),
+        None => " Snippets ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(Span::styled(
+            title,
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )));
+    f.render_widget(block, popup);
+
+    let inner = popup.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Input line: the search query, or the value for the current placeholder
+    let input_line = match &palette.prompt {
+        Some(prompt) => {
+            let (current, total) = prompt.progress();
+            Line::from(vec![
+                Span::styled(
+                    format!("{} ({current}/{total}): ", prompt.current_name()),
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!("{}_", prompt.input)),
+            ])
+        }
+        None => {
+            let mut spans = vec![Span::styled(
+                "Search: ",
+                Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::BOLD),
+            )];
+            if palette.query.is_empty() {
+                spans.push(Span::styled(
+                    "Type to filter snippets",
+                    Style::default().fg(theme.muted).dim(),
+                ));
+            } else {
+                spans.push(Span::raw(format!("{}_", palette.query)));
+            }
+            Line::from(spans)
+        }
+    };
+    f.render_widget(Paragraph::new(input_line), sections[0]);
+
+    let items: Vec<ListItem> = filtered
+        .iter()
+        .filter_map(|&idx| palette.snippets().get(idx))
+        .map(|snippet| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    snippet.name.clone(),
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  "),
+                Span::styled(snippet.command.clone(), Style::default().fg(theme.muted)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    let mut list_state = ListState::default();
+    if !filtered.is_empty() {
+        list_state.select(Some(palette.selected.min(filtered.len() - 1)));
+    }
+    f.render_stateful_widget(list, sections[1], &mut list_state);
+
+    let hint = if palette.prompt.is_some() {
+        "Enter: Next | Esc: Back"
+    } else {
+        "↑↓: Navigate | Enter: Insert | Esc: Close"
+    };
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            hint,
+            Style::default()
+                .fg(theme.footer)
+                .add_modifier(Modifier::DIM),
+        )),
+        sections[2],
+    );
+}
@@ -4,6 +4,7 @@
 /// including smart scrollbar positioning and three-state footer rendering.
 use super::table::{TableListComponent, TableListState};
 use crate::SearchState;
+use crate::config::theme::Theme;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Margin, Rect},
@@ -31,6 +32,7 @@ use ratatui::{
 /// * `state` - The table list state (selection + search)
 /// * `frame` - The ratatui frame to render to
 /// * `title` - The base title for the table (e.g., "Connection List")
+/// * `theme` - Interface colours
 ///
 /// # Example
 /// ```ignore
@@ -50,6 +52,7 @@ use ratatui::{
 ///     &state,
 ///     frame,
 ///     "Connection List",
+///     &theme,
 /// );
 /// ```
 pub fn draw_table_list<'a, T, const N: usize>(
@@ -59,6 +62,7 @@ pub fn draw_table_list<'a, T, const N: usize>(
     state: &TableListState,
     frame: &mut Frame<'_>,
     title: &str,
+    theme: &Theme,
 ) where
     T: TableListComponent<N> + 'a,
 {
@@ -105,7 +109,7 @@ pub fn draw_table_list<'a, T, const N: usize>(
     )
     .style(
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme.title)
             .add_modifier(Modifier::BOLD),
     )
     .height(1);
@@ -125,8 +129,8 @@ pub fn draw_table_list<'a, T, const N: usize>(
     )))
     .row_highlight_style(
         Style::default()
-            .bg(Color::Cyan)
-            .fg(Color::White)
+            .bg(theme.selection_bg)
+            .fg(theme.selection_fg)
             .add_modifier(Modifier::BOLD),
     )
     .highlight_symbol("▶ ");
@@ -139,7 +143,13 @@ pub fn draw_table_list<'a, T, const N: usize>(
     render_scrollbar(frame, layout[0], sel, rows_len);
 
     // Render footer based on search state
-    render_footer(frame, layout[1], &state.search, component.footer_hints());
+    render_footer(
+        frame,
+        layout[1],
+        &state.search,
+        component.footer_hints(),
+        theme,
+    );
 }
 
 /// Render vertical scrollbar only if content exceeds visible rows.
@@ -195,20 +205,27 @@ fn render_scrollbar(frame: &mut Frame<'_>, table_area: Rect, selected: usize, to
 /// * `footer_area` - The area for the footer
 /// * `search` - Current search state
 /// * `hints` - Keyboard hints to display in normal mode
-fn render_footer(frame: &mut Frame<'_>, footer_area: Rect, search: &SearchState, hints: &str) {
+/// * `theme` - Interface colours
+fn render_footer(
+    frame: &mut Frame<'_>,
+    footer_area: Rect,
+    search: &SearchState,
+    hints: &str,
+    theme: &Theme,
+) {
     if search.is_on() {
         // Search mode: show search input with placeholder
         let mut spans = vec![Span::styled(
             "Search: ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )];
 
         if search.query().is_empty() {
             spans.push(Span::styled(
                 "Type to filter items",
-                Style::default().fg(Color::DarkGray).dim(),
+                Style::default().fg(theme.muted).dim(),
             ));
         } else {
             spans.push(Span::raw(search.query()));
@@ -223,19 +240,19 @@ fn render_footer(frame: &mut Frame<'_>, footer_area: Rect, search: &SearchState,
             Span::styled(
                 "Searched: ",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(search.query(), Style::default().fg(Color::Yellow)),
+            Span::styled(search.query(), Style::default().fg(theme.warning)),
             Span::styled(
                 "   Press Esc to clear",
-                Style::default().fg(Color::DarkGray).dim(),
+                Style::default().fg(theme.muted).dim(),
             ),
         ]))
         .style(Style::default().bg(Color::Reset));
         frame.render_widget(search_line, footer_area);
     } else {
         // Normal mode: show hints + version
-        super::render_normal_footer(frame, footer_area, hints);
+        super::render_normal_footer(frame, footer_area, hints, theme);
     }
 }
@@ -1,7 +1,7 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::Hasher;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use grep_matcher::Matcher;
 use grep_regex::RegexMatcherBuilder;
@@ -13,21 +13,47 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Widget};
 use wezterm_surface::CursorVisibility;
-use wezterm_term::color::{ColorAttribute, ColorPalette};
+use wezterm_term::color::{ColorAttribute, ColorPalette, SrgbaTuple};
 use wezterm_term::config::TerminalConfiguration;
-use wezterm_term::{Intensity, Terminal as WezTerminal, TerminalSize, Underline};
+use wezterm_term::{
+    Alert, AlertHandler, Intensity, KeyModifiers as WezModifiers, MouseButton as WezMouseButton,
+    MouseEvent as WezMouseEvent, MouseEventKind as WezMouseEventKind, SemanticType, StableRowIndex,
+    Terminal as WezTerminal, TerminalSize, Underline,
+};
 
+use crate::config::color_scheme::{ColorScheme, Rgb};
 use crate::config::manager::DEFAULT_TERMINAL_SCROLLBACK_LINES;
+use crate::config::theme::Theme;
+use crate::terminal::selection::rev_to_view_row;
+use crate::terminal::{
+    CommandDone, CopyMode, ExportFormat, ExportScope, IdleWatch, PrivateModeScanner, SessionLogger,
+    SessionRecorder, TerminalLink, VisualMode, find_urls, recording_path,
+};
 
 /// Simple configuration for the wezterm terminal
 #[derive(Debug)]
 struct SimpleConfig {
     scrollback_size: usize,
+    color_scheme: Option<ColorScheme>,
 }
 
 impl TerminalConfiguration for SimpleConfig {
+    /// The palette answers OSC 4/10/11 colour queries, so programs that
+    /// probe for a dark or light background see the configured scheme
     fn color_palette(&self) -> ColorPalette {
-        ColorPalette::default()
+        let mut palette = ColorPalette::default();
+        if let Some(scheme) = &self.color_scheme {
+            for (idx, color) in scheme.ansi.iter().enumerate() {
+                palette.colors.0[idx] = srgba(*color);
+            }
+            palette.foreground = srgba(scheme.foreground);
+            palette.background = srgba(scheme.background);
+            palette.cursor_fg = srgba(scheme.background);
+            palette.cursor_bg = srgba(scheme.cursor);
+            palette.cursor_border = srgba(scheme.cursor);
+            palette.selection_bg = srgba(scheme.selection);
+        }
+        palette
     }
 
     fn scrollback_size(&self) -> usize {
@@ -35,6 +61,77 @@ impl TerminalConfiguration for SimpleConfig {
     }
 }
 
+fn srgba(rgb: Rgb) -> SrgbaTuple {
+    SrgbaTuple(
+        rgb.0 as f32 / 255.0,
+        rgb.1 as f32 / 255.0,
+        rgb.2 as f32 / 255.0,
+        1.0,
+    )
+}
+
+/// Collects what the emulator writes back to the host: answers to device
+/// attribute queries, cursor position reports, DECRQM and OSC colour queries
+struct ReplyWriter(tokio::sync::mpsc::UnboundedSender<Vec<u8>>);
+
+impl std::io::Write for ReplyWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // Nobody listening (e.g. in tests) just means the reply is dropped
+        let _ = self.0.send(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Forwards OSC 52 clipboard writes decoded by wezterm to the app
+#[cfg(feature = "anyhow")]
+struct ClipboardForwarder(tokio::sync::mpsc::UnboundedSender<String>);
+
+#[cfg(feature = "anyhow")]
+impl wezterm_term::Clipboard for ClipboardForwarder {
+    fn set_contents(
+        &self,
+        _selection: wezterm_term::ClipboardSelection,
+        data: Option<String>,
+    ) -> anyhow::Result<()> {
+        // `None` or empty text clears the clipboard; there's nothing to copy
+        if let Some(text) = data.filter(|text| !text.is_empty()) {
+            let _ = self.0.send(text);
+        }
+        Ok(())
+    }
+}
+
+/// How long the title bar flashes when the remote side rings the bell
+pub const BELL_FLASH_DURATION: Duration = Duration::from_millis(200);
+
+/// Collects wezterm's alerts while bytes are being processed; they are
+/// drained right afterwards by [`TerminalState::process_bytes`]
+struct AlertCollector(std::sync::mpsc::Sender<Alert>);
+
+impl AlertHandler for AlertCollector {
+    fn alert(&mut self, alert: Alert) {
+        let _ = self.0.send(alert);
+    }
+}
+
+/// Alert from the remote side that the app has to act on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TerminalAlert {
+    /// BEL was received
+    Bell,
+    /// OSC 9 or OSC 777 desktop notification
+    Notification { title: Option<String>, body: String },
+    /// `sz` or `rz` started a ZMODEM transfer; output is held back until
+    /// [`TerminalState::finish_zmodem`]
+    Zmodem(crate::zmodem::Direction),
+    /// A command finished while "notify when idle" was on; the bell rang too
+    CommandDone(CommandDone),
+}
+
 /// Represents a single search match position in the terminal
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SearchMatch {
@@ -43,6 +140,34 @@ pub struct SearchMatch {
     pub end_col: u16,   // End column (exclusive)
 }
 
+/// Number of confirmed queries kept for recall with Up/Down
+const SEARCH_HISTORY_LIMIT: usize = 50;
+
+/// How a search query is matched
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Treat the query as a regex instead of literal text
+    pub regex: bool,
+    /// Always match case; otherwise case is ignored unless the query has uppercase
+    pub case_sensitive: bool,
+    /// Only match whole words
+    pub whole_word: bool,
+}
+
+impl SearchOptions {
+    /// Short labels of the enabled options, for the search title
+    pub fn labels(&self) -> Vec<&'static str> {
+        [
+            (self.regex, "regex"),
+            (self.case_sensitive, "case"),
+            (self.whole_word, "word"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, label)| enabled.then_some(label))
+        .collect()
+    }
+}
+
 /// Search state for terminal content
 #[derive(Clone)]
 pub struct TerminalSearch {
@@ -53,8 +178,12 @@ pub struct TerminalSearch {
     pub current_idx: usize,
     pub max_scrollback: usize, // cached max scrollback for coordinate conversion
     pub needs_initial_scroll: bool, // true when search is confirmed and needs auto-scroll check
+    pub options: SearchOptions,
+    pub error: Option<String>, // why the query could not be compiled
     dirty: bool,
     last_query: String,
+    history: Vec<String>, // confirmed queries, oldest first; kept across searches
+    history_idx: Option<usize>, // entry being recalled with Up/Down
 }
 
 impl Default for TerminalSearch {
@@ -73,8 +202,12 @@ impl TerminalSearch {
             current_idx: 0,
             max_scrollback: 0,
             needs_initial_scroll: false,
+            options: SearchOptions::default(),
+            error: None,
             dirty: true,
             last_query: String::new(),
+            history: Vec::new(),
+            history_idx: None,
         }
     }
 
@@ -86,8 +219,10 @@ impl TerminalSearch {
         self.matches.clear();
         self.current_idx = 0;
         self.needs_initial_scroll = false;
+        self.error = None;
         self.dirty = true;
         self.last_query.clear();
+        self.history_idx = None;
     }
 
     /// Exit search mode completely
@@ -98,14 +233,23 @@ impl TerminalSearch {
         self.matches.clear();
         self.current_idx = 0;
         self.needs_initial_scroll = false;
+        self.error = None;
         self.dirty = true;
         self.last_query.clear();
+        self.history_idx = None;
     }
 
     /// Confirm query and enter navigation mode
     pub fn confirm(&mut self) {
         self.inputting = false;
         self.needs_initial_scroll = true;
+        self.history_idx = None;
+        if !self.query.is_empty() && self.history.last() != Some(&self.query) {
+            self.history.push(self.query.clone());
+            if self.history.len() > SEARCH_HISTORY_LIMIT {
+                self.history.remove(0);
+            }
+        }
     }
 
     /// Go back to input mode (e.g., to edit query)
@@ -184,32 +328,82 @@ impl TerminalSearch {
             self.dirty = true;
         }
     }
+
+    /// Replace the query with the previous entry in the search history
+    pub fn history_prev(&mut self) {
+        let idx = match self.history_idx {
+            Some(idx) => idx.saturating_sub(1),
+            None if !self.history.is_empty() => self.history.len() - 1,
+            None => return,
+        };
+        self.history_idx = Some(idx);
+        self.set_query(self.history[idx].clone());
+    }
+
+    /// Replace the query with the next entry in the search history, or
+    /// clear it after the most recent one
+    pub fn history_next(&mut self) {
+        let Some(idx) = self.history_idx else {
+            return;
+        };
+        if idx + 1 < self.history.len() {
+            self.history_idx = Some(idx + 1);
+            self.set_query(self.history[idx + 1].clone());
+        } else {
+            self.history_idx = None;
+            self.set_query(String::new());
+        }
+    }
+
+    pub fn toggle_regex(&mut self) {
+        self.options.regex = !self.options.regex;
+        self.dirty = true;
+    }
+
+    pub fn toggle_case_sensitive(&mut self) {
+        self.options.case_sensitive = !self.options.case_sensitive;
+        self.dirty = true;
+    }
+
+    pub fn toggle_whole_word(&mut self) {
+        self.options.whole_word = !self.options.whole_word;
+        self.dirty = true;
+    }
 }
 
 /// Find all matches of a pattern in multi-line text.
 ///
 /// Returns a vector of (line_index, start_col, end_col) for each match.
-/// Uses smart case matching (case-insensitive if pattern is all lowercase).
+/// Uses smart case matching (case-insensitive if pattern is all lowercase)
+/// unless `options.case_sensitive` is set.
 ///
 /// # Arguments
 /// * `text` - The multi-line text to search in
-/// * `pattern` - The regex pattern to search for
+/// * `pattern` - The literal text or regex pattern to search for
+/// * `options` - How to match the pattern
 ///
 /// # Returns
-/// A vector of (line_index, start_col, end_col) tuples where line_index is 0-based
-pub fn find_matches_in_text(text: &str, pattern: &str) -> Vec<(usize, u16, u16)> {
-    if pattern.is_empty() || text.is_empty() {
-        return Vec::new();
-    }
-
-    let matcher = match RegexMatcherBuilder::new()
-        .case_smart(true)
+/// A vector of (line_index, start_col, end_col) tuples where line_index is 0-based,
+/// or the error message if the pattern is not a valid regex
+pub fn find_matches_in_text(
+    text: &str,
+    pattern: &str,
+    options: SearchOptions,
+) -> Result<Vec<(usize, u16, u16)>, String> {
+    if pattern.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let matcher = RegexMatcherBuilder::new()
+        .case_smart(!options.case_sensitive)
+        .fixed_strings(!options.regex)
+        .word(options.whole_word)
         .line_terminator(Some(b'\n'))
         .build(pattern)
-    {
-        Ok(m) => m,
-        Err(_) => return Vec::new(),
-    };
+        .map_err(|e| e.to_string())?;
+    if text.is_empty() {
+        return Ok(Vec::new());
+    }
 
     let mut matches = Vec::new();
     let _ = Searcher::new().search_slice(
@@ -232,7 +426,7 @@ pub fn find_matches_in_text(text: &str, pattern: &str) -> Vec<(usize, u16, u16)>
         }),
     );
 
-    matches
+    Ok(matches)
 }
 
 pub struct TerminalState {
@@ -245,8 +439,39 @@ pub struct TerminalState {
     cached_width: u16,
     cache_invalidated: bool,
     scrollback_limit: usize,
+    /// Colours to render with; `None` passes the host terminal's colours through
+    color_scheme: Option<ColorScheme>,
     /// Current scrollback offset (0 = at bottom, positive = scrolled up)
     scrollback_offset: usize,
+    replies: Option<tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>>,
+    modes: PrivateModeScanner,
+    clipboard_requests: Option<tokio::sync::mpsc::UnboundedReceiver<String>>,
+    wez_alerts: std::sync::mpsc::Receiver<Alert>,
+    alert_tx: tokio::sync::mpsc::UnboundedSender<TerminalAlert>,
+    alerts: Option<tokio::sync::mpsc::UnboundedReceiver<TerminalAlert>>,
+    /// Title set by the remote side with OSC 0/2
+    window_title: Option<String>,
+    /// When the bell last rang, for the visual bell flash
+    bell_at: Option<Instant>,
+    /// The bell rang and the user hasn't typed anything since
+    bell_pending: bool,
+    /// Link under the mouse pointer, drawn underlined
+    hovered_link: Option<TerminalLink>,
+    /// Asciicast recording of the session's output and resizes
+    recorder: Option<SessionRecorder>,
+    /// Plain-text log of the session's output
+    logger: Option<SessionLogger>,
+    /// Diverts output to a ZMODEM transfer while one runs
+    zmodem: crate::zmodem::Tap,
+    /// Set while "notify when idle" is on
+    idle_watch: Option<IdleWatch>,
+    /// The shell marks its prompts with OSC 133, which wezterm keeps as
+    /// semantic zones
+    shell_integration: bool,
+    /// A column is kept left of the text for the command gutter
+    prompt_gutter: bool,
+    /// Commands found from the prompt marks, until the next output
+    commands: Option<Vec<ShellCommand>>,
 }
 
 impl TerminalState {
@@ -255,6 +480,15 @@ impl TerminalState {
     }
 
     pub fn new_with_scrollback(rows: u16, cols: u16, scrollback_limit: usize) -> Self {
+        Self::new_with_color_scheme(rows, cols, scrollback_limit, None)
+    }
+
+    pub fn new_with_color_scheme(
+        rows: u16,
+        cols: u16,
+        scrollback_limit: usize,
+        color_scheme: Option<ColorScheme>,
+    ) -> Self {
         let limit = scrollback_limit.max(1);
         let size = TerminalSize {
             rows: rows as usize,
@@ -265,8 +499,23 @@ impl TerminalState {
         };
         let config = Arc::new(SimpleConfig {
             scrollback_size: limit,
+            color_scheme,
         });
-        let terminal = WezTerminal::new(size, config, "termirs", "0.1", Box::new(std::io::sink()));
+        let (reply_tx, replies) = tokio::sync::mpsc::unbounded_channel();
+        #[cfg_attr(not(feature = "anyhow"), allow(unused_variables))]
+        let (clipboard_tx, clipboard_requests) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let (alert_tx, alerts) = tokio::sync::mpsc::unbounded_channel();
+        let (wez_alert_tx, wez_alerts) = std::sync::mpsc::channel();
+        let mut terminal = WezTerminal::new(
+            size,
+            config,
+            "termirs",
+            "0.1",
+            Box::new(ReplyWriter(reply_tx)),
+        );
+        terminal.set_notification_handler(Box::new(AlertCollector(wez_alert_tx)));
+        #[cfg(feature = "anyhow")]
+        terminal.set_clipboard(&Arc::new(ClipboardForwarder(clipboard_tx)));
 
         Self {
             terminal,
@@ -278,7 +527,25 @@ impl TerminalState {
             cached_width: 0,
             cache_invalidated: true,
             scrollback_limit: limit,
+            color_scheme,
             scrollback_offset: 0,
+            replies: Some(replies),
+            modes: PrivateModeScanner::new(),
+            clipboard_requests: Some(clipboard_requests),
+            wez_alerts,
+            alert_tx,
+            alerts: Some(alerts),
+            window_title: None,
+            bell_at: None,
+            bell_pending: false,
+            hovered_link: None,
+            recorder: None,
+            logger: None,
+            zmodem: crate::zmodem::Tap::default(),
+            idle_watch: None,
+            shell_integration: false,
+            prompt_gutter: false,
+            commands: None,
         }
     }
 
@@ -292,16 +559,445 @@ impl TerminalState {
         };
         self.terminal.resize(size);
         self.last_change = Instant::now();
+        self.commands = None;
         self.invalidate_cache();
+        if let Some(recorder) = &mut self.recorder
+            && let Err(e) = recorder.record_resize(cols, rows)
+        {
+            self.stop_recording_after_error(e);
+        }
+    }
+
+    /// Start recording to a new asciicast file in the recordings directory
+    /// of `connection_name`, replacing any recording in progress. Returns the
+    /// file's path.
+    pub fn start_recording(
+        &mut self,
+        connection_name: &str,
+    ) -> crate::error::Result<std::path::PathBuf> {
+        let path = recording_path(connection_name, chrono::Local::now())?;
+        let (rows, cols) = self.screen_size();
+        let title = Some(connection_name.to_string());
+        let recorder = SessionRecorder::create(&path, cols, rows, title)?;
+        let path = recorder.path().to_path_buf();
+        self.recorder = Some(recorder);
+        Ok(path)
+    }
+
+    /// Stop recording; returns the file that was written
+    pub fn stop_recording(&mut self) -> Option<std::path::PathBuf> {
+        self.recorder
+            .take()
+            .map(|recorder| recorder.path().to_path_buf())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Tee output from the remote side into the recording, if any
+    pub fn record_output(&mut self, data: &[u8]) {
+        if let Some(recorder) = &mut self.recorder
+            && let Err(e) = recorder.record_output(data)
+        {
+            self.stop_recording_after_error(e);
+        }
+    }
+
+    pub fn set_session_logger(&mut self, logger: SessionLogger) {
+        self.logger = Some(logger);
+    }
+
+    /// Tee output from the remote side into the session log, if any
+    pub fn log_output(&mut self, data: &[u8]) {
+        if let Some(logger) = &mut self.logger
+            && let Err(e) = logger.log_output(data)
+        {
+            tracing::warn!("Stopped session log: {}", e);
+            self.logger = None;
+        }
+    }
+
+    /// The scrollback with its colours, to be restored by the next session.
+    /// `None` while a full-screen program hides the shell's history.
+    pub fn history_to_save(&self) -> Option<String> {
+        if self.is_alternate_screen() {
+            return None;
+        }
+        Some(self.export(ExportScope::Scrollback, ExportFormat::Ansi, ""))
+    }
+
+    /// Put a previous session's scrollback above everything the new session
+    /// prints, under a dimmed marker line, leaving the screen empty
+    pub fn restore_history(&mut self, history: &str, saved: chrono::DateTime<chrono::Local>) {
+        if history.trim().is_empty() {
+            return;
+        }
+        let (rows, _) = self.screen_size();
+        let mut bytes = history.replace('\n', "\r\n");
+        bytes.push_str(&format!(
+            "\x1b[0;2m── previous session, saved {} ──\x1b[0m",
+            saved.format("%Y-%m-%d %H:%M")
+        ));
+        // Scroll it all off the screen and start again at the top
+        bytes.push_str(&"\r\n".repeat(rows as usize));
+        bytes.push_str("\x1b[H");
+        self.process_bytes(bytes.as_bytes());
+    }
+
+    fn stop_recording_after_error(&mut self, error: std::io::Error) {
+        if let Some(path) = self.stop_recording() {
+            tracing::warn!("Stopped recording to {}: {}", path.display(), error);
+        }
     }
 
     pub fn process_bytes(&mut self, data: &[u8]) {
+        self.modes.scan(data);
         self.terminal.advance_bytes(data);
+        self.handle_alerts();
+        if !self.shell_integration {
+            self.shell_integration = self.cursor_line_has_prompt();
+        }
+        // New output can move the text under the pointer
+        self.hovered_link = None;
         self.last_change = Instant::now();
+        self.commands = None;
         self.invalidate_cache();
         self.search.mark_dirty();
     }
 
+    /// Hold back output that belongs to a ZMODEM transfer, which starts
+    /// when `sz` or `rz` sends its first header. Returns the part of `data`
+    /// for the screen.
+    pub fn intercept_zmodem<'a>(&mut self, data: &'a [u8]) -> &'a [u8] {
+        let (shown, started) = self.zmodem.intercept(data);
+        if let Some(direction) = started {
+            let _ = self.alert_tx.send(TerminalAlert::Zmodem(direction));
+        }
+        &data[..shown]
+    }
+
+    /// Take the output of the ZMODEM transfer that just started, and the
+    /// token that cancels it. Returns `None` if already taken.
+    pub fn take_zmodem_input(
+        &mut self,
+    ) -> Option<(
+        tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>,
+        tokio_util::sync::CancellationToken,
+    )> {
+        self.zmodem.take_input()
+    }
+
+    pub fn cancel_zmodem(&self) {
+        self.zmodem.cancel();
+    }
+
+    /// Show output again after a ZMODEM transfer, starting with what the
+    /// transfer read past its end
+    pub fn finish_zmodem(
+        &mut self,
+        leftover: Vec<u8>,
+        input: &mut tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>,
+    ) {
+        let rest = self.zmodem.finish(leftover, input);
+        if !rest.is_empty() {
+            self.record_output(&rest);
+            self.log_output(&rest);
+            self.process_bytes(&rest);
+        }
+    }
+
+    fn handle_alerts(&mut self) {
+        while let Ok(alert) = self.wez_alerts.try_recv() {
+            match alert {
+                Alert::WindowTitleChanged(title) => {
+                    let title = title.trim();
+                    self.window_title = (!title.is_empty()).then(|| title.to_string());
+                }
+                Alert::Bell => {
+                    self.ring_bell();
+                    let _ = self.alert_tx.send(TerminalAlert::Bell);
+                }
+                Alert::ToastNotification { title, body, .. } => {
+                    let _ = self
+                        .alert_tx
+                        .send(TerminalAlert::Notification { title, body });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Turn "notify when idle" on, with the given idle time, or off
+    pub fn set_idle_watch(&mut self, after: Option<Duration>) {
+        self.idle_watch = after.map(IdleWatch::new);
+    }
+
+    /// Idle time of "notify when idle", if it's on
+    pub fn idle_watch(&self) -> Option<Duration> {
+        self.idle_watch.as_ref().map(IdleWatch::after)
+    }
+
+    /// Tell "notify when idle" a key was sent to the session
+    pub fn idle_watch_key(&mut self, enter: bool) {
+        if self.idle_watch.is_none() {
+            return;
+        }
+        let prompt = self.last_prompt();
+        if let Some(watch) = &mut self.idle_watch {
+            watch.key_pressed(enter, prompt, Instant::now());
+        }
+    }
+
+    /// Check whether the prompt came back or output has gone quiet; called
+    /// every second while "notify when idle" is on
+    pub fn poll_idle_watch(&mut self) {
+        if !self.idle_watch.as_ref().is_some_and(IdleWatch::running) {
+            return;
+        }
+        let prompt = self.last_prompt();
+        if let Some(done) = self
+            .idle_watch
+            .as_mut()
+            .and_then(|watch| watch.poll(self.last_change, prompt, Instant::now()))
+        {
+            self.command_done(done);
+        }
+    }
+
+    /// Stable row of the newest prompt the shell marked
+    fn last_prompt(&mut self) -> Option<StableRowIndex> {
+        if !self.shell_integration {
+            return None;
+        }
+        let zones = self.terminal.get_semantic_zones().unwrap_or_else(|e| {
+            tracing::warn!("Could not read the prompt marks: {}", e);
+            Vec::new()
+        });
+        zones
+            .iter()
+            .rev()
+            .find(|zone| zone.semantic_type == SemanticType::Prompt)
+            .map(|zone| zone.start_y)
+    }
+
+    /// Whether the cursor's line holds a marked prompt, which is where the
+    /// cursor waits once the shell has printed one
+    fn cursor_line_has_prompt(&self) -> bool {
+        let screen = self.terminal.screen();
+        let row = screen.phys_row(self.terminal.cursor_pos().y);
+        screen
+            .lines_in_phys_range(row..row + 1)
+            .iter()
+            .flat_map(|line| line.visible_cells())
+            .any(|cell| cell.attrs().semantic_type() == SemanticType::Prompt)
+    }
+
+    /// Start the visual bell. A flash already showing isn't restarted, so
+    /// a burst of bells ends with one flash.
+    fn ring_bell(&mut self) {
+        if !self.bell_flashing() {
+            self.bell_at = Some(Instant::now());
+        }
+        self.bell_pending = true;
+    }
+
+    fn command_done(&mut self, done: CommandDone) {
+        self.ring_bell();
+        let _ = self.alert_tx.send(TerminalAlert::CommandDone(done));
+    }
+
+    /// Take the receiver for the emulator's replies, which must be written
+    /// back to the remote host. Returns `None` if already taken.
+    pub fn take_reply_receiver(&mut self) -> Option<tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>> {
+        self.replies.take()
+    }
+
+    /// Take the receiver for text remote programs put on the clipboard with
+    /// OSC 52. Returns `None` if already taken.
+    pub fn take_clipboard_receiver(
+        &mut self,
+    ) -> Option<tokio::sync::mpsc::UnboundedReceiver<String>> {
+        self.clipboard_requests.take()
+    }
+
+    /// Take the receiver for bells and desktop notifications from the
+    /// remote side. Returns `None` if already taken.
+    pub fn take_alert_receiver(
+        &mut self,
+    ) -> Option<tokio::sync::mpsc::UnboundedReceiver<TerminalAlert>> {
+        self.alerts.take()
+    }
+
+    /// Window title set by the remote side, if any
+    pub fn window_title(&self) -> Option<&str> {
+        self.window_title.as_deref()
+    }
+
+    /// Whether the visual bell is currently flashing
+    pub fn bell_flashing(&self) -> bool {
+        self.bell_at
+            .is_some_and(|at| at.elapsed() < BELL_FLASH_DURATION)
+    }
+
+    /// Whether the bell rang since the user last typed
+    pub fn bell_pending(&self) -> bool {
+        self.bell_pending
+    }
+
+    pub fn acknowledge_bell(&mut self) {
+        self.bell_pending = false;
+    }
+
+    /// Absolute row shown at the top of the viewport
+    fn viewport_top_row(&self) -> usize {
+        let screen = self.terminal.screen();
+        screen
+            .scrollback_rows()
+            .saturating_sub(screen.physical_rows)
+            .saturating_sub(self.scrollback_offset)
+    }
+
+    /// Rows in the scrollback and on screen
+    pub fn history_rows(&self) -> usize {
+        self.terminal.screen().scrollback_rows()
+    }
+
+    /// Stable index of the bottom row of the screen; it grows by one for
+    /// every row output scrolls up, even once old rows are trimmed
+    pub fn bottom_stable_row(&self) -> StableRowIndex {
+        let screen = self.terminal.screen();
+        screen.phys_to_stable_row_index(screen.scrollback_rows().saturating_sub(1))
+    }
+
+    /// Characters of a row, one per column (a wide character fills both of
+    /// its columns). Rows count up from the bottom of the screen, like
+    /// [`crate::terminal::SelectionEndpoint::rev_row`].
+    pub fn row_chars(&self, rev_row: i64) -> Option<Vec<char>> {
+        let screen = self.terminal.screen();
+        let width = screen.physical_cols;
+        let total_rows = screen.scrollback_rows();
+        let row = usize::try_from(total_rows as i64 - 1 - rev_row)
+            .ok()
+            .filter(|&row| row < total_rows)?;
+        let line = screen
+            .lines_in_phys_range(row..row + 1)
+            .into_iter()
+            .next()?;
+        let mut chars = Vec::with_capacity(width);
+        for cell in line.visible_cells() {
+            let ch = cell.str().chars().next().unwrap_or(' ');
+            chars.extend(std::iter::repeat_n(ch, cell.width().max(1)));
+        }
+        chars.resize(width, ' ');
+        Some(chars)
+    }
+
+    /// Link under a viewport cell, if any
+    pub fn link_at(&self, view_row: u16, col: u16) -> Option<TerminalLink> {
+        let screen = self.terminal.screen();
+        let row = self.viewport_top_row() + view_row as usize;
+        if row >= screen.scrollback_rows() {
+            return None;
+        }
+        let line = screen
+            .lines_in_phys_range(row..row + 1)
+            .into_iter()
+            .next()?;
+        line_links(&line, row, screen.physical_cols)
+            .into_iter()
+            .find(|link| (link.start_col..link.end_col).contains(&col))
+    }
+
+    /// Whether the shell marks its prompts (OSC 133)
+    pub fn has_shell_integration(&self) -> bool {
+        self.shell_integration
+    }
+
+    /// Keep a column for the command gutter. It is set before the session
+    /// starts, so the remote side never sees the width change.
+    pub fn set_prompt_gutter(&mut self, on: bool) {
+        self.prompt_gutter = on;
+    }
+
+    /// Commands at marked prompts in the scrollback and on screen, oldest first
+    pub fn commands(&mut self) -> &[ShellCommand] {
+        if self.commands.is_none() {
+            let zones = if self.shell_integration {
+                self.terminal.get_semantic_zones().unwrap_or_else(|e| {
+                    tracing::warn!("Could not read the prompt marks: {}", e);
+                    Vec::new()
+                })
+            } else {
+                Vec::new()
+            };
+            let screen = self.terminal.screen();
+            let total_rows = screen.scrollback_rows() as i64;
+            // Zones trimmed from the scrollback are left out
+            let endpoint = |y, x: usize| {
+                screen.stable_row_to_phys(y).map(|row| SelectionEndpoint {
+                    rev_row: total_rows - 1 - row as i64,
+                    col: x.min(u16::MAX as usize) as u16,
+                })
+            };
+            let commands = commands_from_zones(zones.iter().filter_map(|zone| {
+                Some((
+                    zone.semantic_type,
+                    endpoint(zone.start_y, zone.start_x)?,
+                    endpoint(zone.end_y, zone.end_x)?,
+                ))
+            }));
+            self.commands = Some(commands);
+        }
+        self.commands.as_deref().unwrap_or_default()
+    }
+
+    /// Scroll the previous (`up`) or next prompt to the top of the view.
+    /// Returns false when there is none; going down then ends at the bottom.
+    pub fn jump_to_prompt(&mut self, up: bool) -> bool {
+        let (height, _) = self.screen_size();
+        let top = (self.scrollback_offset + height.saturating_sub(1) as usize) as i64;
+        let Some(prompt) = adjacent_prompt(self.commands(), top, up) else {
+            if !up {
+                self.scroll_to_bottom();
+            }
+            return false;
+        };
+        let offset = prompt - i64::from(height.saturating_sub(1));
+        self.scroll_by((offset - self.scrollback_offset as i64) as i32);
+        true
+    }
+
+    /// Output of the last command that finished
+    pub fn last_command_output(&mut self) -> Option<(SelectionEndpoint, SelectionEndpoint)> {
+        self.commands()
+            .iter()
+            .rev()
+            .find_map(|command| command.output)
+    }
+
+    /// Links on screen and in the scrollback, oldest first
+    pub fn links(&self) -> Vec<TerminalLink> {
+        let screen = self.terminal.screen();
+        let width = screen.physical_cols;
+        screen
+            .lines_in_phys_range(0..screen.scrollback_rows())
+            .iter()
+            .enumerate()
+            .flat_map(|(row, line)| line_links(line, row, width))
+            .collect()
+    }
+
+    /// Set the link under the mouse pointer
+    pub fn set_hovered_link(&mut self, link: Option<TerminalLink>) {
+        self.hovered_link = link;
+    }
+
+    /// Colour scheme the terminal is rendered with, if any
+    pub fn color_scheme(&self) -> Option<ColorScheme> {
+        self.color_scheme
+    }
+
     /// Get the current scrollback offset
     pub fn scrollback(&self) -> usize {
         self.scrollback_offset
@@ -320,6 +1016,7 @@ impl TerminalState {
         let current = self.scrollback_offset as i32;
         let target = current.saturating_add(delta_lines).max(0) as usize;
         self.scrollback_offset = target.min(max_sb);
+        self.hovered_link = None;
         self.invalidate_cache();
     }
 
@@ -342,6 +1039,7 @@ impl TerminalState {
         };
         let config = Arc::new(SimpleConfig {
             scrollback_size: self.scrollback_limit,
+            color_scheme: self.color_scheme,
         });
         self.terminal = WezTerminal::new(size, config, "termirs", "0.1", Box::new(std::io::sink()));
         self.cached_lines.clear();
@@ -391,7 +1089,7 @@ impl TerminalState {
             }
             let new_hash = compute_row_hash_wez(line, width);
             if self.row_hashes[row_idx] != new_hash || self.cache_invalidated {
-                let built_line = build_line_wez(line, width);
+                let built_line = build_line_wez(line, width, self.color_scheme.as_ref());
                 self.cached_lines[row_idx] = built_line;
                 self.row_hashes[row_idx] = new_hash;
             }
@@ -415,13 +1113,14 @@ impl TerminalState {
     /// This allows for dependency injection in tests
     fn update_search_with_finder<F>(&mut self, find_matches: F)
     where
-        F: Fn(&str, &str) -> Vec<(usize, u16, u16)>,
+        F: Fn(&str, &str, SearchOptions) -> Result<Vec<(usize, u16, u16)>, String>,
     {
         if !self.search.needs_update() {
             return;
         }
 
         self.search.matches.clear();
+        self.search.error = None;
         self.search.dirty = false;
 
         if self.search.query.is_empty() {
@@ -454,7 +1153,13 @@ impl TerminalState {
 
         // Build the complete text with newlines and search once
         let full_text = row_texts.join("\n");
-        let all_matches = find_matches(&full_text, &self.search.query);
+        let all_matches = match find_matches(&full_text, &self.search.query, self.search.options) {
+            Ok(matches) => matches,
+            Err(e) => {
+                self.search.error = Some(e);
+                Vec::new()
+            }
+        };
 
         // Map line indices back to absolute row indices
         for (line_idx, start_col, end_col) in all_matches {
@@ -481,6 +1186,59 @@ impl TerminalState {
         }
     }
 
+    /// Text of every row with a search match, oldest first, one per line
+    pub fn matching_lines_text(&self) -> Option<String> {
+        let screen = self.terminal.screen();
+        let total_lines = screen.scrollback_rows();
+        let width = screen.physical_cols;
+
+        let mut rows: Vec<usize> = self.search.matches.iter().map(|mat| mat.row).collect();
+        rows.dedup();
+        let lines: Vec<String> = rows
+            .into_iter()
+            .filter(|&row| row < total_lines)
+            .filter_map(|row| {
+                let line = screen
+                    .lines_in_phys_range(row..row + 1)
+                    .into_iter()
+                    .next()?;
+                Some(extract_line_text_wez(&line, width).trim_end().to_string())
+            })
+            .collect();
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+
+    /// The scrollback or the visible rows rendered in `format`, with the
+    /// colours they are drawn with
+    pub fn export(&self, scope: ExportScope, format: ExportFormat, title: &str) -> String {
+        let screen = self.terminal.screen();
+        let width = screen.physical_cols;
+        let range = match scope {
+            ExportScope::Scrollback => 0..screen.scrollback_rows(),
+            ExportScope::Screen => {
+                let top = self.viewport_top_row();
+                top..top + screen.physical_rows
+            }
+        };
+        let scheme = self.color_scheme.as_ref();
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        let mut wrapped = false;
+        for line in screen.lines_in_phys_range(range) {
+            let built = build_line_wez(&line, width, scheme);
+            match lines.last_mut() {
+                // Text wrapped at the screen edge is one line, as when copied
+                Some(last) if wrapped => last.spans.extend(built.spans),
+                _ => lines.push(built),
+            }
+            wrapped = line.last_cell_was_wrapped();
+        }
+        crate::terminal::export::render(&lines, format, blank_style(scheme), title)
+    }
+
     /// Scroll to make current match visible
     pub fn scroll_to_current_match(&mut self) {
         if let Some(mat) = self.search.current_match().cloned() {
@@ -541,6 +1299,11 @@ impl TerminalState {
         self.terminal.is_alt_screen_active()
     }
 
+    /// Check if the remote program enabled bracketed paste (DECSET 2004)
+    pub fn bracketed_paste(&self) -> bool {
+        self.modes.bracketed_paste()
+    }
+
     /// Check if application cursor keys mode is active
     pub fn application_cursor_keys(&self) -> bool {
         self.terminal.get_application_cursor_keys()
@@ -556,13 +1319,78 @@ impl TerminalState {
     pub fn hide_cursor(&self) -> bool {
         self.terminal.cursor_pos().visibility != CursorVisibility::Visible
     }
+
+    /// Check if the remote program has enabled mouse tracking
+    pub fn mouse_grabbed(&self) -> bool {
+        self.terminal.is_mouse_grabbed()
+    }
+
+    /// Report a mouse event at the given screen cell to the remote program.
+    /// The emulator encodes it for the active tracking mode (X10, 1000,
+    /// 1002, 1006 SGR) and emits it through the reply channel; events the
+    /// mode doesn't ask for (e.g. plain motion) are dropped there.
+    pub fn send_mouse_event(
+        &mut self,
+        kind: crossterm::event::MouseEventKind,
+        row: u16,
+        col: u16,
+        modifiers: crossterm::event::KeyModifiers,
+    ) {
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEventKind};
+
+        let map_button = |button: MouseButton| match button {
+            MouseButton::Left => WezMouseButton::Left,
+            MouseButton::Right => WezMouseButton::Right,
+            MouseButton::Middle => WezMouseButton::Middle,
+        };
+        let (kind, button) = match kind {
+            MouseEventKind::Down(button) => (WezMouseEventKind::Press, map_button(button)),
+            MouseEventKind::Up(button) => (WezMouseEventKind::Release, map_button(button)),
+            MouseEventKind::Drag(button) => (WezMouseEventKind::Move, map_button(button)),
+            MouseEventKind::Moved => (WezMouseEventKind::Move, WezMouseButton::None),
+            MouseEventKind::ScrollUp => (WezMouseEventKind::Press, WezMouseButton::WheelUp(1)),
+            MouseEventKind::ScrollDown => (WezMouseEventKind::Press, WezMouseButton::WheelDown(1)),
+            MouseEventKind::ScrollLeft => (WezMouseEventKind::Press, WezMouseButton::WheelLeft(1)),
+            MouseEventKind::ScrollRight => {
+                (WezMouseEventKind::Press, WezMouseButton::WheelRight(1))
+            }
+        };
+
+        let mut mods = WezModifiers::NONE;
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            mods |= WezModifiers::SHIFT;
+        }
+        if modifiers.contains(KeyModifiers::ALT) {
+            mods |= WezModifiers::ALT;
+        }
+        if modifiers.contains(KeyModifiers::CONTROL) {
+            mods |= WezModifiers::CTRL;
+        }
+
+        let event = WezMouseEvent {
+            kind,
+            x: col as usize,
+            y: row as i64,
+            x_pixel_offset: 0,
+            y_pixel_offset: 0,
+            button,
+            modifiers: mods,
+        };
+        if let Err(e) = self.terminal.mouse_event(event) {
+            tracing::debug!("Failed to encode mouse event: {}", e);
+        }
+    }
 }
 
-/// Convert wezterm ColorAttribute to ratatui Color
-fn map_color_wez(color: &ColorAttribute) -> Color {
+/// Convert wezterm ColorAttribute to ratatui Color. `default` stands in for
+/// the default colour; the scheme, if any, replaces ANSI colours 0-15.
+fn map_color_wez(color: &ColorAttribute, default: Color, scheme: Option<&ColorScheme>) -> Color {
     match color {
-        ColorAttribute::Default => Color::Reset,
-        ColorAttribute::PaletteIndex(idx) => Color::Indexed(*idx),
+        ColorAttribute::Default => default,
+        ColorAttribute::PaletteIndex(idx) => match scheme.and_then(|s| s.ansi.get(*idx as usize)) {
+            Some(rgb) => (*rgb).into(),
+            None => Color::Indexed(*idx),
+        },
         ColorAttribute::TrueColorWithDefaultFallback(c)
         | ColorAttribute::TrueColorWithPaletteFallback(c, _) => {
             let (r, g, b, _) = c.to_tuple_rgba();
@@ -601,20 +1429,94 @@ fn extract_line_text_wez(line: &wezterm_term::Line, width: usize) -> String {
     text
 }
 
+/// Links on one line: OSC 8 hyperlinks, then URLs detected in the text
+/// that don't overlap them
+fn line_links(line: &wezterm_term::Line, row: usize, width: usize) -> Vec<TerminalLink> {
+    let mut text = String::with_capacity(width);
+    // Byte offset in `text` and column of each cell
+    let mut cells: Vec<(usize, u16)> = Vec::with_capacity(width);
+    let mut links: Vec<TerminalLink> = Vec::new();
+    let mut col = 0usize;
+
+    for cell in line.visible_cells() {
+        if col >= width {
+            break;
+        }
+        cells.push((text.len(), col as u16));
+        let contents = cell.str();
+        text.push_str(if contents.is_empty() { " " } else { contents });
+
+        let end_col = (col + cell.width()).min(width) as u16;
+        if let Some(hyperlink) = cell.attrs().hyperlink() {
+            match links.last_mut() {
+                Some(last) if last.end_col == col as u16 && last.url == hyperlink.uri() => {
+                    last.end_col = end_col;
+                }
+                _ => links.push(TerminalLink {
+                    row,
+                    start_col: col as u16,
+                    end_col,
+                    url: hyperlink.uri().to_string(),
+                    hidden_target: false,
+                }),
+            }
+        }
+        col += cell.width();
+    }
+
+    let osc8_links = links.len();
+    let text_end = col.min(width) as u16;
+    let offset_at = |col: u16| {
+        cells
+            .iter()
+            .find(|&&(_, cell_col)| cell_col >= col)
+            .map_or(text.len(), |&(offset, _)| offset)
+    };
+    for link in &mut links {
+        let shown = &text[offset_at(link.start_col)..offset_at(link.end_col)];
+        link.hidden_target = shown.trim() != link.url;
+    }
+    for range in find_urls(&text) {
+        let start_col = cells[cells.partition_point(|&(offset, _)| offset <= range.start) - 1].1;
+        let end_col = cells
+            .get(cells.partition_point(|&(offset, _)| offset < range.end))
+            .map_or(text_end, |&(_, col)| col);
+        let overlaps = links[..osc8_links]
+            .iter()
+            .any(|link| start_col < link.end_col && link.start_col < end_col);
+        if !overlaps {
+            links.push(TerminalLink {
+                row,
+                start_col,
+                end_col,
+                url: text[range].to_string(),
+                hidden_target: false,
+            });
+        }
+    }
+    links
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct TerminalSelection {
     pub start_row: u16,
     pub start_col: u16,
     pub end_row: u16,
     pub end_col: u16,
+    /// Same columns on every row instead of wrapping
+    pub block: bool,
 }
 
+/// Draw the terminal below a one-line title bar; `title` is shown unless
+/// copy mode or search replaces it
 pub fn draw_terminal(
     area: Rect,
     state: &mut TerminalState,
-    name: &str,
+    title: &str,
     frame: &mut ratatui::Frame<'_>,
     selection: Option<TerminalSelection>,
+    copy_mode: Option<&CopyMode>,
+    theme: &Theme,
 ) {
     // Update search matches if needed
     state.update_search();
@@ -627,83 +1529,191 @@ pub fn draw_terminal(
         state.search.needs_initial_scroll = false;
     }
 
-    // Build title based on search state
-    let (title, title_style) = if state.search.active {
+    // Build title based on copy mode and search state
+    let (title, title_style) = if let Some(copy_mode) = copy_mode {
+        let title_text = match copy_mode.visual() {
+            None => " COPY ".to_string(),
+            Some(VisualMode::Char) => " COPY: VISUAL ".to_string(),
+            Some(VisualMode::Line) => " COPY: VISUAL LINE ".to_string(),
+            Some(VisualMode::Block) => " COPY: VISUAL BLOCK ".to_string(),
+        };
+        let style = Style::default()
+            .fg(theme.selection_fg)
+            .bg(theme.selection_bg)
+            .add_modifier(Modifier::BOLD);
+        (title_text, style)
+    } else if state.search.active {
         let search = &state.search;
+        let labels = search.options.labels();
+        let prefix = if labels.is_empty() {
+            "SEARCHING".to_string()
+        } else {
+            format!("SEARCHING ({})", labels.join(", "))
+        };
         let title_text = if search.is_inputting() {
             if search.query.is_empty() {
-                " SEARCHING: _ ".to_string()
+                format!(" {prefix}: _ ")
+            } else if search.error.is_some() {
+                format!(" {prefix}: {}_ (invalid pattern) ", search.query)
             } else {
-                format!(" SEARCHING: {}_ ", search.query)
+                format!(" {prefix}: {}_ ", search.query)
             }
         } else {
             // Navigation mode
-            if search.matches.is_empty() {
-                format!(" SEARCHING: {} (no matches) ", search.query)
+            if search.error.is_some() {
+                format!(" {prefix}: {} (invalid pattern) ", search.query)
+            } else if search.matches.is_empty() {
+                format!(" {prefix}: {} (no matches) ", search.query)
             } else {
                 format!(
-                    " SEARCHING: {} ({}/{}) ",
+                    " {prefix}: {} ({}/{}) ",
                     search.query,
                     search.current_idx + 1,
                     search.matches.len()
                 )
             }
         };
+        let background = if search.error.is_some() {
+            theme.error
+        } else {
+            theme.warning
+        };
         let style = Style::default()
-            .fg(Color::White)
-            .bg(Color::Yellow)
+            .fg(theme.text)
+            .bg(background)
             .add_modifier(Modifier::BOLD);
         (title_text, style)
     } else {
-        (
-            format!("Connected to {name}"),
-            Style::default().fg(Color::Cyan),
-        )
+        let mut title_text = title.to_string();
+        if state.is_recording() {
+            title_text.push_str(" ⏺ REC");
+        }
+        if state.idle_watch().is_some() {
+            title_text.push_str(" ⏰ NOTIFY");
+        }
+        if state.bell_pending() {
+            title_text.push_str(" 🔔");
+        }
+        let mut style = Style::default().fg(theme.title);
+        if state.bell_flashing() {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        (title_text, style)
     };
 
     // Render the block with appropriate title
     let mut term_block = Block::default()
         .borders(Borders::TOP)
         .title(Span::styled(title, title_style));
-    if state.search.active {
-        term_block = term_block.border_style(Style::default().fg(Color::Yellow));
+    if copy_mode.is_some() {
+        term_block = term_block.border_style(Style::default().fg(theme.selection_bg));
+    } else if state.search.active || state.bell_flashing() {
+        term_block = term_block.border_style(Style::default().fg(theme.warning));
     } else {
-        term_block = term_block.border_style(Style::default().fg(Color::Cyan));
+        term_block = term_block.border_style(Style::default().fg(theme.border));
     }
 
     frame.render_widget(&term_block, area);
 
     // Get the inner area for terminal content
-    let inner = term_block.inner(area);
+    let inner = terminal_text_area(area, state);
+    if inner.x > area.x {
+        draw_command_gutter(frame.buffer_mut(), inner, state, theme);
+    }
     let height = inner.height;
     let width = inner.width;
     let (cur_row, cur_col) = state.cursor_position();
     let hide_cursor = state.hide_cursor();
+    let scheme = state.color_scheme();
     let lines = state.cached_lines(height, width);
 
     // Render terminal rows using a lightweight cached widget
-    let widget = CachedTerminalWidget { lines };
+    let widget = CachedTerminalWidget {
+        lines,
+        background: blank_style(scheme.as_ref()),
+    };
     frame.render_widget(widget, inner);
 
     // Highlight search matches
     if state.search.active && !state.search.matches.is_empty() {
-        highlight_search_matches(frame.buffer_mut(), inner, state);
+        highlight_search_matches(frame.buffer_mut(), inner, state, theme);
+    }
+
+    if let Some(link) = &state.hovered_link {
+        underline_link(frame.buffer_mut(), inner, link, state.viewport_top_row());
     }
 
     if let Some(selection) = selection {
-        highlight_selection(frame.buffer_mut(), inner, selection);
+        let color = scheme.map_or(theme.muted, |s| s.selection.into());
+        highlight_selection(frame.buffer_mut(), inner, selection, color);
     }
 
-    if !hide_cursor && !state.search.active {
+    if let Some(copy_mode) = copy_mode {
+        // The copy mode cursor replaces the terminal's own
+        if let Some(row) = rev_to_view_row(state, copy_mode.cursor.rev_row) {
+            frame.set_cursor_position((inner.x + copy_mode.cursor.col, inner.y + row));
+        }
+    } else if !hide_cursor && !state.search.active {
         // Use inner area coordinates (already accounts for borders)
         let cursor_x = inner.x + cur_col;
         let cursor_y = inner.y + cur_row;
+        // The host terminal's cursor colour can't be changed per pane, so
+        // paint the scheme's cursor colour into the cell underneath
+        if let Some(scheme) = scheme
+            && let Some(cell) = frame.buffer_mut().cell_mut((cursor_x, cursor_y))
+        {
+            cell.set_bg(scheme.cursor.into());
+            cell.set_fg(scheme.background.into());
+        }
         frame.set_cursor_position((cursor_x, cursor_y));
     }
 }
 
+/// Where the terminal's text goes in `area`: below the title and, with
+/// `prompt_gutter` on, right of the command gutter
+pub fn terminal_text_area(area: Rect, state: &TerminalState) -> Rect {
+    let mut inner = crate::ui::rect_with_top_margin(area, 1);
+    if state.prompt_gutter && inner.width > 1 {
+        inner.x += 1;
+        inner.width -= 1;
+    }
+    inner
+}
+
+/// Mark prompts with `❯` and the output of finished commands with `│` in
+/// the column left of `text_area`
+fn draw_command_gutter(
+    buf: &mut Buffer,
+    text_area: Rect,
+    state: &mut TerminalState,
+    theme: &Theme,
+) {
+    let (height, scrollback) = (text_area.height, state.scrollback() as i64);
+    let view_row = |rev_row: i64| {
+        let row = i64::from(height) - 1 - (rev_row - scrollback);
+        (0..i64::from(height)).contains(&row).then_some(row as u16)
+    };
+    let x = text_area.x - 1;
+    for command in state.commands() {
+        if let Some((first, last)) = command.output {
+            for rev_row in last.rev_row..=first.rev_row {
+                if let Some(row) = view_row(rev_row)
+                    && let Some(cell) = buf.cell_mut((x, text_area.y + row))
+                {
+                    cell.set_symbol("│").set_fg(theme.muted);
+                }
+            }
+        }
+        if let Some(row) = view_row(command.prompt)
+            && let Some(cell) = buf.cell_mut((x, text_area.y + row))
+        {
+            cell.set_symbol("❯").set_fg(theme.title);
+        }
+    }
+}
+
 /// Highlight search matches in the terminal buffer
-fn highlight_search_matches(buf: &mut Buffer, area: Rect, state: &TerminalState) {
+fn highlight_search_matches(buf: &mut Buffer, area: Rect, state: &TerminalState, theme: &Theme) {
     if area.width == 0 || area.height == 0 {
         return;
     }
@@ -718,7 +1728,7 @@ fn highlight_search_matches(buf: &mut Buffer, area: Rect, state: &TerminalState)
     let view_start_abs = max_scrollback.saturating_sub(current_scrollback);
     let view_end_abs = view_start_abs + height;
 
-    // First pass: render all non-current matches with Yellow
+    // First pass: render all non-current matches
     for (idx, mat) in state.search.matches.iter().enumerate() {
         if idx == current_match_idx {
             continue; // Skip current match in first pass
@@ -744,13 +1754,13 @@ fn highlight_search_matches(buf: &mut Buffer, area: Rect, state: &TerminalState)
         for col in start_col..end_col {
             let x = area.x + col;
             if let Some(cell) = buf.cell_mut((x, y)) {
-                cell.set_bg(Color::Yellow);
+                cell.set_bg(theme.search_match);
                 cell.set_fg(Color::Black);
             }
         }
     }
 
-    // Second pass: render current match with LightGreen (ensures it overrides Yellow)
+    // Second pass: render the current match (ensures it overrides the others)
     if let Some(mat) = state.search.matches.get(current_match_idx) {
         // Check if current match is in the current view
         if mat.row >= view_start_abs && mat.row < view_end_abs {
@@ -764,7 +1774,7 @@ fn highlight_search_matches(buf: &mut Buffer, area: Rect, state: &TerminalState)
                 for col in start_col..end_col {
                     let x = area.x + col;
                     if let Some(cell) = buf.cell_mut((x, y)) {
-                        cell.set_bg(Color::LightGreen);
+                        cell.set_bg(theme.search_current);
                         cell.set_fg(Color::Black);
                     }
                 }
@@ -775,10 +1785,13 @@ fn highlight_search_matches(buf: &mut Buffer, area: Rect, state: &TerminalState)
 
 struct CachedTerminalWidget<'a> {
     lines: &'a [Line<'static>],
+    /// Style for cells no line covers
+    background: Style,
 }
 
 impl<'a> Widget for CachedTerminalWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, self.background);
         let height = area.height.min(self.lines.len() as u16);
         for row in 0..height {
             let line = &self.lines[row as usize];
@@ -787,7 +1800,23 @@ impl<'a> Widget for CachedTerminalWidget<'a> {
     }
 }
 
-fn highlight_selection(buf: &mut Buffer, area: Rect, selection: TerminalSelection) {
+/// Underline the hovered link if it's in the viewport
+fn underline_link(buf: &mut Buffer, area: Rect, link: &TerminalLink, top_row: usize) {
+    let Some(view_row) = link.row.checked_sub(top_row) else {
+        return;
+    };
+    if view_row >= area.height as usize {
+        return;
+    }
+    let y = area.y + view_row as u16;
+    for col in link.start_col..link.end_col.min(area.width) {
+        if let Some(cell) = buf.cell_mut((area.x + col, y)) {
+            cell.modifier.insert(Modifier::UNDERLINED);
+        }
+    }
+}
+
+fn highlight_selection(buf: &mut Buffer, area: Rect, selection: TerminalSelection, color: Color) {
     if area.width == 0 || area.height == 0 {
         return;
     }
@@ -805,7 +1834,7 @@ fn highlight_selection(buf: &mut Buffer, area: Rect, selection: TerminalSelectio
         let x_end = area.x + col_end;
         for x in x_start..x_end {
             if let Some(cell) = buf.cell_mut((x, y)) {
-                cell.set_bg(Color::DarkGray);
+                cell.set_bg(color);
             }
         }
     }
@@ -822,8 +1851,8 @@ fn selection_bounds_for_row(selection: TerminalSelection, row: u16, width: u16)
         end_col = selection.end_col.min(width);
     }
 
-    // Handle selection entirely within one row
-    if selection.start_row == selection.end_row {
+    // Handle selection entirely within one row, or a block
+    if selection.start_row == selection.end_row || selection.block {
         start_col = selection.start_col.min(width);
         end_col = selection.end_col.min(width);
     }
@@ -885,8 +1914,25 @@ fn hash_color_wez(hasher: &mut DefaultHasher, color: &ColorAttribute) {
     }
 }
 
+/// Style of a blank cell: the scheme's default colours, or the host's
+fn blank_style(scheme: Option<&ColorScheme>) -> Style {
+    match scheme {
+        Some(scheme) => Style::default()
+            .fg(scheme.foreground.into())
+            .bg(scheme.background.into()),
+        None => Style::default(),
+    }
+}
+
 /// Build a ratatui Line from a wezterm Line
-fn build_line_wez(line: &wezterm_term::Line, width: usize) -> Line<'static> {
+fn build_line_wez(
+    line: &wezterm_term::Line,
+    width: usize,
+    scheme: Option<&ColorScheme>,
+) -> Line<'static> {
+    let blank = blank_style(scheme);
+    let default_fg = blank.fg.unwrap_or(Color::Reset);
+    let default_bg = blank.bg.unwrap_or(Color::Reset);
     let mut spans: Vec<Span<'static>> = Vec::new();
     let mut current_style = Style::default();
     let mut current_text = String::new();
@@ -900,8 +1946,8 @@ fn build_line_wez(line: &wezterm_term::Line, width: usize) -> Line<'static> {
         let cell_width = cell.width();
         let attrs = cell.attrs();
 
-        let fg = map_color_wez(&attrs.foreground());
-        let bg = map_color_wez(&attrs.background());
+        let fg = map_color_wez(&attrs.foreground(), default_fg, scheme);
+        let bg = map_color_wez(&attrs.background(), default_bg, scheme);
         let bold = attrs.intensity() == Intensity::Bold;
         let italic = attrs.italic();
         let underline = attrs.underline() != Underline::None;
@@ -953,7 +1999,7 @@ fn build_line_wez(line: &wezterm_term::Line, width: usize) -> Line<'static> {
 
     // Fill remaining columns with spaces
     while col < width {
-        if current_style == Style::default() {
+        if current_style == blank {
             current_text.push(' ');
         } else {
             if !current_text.is_empty() {
@@ -962,7 +2008,7 @@ fn build_line_wez(line: &wezterm_term::Line, width: usize) -> Line<'static> {
                     current_style,
                 ));
             }
-            current_style = Style::default();
+            current_style = blank;
             current_text.push(' ');
         }
         col += 1;
@@ -978,7 +2024,176 @@ fn build_line_wez(line: &wezterm_term::Line, width: usize) -> Line<'static> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Instant;
+    use std::time::{Duration, Instant};
+
+    /// The regex, smart-case matching that search always used before it had options
+    const REGEX: SearchOptions = SearchOptions {
+        regex: true,
+        case_sensitive: false,
+        whole_word: false,
+    };
+
+    #[tokio::test]
+    async fn test_terminal_replies_to_queries() {
+        let mut state = TerminalState::new(24, 80);
+        let mut replies = state.take_reply_receiver().unwrap();
+        assert!(state.take_reply_receiver().is_none());
+
+        // Move the cursor to row 3, column 5, then ask for its position (CSI 6n)
+        state.process_bytes(b"\x1b[3;5H\x1b[6n");
+        let reply = tokio::time::timeout(Duration::from_secs(1), replies.recv())
+            .await
+            .expect("no reply to cursor position report")
+            .unwrap();
+        assert_eq!(reply, b"\x1b[3;5R");
+    }
+
+    #[test]
+    fn test_remote_title_bell_and_notifications() {
+        let mut state = TerminalState::new(24, 80);
+        let mut alerts = state.take_alert_receiver().unwrap();
+
+        state.process_bytes(b"\x1b]2;vim main.rs\x07");
+        assert_eq!(state.window_title(), Some("vim main.rs"));
+        assert!(!state.bell_pending());
+
+        state.process_bytes(b"\x07\x1b]777;notify;Build;finished\x07\x1b]9;done\x07");
+        assert!(state.bell_pending());
+        assert!(state.bell_flashing());
+        // Another bell doesn't restart the flash
+        let flash = state.bell_at;
+        state.process_bytes(b"\x07");
+        assert_eq!(state.bell_at, flash);
+        assert_eq!(alerts.try_recv().unwrap(), TerminalAlert::Bell);
+        assert_eq!(
+            alerts.try_recv().unwrap(),
+            TerminalAlert::Notification {
+                title: Some("Build".to_string()),
+                body: "finished".to_string(),
+            }
+        );
+        assert_eq!(
+            alerts.try_recv().unwrap(),
+            TerminalAlert::Notification {
+                title: None,
+                body: "done".to_string(),
+            }
+        );
+
+        state.acknowledge_bell();
+        assert!(!state.bell_pending());
+        // OSC 0 sets the title too; an empty title clears it
+        state.process_bytes(b"\x1b]0;\x07");
+        assert_eq!(state.window_title(), None);
+    }
+
+    #[test]
+    fn test_idle_watch_rings_on_prompt() {
+        let mut state = TerminalState::new(24, 80);
+        let mut alerts = state.take_alert_receiver().unwrap();
+        state.process_bytes(b"\x1b]133;A\x07$ \x1b]133;B\x07");
+
+        // Nothing is watched while the watch is off
+        state.idle_watch_key(true);
+        state.process_bytes(b"\r\n\x1b]133;C\x07done\r\n\x1b]133;A\x07$ \x1b]133;B\x07");
+        state.poll_idle_watch();
+        assert!(alerts.try_recv().is_err());
+
+        state.set_idle_watch(Some(Duration::ZERO));
+        state.idle_watch_key(true);
+        // Still at the same prompt
+        state.poll_idle_watch();
+        assert!(alerts.try_recv().is_err());
+        state.process_bytes(b"\r\n\x1b]133;C\x07done\r\n\x1b]133;A\x07$ \x1b]133;B\x07");
+        state.poll_idle_watch();
+        assert!(state.bell_pending());
+        assert_eq!(
+            alerts.try_recv().unwrap(),
+            TerminalAlert::CommandDone(CommandDone::Prompt)
+        );
+    }
+
+    #[test]
+    fn test_gutter_width_doesnt_change_with_prompt_marks() {
+        let mut state = TerminalState::new(24, 79);
+        let area = Rect::new(0, 0, 80, 25);
+        assert_eq!(terminal_text_area(area, &state), Rect::new(0, 1, 80, 24));
+
+        state.set_prompt_gutter(true);
+        assert_eq!(terminal_text_area(area, &state), Rect::new(1, 1, 79, 24));
+        assert!(state.commands().is_empty());
+        state.process_bytes(b"\x1b]133;A\x07$ ");
+        assert!(state.has_shell_integration());
+        assert_eq!(state.commands().len(), 1);
+        assert_eq!(terminal_text_area(area, &state), Rect::new(1, 1, 79, 24));
+    }
+
+    #[test]
+    fn test_export_joins_wrapped_rows() {
+        let mut state = TerminalState::new(4, 10);
+        state.process_bytes(b"$ echo 0123456789abcdef\r\nok\r\n");
+        assert_eq!(
+            state.export(ExportScope::Scrollback, ExportFormat::Text, ""),
+            "$ echo 0123456789abcdef\nok\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "anyhow")]
+    fn test_osc52_writes_reach_the_clipboard_receiver() {
+        let mut state = TerminalState::new(4, 40);
+        let mut requests = state.take_clipboard_receiver().unwrap();
+        state.process_bytes(b"\x1b]52;c;aGVs");
+        state.process_bytes(b"bG8=\x07\x1b]52;c;?\x07");
+
+        assert_eq!(requests.try_recv().ok().as_deref(), Some("hello"));
+        // Queries aren't answered
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_links_from_osc8_and_text() {
+        let mut state = TerminalState::new(4, 60);
+        state.process_bytes(b"docs: \x1b]8;;https://docs.example/\x1b\\manual\x1b]8;;\x1b\\\r\n");
+        state.process_bytes("café → https://example.com/x, ok".as_bytes());
+
+        let links = state.links();
+        assert_eq!(
+            links,
+            vec![
+                TerminalLink {
+                    row: 0,
+                    start_col: 6,
+                    end_col: 12,
+                    url: "https://docs.example/".to_string(),
+                    hidden_target: true,
+                },
+                TerminalLink {
+                    row: 1,
+                    start_col: 7,
+                    end_col: 28,
+                    url: "https://example.com/x".to_string(),
+                    hidden_target: false,
+                },
+            ]
+        );
+        assert_eq!(state.link_at(1, 10), Some(links[1].clone()));
+        assert_eq!(state.link_at(1, 28), None);
+        assert_eq!(state.link_at(0, 2), None);
+    }
+
+    #[test]
+    fn test_color_scheme_maps_default_and_ansi_colors() {
+        let scheme = crate::config::color_scheme::builtin_scheme("dracula").unwrap();
+        let mut state = TerminalState::new_with_color_scheme(2, 10, 100, Some(scheme));
+        state.process_bytes(b"\x1b[31mred\x1b[0m");
+
+        let spans = &state.cached_lines(2, 10)[0].spans;
+        assert_eq!(spans[0].content, "red");
+        assert_eq!(spans[0].style.fg, Some(scheme.ansi[1].into()));
+        assert_eq!(spans[0].style.bg, Some(scheme.background.into()));
+        assert_eq!(spans[1].style, blank_style(Some(&scheme)));
+    }
 
     #[test]
     #[ignore = "profiling helper; run explicitly when needed"]
@@ -1019,7 +2234,7 @@ mod tests {
     #[test]
     fn test_find_matches_in_text_single_match() {
         let text = "hello world";
-        let matches = find_matches_in_text(text, "world");
+        let matches = find_matches_in_text(text, "world", REGEX).unwrap();
         // (line_idx, start_col, end_col)
         assert_eq!(matches, vec![(0, 6, 11)]);
     }
@@ -1027,7 +2242,7 @@ mod tests {
     #[test]
     fn test_find_matches_in_text_multiline() {
         let text = "hello world\nwow hello world";
-        let matches = find_matches_in_text(text, "world");
+        let matches = find_matches_in_text(text, "world", REGEX).unwrap();
         // Line 0: "hello world" -> match at 6-11
         // Line 1: "wow hello world" -> match at 10-15
         assert_eq!(matches, vec![(0, 6, 11), (1, 10, 15)]);
@@ -1036,27 +2251,27 @@ mod tests {
     #[test]
     fn test_find_matches_in_text_multiple_matches() {
         let text = "foo bar foo baz foo";
-        let matches = find_matches_in_text(text, "foo");
+        let matches = find_matches_in_text(text, "foo", REGEX).unwrap();
         assert_eq!(matches, vec![(0, 0, 3), (0, 8, 11), (0, 16, 19)]);
     }
 
     #[test]
     fn test_find_matches_in_text_no_matches() {
         let text = "hello world";
-        let matches = find_matches_in_text(text, "xyz");
+        let matches = find_matches_in_text(text, "xyz", REGEX).unwrap();
         assert!(matches.is_empty());
     }
 
     #[test]
     fn test_find_matches_in_text_empty_pattern() {
         let text = "hello world";
-        let matches = find_matches_in_text(text, "");
+        let matches = find_matches_in_text(text, "", REGEX).unwrap();
         assert!(matches.is_empty());
     }
 
     #[test]
     fn test_find_matches_in_text_empty_text() {
-        let matches = find_matches_in_text("", "pattern");
+        let matches = find_matches_in_text("", "pattern", REGEX).unwrap();
         assert!(matches.is_empty());
     }
 
@@ -1064,7 +2279,7 @@ mod tests {
     fn test_find_matches_in_text_case_insensitive() {
         // Smart case: lowercase pattern should match case-insensitively
         let text = "Hello HELLO hello";
-        let matches = find_matches_in_text(text, "hello");
+        let matches = find_matches_in_text(text, "hello", REGEX).unwrap();
         assert_eq!(matches, vec![(0, 0, 5), (0, 6, 11), (0, 12, 17)]);
     }
 
@@ -1072,14 +2287,14 @@ mod tests {
     fn test_find_matches_in_text_case_sensitive_with_uppercase() {
         // Smart case: pattern with uppercase should match case-sensitively
         let text = "Hello HELLO hello";
-        let matches = find_matches_in_text(text, "Hello");
+        let matches = find_matches_in_text(text, "Hello", REGEX).unwrap();
         assert_eq!(matches, vec![(0, 0, 5)]);
     }
 
     #[test]
     fn test_find_matches_in_text_regex_pattern() {
         let text = "foo123 bar456 baz789";
-        let matches = find_matches_in_text(text, r"\d+");
+        let matches = find_matches_in_text(text, r"\d+", REGEX).unwrap();
         // The implementation finds overlapping matches by advancing 1 byte after each match
         assert_eq!(
             matches,
@@ -1101,7 +2316,7 @@ mod tests {
     fn test_find_matches_in_text_overlapping_potential() {
         // Pattern "aa" in "aaaa" - should find non-overlapping matches
         let text = "aaaa";
-        let matches = find_matches_in_text(text, "aa");
+        let matches = find_matches_in_text(text, "aa", REGEX).unwrap();
         // With start = match_start + 1, we get overlapping positions
         assert_eq!(matches, vec![(0, 0, 2), (0, 1, 3), (0, 2, 4)]);
     }
@@ -1109,22 +2324,24 @@ mod tests {
     #[test]
     fn test_find_matches_in_text_special_chars() {
         let text = "path/to/file.rs";
-        let matches = find_matches_in_text(text, r"\.rs");
+        let matches = find_matches_in_text(text, r"\.rs", REGEX).unwrap();
         assert_eq!(matches, vec![(0, 12, 15)]);
     }
 
     #[test]
     fn test_find_matches_in_text_invalid_regex() {
         let text = "hello world";
-        // Invalid regex pattern should return empty
-        let matches = find_matches_in_text(text, "[invalid");
-        assert!(matches.is_empty());
+        // Invalid regex pattern should return an error
+        assert!(find_matches_in_text(text, "[invalid", REGEX).is_err());
+        // ...but is fine as literal text
+        let matches = find_matches_in_text("a [invalid one", "[invalid", SearchOptions::default());
+        assert_eq!(matches.unwrap(), vec![(0, 2, 10)]);
     }
 
     #[test]
     fn test_find_matches_in_text_word_boundary() {
         let text = "the them there";
-        let matches = find_matches_in_text(text, r"\bthe\b");
+        let matches = find_matches_in_text(text, r"\bthe\b", REGEX).unwrap();
         assert_eq!(matches, vec![(0, 0, 3)]);
     }
 
@@ -1132,24 +2349,119 @@ mod tests {
     fn test_find_matches_in_text_at_boundaries() {
         // Test matching at start and end of text
         let text = "foo bar foo";
-        let matches = find_matches_in_text(text, "foo");
+        let matches = find_matches_in_text(text, "foo", REGEX).unwrap();
         assert_eq!(matches, vec![(0, 0, 3), (0, 8, 11)]);
     }
 
     #[test]
     fn test_find_matches_in_text_whole_text() {
         let text = "exact";
-        let matches = find_matches_in_text(text, "exact");
+        let matches = find_matches_in_text(text, "exact", REGEX).unwrap();
         assert_eq!(matches, vec![(0, 0, 5)]);
     }
 
     #[test]
     fn test_find_matches_in_text_multiline_multiple_matches() {
         let text = "foo bar\nbaz foo\nfoo end";
-        let matches = find_matches_in_text(text, "foo");
+        let matches = find_matches_in_text(text, "foo", REGEX).unwrap();
         // Line 0: "foo bar" -> match at 0-3
         // Line 1: "baz foo" -> match at 4-7
         // Line 2: "foo end" -> match at 0-3
         assert_eq!(matches, vec![(0, 0, 3), (1, 4, 7), (2, 0, 3)]);
     }
+
+    #[test]
+    fn test_find_matches_in_text_options() {
+        let text = "a.b axb Foo foo food";
+        let literal = SearchOptions::default();
+        assert_eq!(
+            find_matches_in_text(text, "a.b", literal).unwrap(),
+            vec![(0, 0, 3)]
+        );
+        let case_sensitive = SearchOptions {
+            case_sensitive: true,
+            ..literal
+        };
+        assert_eq!(
+            find_matches_in_text(text, "foo", case_sensitive).unwrap(),
+            vec![(0, 12, 15), (0, 16, 19)]
+        );
+        let whole_word = SearchOptions {
+            whole_word: true,
+            ..case_sensitive
+        };
+        assert_eq!(
+            find_matches_in_text(text, "foo", whole_word).unwrap(),
+            vec![(0, 12, 15)]
+        );
+    }
+
+    #[test]
+    fn test_search_history_and_matching_lines() {
+        let mut state = TerminalState::new(4, 20);
+        state.process_bytes(b"error: one\r\nok\r\nerror: two error\r\n");
+
+        state.search.enter();
+        state.search.set_query("error".to_string());
+        state.update_search();
+        assert_eq!(state.search.matches.len(), 3);
+        assert_eq!(
+            state.matching_lines_text().as_deref(),
+            Some("error: one\nerror: two error")
+        );
+        state.search.confirm();
+
+        state.search.enter();
+        state.search.set_query("(".to_string());
+        state.search.toggle_regex();
+        state.update_search();
+        assert!(state.search.error.is_some());
+        state.search.toggle_regex();
+        state.update_search();
+        assert!(state.search.error.is_none());
+        state.search.confirm();
+
+        state.search.enter();
+        state.search.history_prev();
+        assert_eq!(state.search.query, "(");
+        state.search.history_prev();
+        assert_eq!(state.search.query, "error");
+        state.search.history_prev();
+        assert_eq!(state.search.query, "error");
+        state.search.history_next();
+        assert_eq!(state.search.query, "(");
+        state.search.history_next();
+        assert!(state.search.query.is_empty());
+    }
+
+    #[test]
+    fn test_restore_history_above_new_output() {
+        let mut old = TerminalState::new(4, 20);
+        old.process_bytes(b"\x1b[31mred\x1b[0m one\r\ntwo\r\n$ ");
+        let history = old.history_to_save().unwrap();
+        assert_eq!(
+            history,
+            "\x1b[0;31mred\x1b[0m one\x1b[0m\n\x1b[0mtwo\x1b[0m\n\x1b[0m$\x1b[0m\n"
+        );
+
+        let mut state = TerminalState::new(4, 20);
+        let saved = chrono::Local::now();
+        state.restore_history(&history, saved);
+        assert_eq!(state.cursor_position(), (0, 0));
+        state.process_bytes(b"welcome");
+
+        let row = |rev_row| {
+            let chars = state.row_chars(rev_row).unwrap();
+            chars.iter().collect::<String>().trim_end().to_string()
+        };
+        assert_eq!(row(3), "welcome");
+        assert!(row(4).starts_with("── previous session"));
+        assert_eq!(
+            (row(7), row(6), row(5)),
+            ("red one".into(), "two".into(), "$".into())
+        );
+
+        old.process_bytes(b"\x1b[?1049h");
+        assert!(old.history_to_save().is_none());
+    }
 }
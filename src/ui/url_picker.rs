@@ -0,0 +1,79 @@
+//! URL picker overlay for the connected terminal.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::config::theme::Theme;
+use crate::terminal::UrlPicker;
+
+/// Draw the URL picker: the links in the terminal, most recent first
+pub fn draw_url_picker(f: &mut Frame, area: Rect, picker: &UrlPicker, theme: &Theme) {
+    let links = picker.links();
+
+    let popup_w = (area.width as f32 * 0.7) as u16;
+    let popup_h = (links.len() as u16 + 4).min(area.height.saturating_sub(4));
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(popup_w)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_h)) / 2,
+        width: popup_w,
+        height: popup_h,
+    };
+
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(Span::styled(
+            format!(" Links ({}) ", links.len()),
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )));
+    f.render_widget(block, popup);
+
+    let inner = popup.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let items: Vec<ListItem> = links
+        .iter()
+        .map(|link| {
+            ListItem::new(Line::from(Span::styled(
+                link.url.clone(),
+                Style::default().fg(theme.text),
+            )))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    let mut list_state = ListState::default();
+    if !links.is_empty() {
+        list_state.select(Some(picker.selected.min(links.len() - 1)));
+    }
+    f.render_stateful_widget(list, sections[0], &mut list_state);
+
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            "↑↓: Navigate | Enter: Open | c: Copy | Esc: Close",
+            Style::default()
+                .fg(theme.footer)
+                .add_modifier(Modifier::DIM),
+        )),
+        sections[1],
+    );
+}
@@ -0,0 +1,76 @@
+//! Picker for the local side of a ZMODEM transfer, over the connected terminal.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::config::theme::Theme;
+use crate::terminal::ZmodemPicker;
+
+/// Draw the picker: a local file explorer with the transfer's keys below
+pub fn draw_zmodem_picker(f: &mut Frame, area: Rect, picker: &mut ZmodemPicker, theme: &Theme) {
+    let popup_w = (area.width as f32 * 0.7) as u16;
+    let popup_h = (area.height as f32 * 0.7) as u16;
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(popup_w)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_h)) / 2,
+        width: popup_w,
+        height: popup_h,
+    };
+
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(Span::styled(
+            format!(" {} | {} ", picker.title(), picker.explorer.cwd().display()),
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )));
+    f.render_widget(block, popup);
+
+    let inner = popup.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    picker.explorer.set_theme(
+        ratatui_async_explorer::Theme::new()
+            .with_item_style(Style::default().fg(theme.text))
+            .with_dir_style(Style::default().fg(theme.directory))
+            .with_highlight_dir_style(Style::default().fg(theme.directory).bg(theme.selection_bg))
+            .with_highlight_item_style(
+                Style::default()
+                    .fg(theme.selection_fg)
+                    .bg(theme.selection_bg),
+            ),
+    );
+    picker
+        .explorer
+        .widget_stateful()
+        .render(sections[0], f.buffer_mut());
+
+    let help = match picker.direction {
+        crate::zmodem::Direction::Upload => "↑↓: Navigate | Enter: Open/Send | ←: Up | Esc: Cancel",
+        crate::zmodem::Direction::Download => {
+            "↑↓: Navigate | Enter: Open | s: Save here | ←: Up | Esc: Cancel"
+        }
+    };
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            help,
+            Style::default()
+                .fg(theme.footer)
+                .add_modifier(Modifier::DIM),
+        )),
+        sections[1],
+    );
+}
@@ -54,3 +54,98 @@ pub fn init_tracing(log_level: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Directory for files termirs creates as it runs (recordings),
+/// `~/.local/share/termirs`
+pub fn data_dir() -> Result<std::path::PathBuf> {
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| AppError::ConfigError("Could not determine home directory".to_string()))?;
+    Ok(home_dir.join(".local").join("share").join("termirs"))
+}
+
+/// `name` with everything but letters, digits, `-`, `_` and `.` replaced by
+/// `_`, for use as a file or directory name
+pub fn file_name_safe(name: &str) -> String {
+    let safe: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    // Not "", "." or "..", which would escape the parent directory
+    if safe.chars().all(|c| c == '.') {
+        safe.replace('.', "_") + "_"
+    } else {
+        safe
+    }
+}
+
+/// `value` in single quotes for a POSIX shell, with any `'` in it escaped
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// URL schemes that links from the terminal may be opened with
+const OPENABLE_SCHEMES: &[&str] = &["http", "https", "mailto", "ftp"];
+
+/// Whether `url` is a web or mail link. Links come from the remote side, so
+/// other schemes (`file:`, `smb:`, custom protocol handlers) are refused.
+fn is_openable_url(url: &str) -> bool {
+    url.split_once(':').is_some_and(|(scheme, _)| {
+        OPENABLE_SCHEMES
+            .iter()
+            .any(|allowed| scheme.eq_ignore_ascii_case(allowed))
+    })
+}
+
+/// Open `url` with the system's default handler (usually the browser)
+pub fn open_url(url: &str) -> Result<()> {
+    if !is_openable_url(url) {
+        return Err(AppError::ValidationError(format!(
+            "Only http, https, mailto and ftp links can be opened: {url}"
+        )));
+    }
+
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    let mut child = command
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    // Reap the opener once it exits so it doesn't linger as a zombie
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_web_and_mail_links_open() {
+        assert!(is_openable_url("https://example.com/a"));
+        assert!(is_openable_url("HTTP://example.com"));
+        assert!(is_openable_url("mailto:ops@example.com"));
+        assert!(!is_openable_url("file:///etc/passwd"));
+        assert!(!is_openable_url("smb://host/share"));
+        assert!(!is_openable_url("-oProxyCommand=x"));
+        assert!(!is_openable_url("example.com"));
+    }
+}
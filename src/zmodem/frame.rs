@@ -0,0 +1,523 @@
+//! ZMODEM framing: headers, data subpackets, ZDLE escaping and CRCs.
+
+pub const ZPAD: u8 = b'*';
+pub const ZDLE: u8 = 0x18;
+const ZBIN: u8 = b'A';
+const ZHEX: u8 = b'B';
+const ZBIN32: u8 = b'C';
+const XON: u8 = 0x11;
+
+// Frame types
+pub const ZRQINIT: u8 = 0;
+pub const ZRINIT: u8 = 1;
+pub const ZSINIT: u8 = 2;
+pub const ZACK: u8 = 3;
+pub const ZFILE: u8 = 4;
+pub const ZSKIP: u8 = 5;
+pub const ZNAK: u8 = 6;
+pub const ZFIN: u8 = 8;
+pub const ZRPOS: u8 = 9;
+pub const ZDATA: u8 = 10;
+pub const ZEOF: u8 = 11;
+pub const ZCOMMAND: u8 = 18;
+
+// ZRINIT capabilities, in ZF0
+pub const CANFDX: u8 = 0x01;
+pub const CANOVIO: u8 = 0x02;
+pub const CANFC32: u8 = 0x20;
+pub const ESCCTL: u8 = 0x40;
+
+/// How a data subpacket ends, and what the receiver does next
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameEnd {
+    /// Last subpacket of the frame; a header follows
+    Crce = 0x68,
+    /// More subpackets follow without an acknowledgement
+    Crcg = 0x69,
+    /// More subpackets follow; the receiver acknowledges this one
+    Crcq = 0x6a,
+    /// Last subpacket of the frame; the receiver acknowledges it
+    Crcw = 0x6b,
+}
+
+impl FrameEnd {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x68 => Some(Self::Crce),
+            0x69 => Some(Self::Crcg),
+            0x6a => Some(Self::Crcq),
+            0x6b => Some(Self::Crcw),
+            _ => None,
+        }
+    }
+
+    /// Whether the frame ends here and a header comes next
+    pub fn ends_frame(self) -> bool {
+        matches!(self, Self::Crce | Self::Crcw)
+    }
+
+    /// Whether the sender waits for a ZACK
+    pub fn wants_ack(self) -> bool {
+        matches!(self, Self::Crcq | Self::Crcw)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub kind: u8,
+    /// ZP0..ZP3, which hold either a file position or ZF3..ZF0 flags
+    pub data: [u8; 4],
+}
+
+impl Header {
+    pub fn new(kind: u8, data: [u8; 4]) -> Self {
+        Self { kind, data }
+    }
+
+    pub fn with_position(kind: u8, position: u64) -> Self {
+        Self::new(kind, (position as u32).to_le_bytes())
+    }
+
+    pub fn position(&self) -> u64 {
+        u32::from_le_bytes(self.data) as u64
+    }
+
+    /// ZF0, the first flags byte
+    pub fn flags(&self) -> u8 {
+        self.data[3]
+    }
+
+    /// Headers followed by data subpackets
+    fn carries_data(&self) -> bool {
+        matches!(self.kind, ZSINIT | ZFILE | ZDATA | ZCOMMAND)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    Header(Header),
+    Data {
+        data: Vec<u8>,
+        end: FrameEnd,
+    },
+    /// A header or subpacket failed its CRC or was malformed
+    Corrupt,
+    /// The other side sent the cancel sequence
+    Cancelled,
+}
+
+pub fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(data);
+    crc.sum()
+}
+
+/// Writes frames, escaping what the link can't carry
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Encoder {
+    pub crc32: bool,
+    /// Escape every control character (the receiver's ESCCTL)
+    pub escape_ctl: bool,
+}
+
+impl Encoder {
+    fn escape_into(&self, out: &mut Vec<u8>, data: &[u8]) {
+        for &byte in data {
+            let prev = out.last().copied().unwrap_or(0);
+            let escape = match byte {
+                ZDLE | 0x10 | 0x11 | 0x13 | 0x90 | 0x91 | 0x93 => true,
+                // Telnet turns "@\r" into a login break
+                0x0d | 0x8d => self.escape_ctl || prev & 0x7f == b'@',
+                _ => self.escape_ctl && byte & 0x60 == 0,
+            };
+            if escape {
+                out.extend_from_slice(&[ZDLE, byte ^ 0x40]);
+            } else {
+                out.push(byte);
+            }
+        }
+    }
+
+    /// Binary header, CRC-32 if the receiver can check it
+    pub fn header(&self, header: Header) -> Vec<u8> {
+        let mut raw = vec![header.kind];
+        raw.extend_from_slice(&header.data);
+        let mut out = vec![ZPAD, ZDLE, if self.crc32 { ZBIN32 } else { ZBIN }];
+        let crc = if self.crc32 {
+            crc32(&raw).to_le_bytes().to_vec()
+        } else {
+            crc16(&raw).to_be_bytes().to_vec()
+        };
+        raw.extend_from_slice(&crc);
+        self.escape_into(&mut out, &raw);
+        out
+    }
+
+    pub fn subpacket(&self, data: &[u8], end: FrameEnd) -> Vec<u8> {
+        let mut out = Vec::with_capacity(data.len() + data.len() / 8 + 8);
+        self.escape_into(&mut out, data);
+        out.extend_from_slice(&[ZDLE, end as u8]);
+        let mut checked = data.to_vec();
+        checked.push(end as u8);
+        let crc = if self.crc32 {
+            crc32(&checked).to_le_bytes().to_vec()
+        } else {
+            crc16(&checked).to_be_bytes().to_vec()
+        };
+        self.escape_into(&mut out, &crc);
+        out
+    }
+}
+
+/// Hex header, which survives any link; always CRC-16
+pub fn hex_header(header: Header) -> Vec<u8> {
+    let mut raw = vec![header.kind];
+    raw.extend_from_slice(&header.data);
+    raw.extend_from_slice(&crc16(&raw).to_be_bytes());
+    let mut out = vec![ZPAD, ZPAD, ZDLE, ZHEX];
+    for byte in raw {
+        out.extend_from_slice(format!("{byte:02x}").as_bytes());
+    }
+    out.extend_from_slice(&[b'\r', 0x8a]);
+    if !matches!(header.kind, ZACK | ZFIN) {
+        out.push(XON);
+    }
+    out
+}
+
+/// Largest subpacket accepted; lrzsz sends at most 8K
+const MAX_SUBPACKET: usize = 16 * 1024;
+
+#[derive(Clone, Debug, Default)]
+enum State {
+    /// Skipping bytes until a header starts
+    #[default]
+    Idle,
+    Pad,
+    Zdle,
+    Hex(Vec<u8>),
+    /// CR LF and XON after a hex header
+    HexTrailer,
+    Binary {
+        crc32: bool,
+        raw: Vec<u8>,
+    },
+    Data {
+        crc32: bool,
+        data: Vec<u8>,
+        end: Option<FrameEnd>,
+        crc: Vec<u8>,
+    },
+}
+
+/// Incremental parser for what the other side sends
+#[derive(Debug, Default)]
+pub struct Decoder {
+    state: State,
+    /// ZDLE seen; the next byte is escaped
+    escaped: bool,
+    /// Consecutive CAN bytes; five cancel the session
+    cancels: usize,
+}
+
+impl Decoder {
+    /// Consume bytes up to and including the next event. Returns how many
+    /// bytes were used, so that what follows the session can be handed back.
+    pub fn next(&mut self, input: &[u8]) -> (usize, Option<Event>) {
+        for (idx, &byte) in input.iter().enumerate() {
+            if let Some(event) = self.push(byte) {
+                return (idx + 1, Some(event));
+            }
+        }
+        (input.len(), None)
+    }
+
+    fn push(&mut self, byte: u8) -> Option<Event> {
+        if byte == ZDLE {
+            self.cancels += 1;
+            if self.cancels >= 5 {
+                self.reset();
+                return Some(Event::Cancelled);
+            }
+        } else {
+            self.cancels = 0;
+        }
+
+        match std::mem::take(&mut self.state) {
+            State::Idle => {
+                if byte == ZPAD {
+                    self.state = State::Pad;
+                }
+                None
+            }
+            State::Pad => {
+                self.state = match byte {
+                    ZPAD => State::Pad,
+                    ZDLE => State::Zdle,
+                    _ => State::Idle,
+                };
+                None
+            }
+            State::Zdle => {
+                self.escaped = false;
+                self.state = match byte {
+                    ZHEX => State::Hex(Vec::with_capacity(14)),
+                    ZBIN => State::Binary {
+                        crc32: false,
+                        raw: Vec::with_capacity(7),
+                    },
+                    ZBIN32 => State::Binary {
+                        crc32: true,
+                        raw: Vec::with_capacity(9),
+                    },
+                    _ => State::Idle,
+                };
+                None
+            }
+            State::Hex(mut digits) => {
+                if !byte.is_ascii_hexdigit() {
+                    return Some(Event::Corrupt);
+                }
+                digits.push(byte);
+                if digits.len() < 14 {
+                    self.state = State::Hex(digits);
+                    return None;
+                }
+                let raw: Vec<u8> = digits
+                    .chunks(2)
+                    .map(|pair| {
+                        u8::from_str_radix(std::str::from_utf8(pair).unwrap_or("00"), 16)
+                            .unwrap_or(0)
+                    })
+                    .collect();
+                self.state = State::HexTrailer;
+                self.finish_header(&raw, false)
+            }
+            State::HexTrailer => {
+                self.state = match byte {
+                    b'\r' | 0x8d | b'\n' | 0x8a | XON => State::HexTrailer,
+                    ZPAD => State::Pad,
+                    _ => State::Idle,
+                };
+                None
+            }
+            State::Binary { crc32, mut raw } => {
+                match self.unescape(byte) {
+                    Unescaped::Byte(byte) => raw.push(byte),
+                    Unescaped::Pending | Unescaped::Noise => {}
+                    Unescaped::End(_) | Unescaped::Invalid => return Some(Event::Corrupt),
+                }
+                if raw.len() < if crc32 { 9 } else { 7 } {
+                    self.state = State::Binary { crc32, raw };
+                    return None;
+                }
+                self.finish_header(&raw, crc32)
+            }
+            State::Data {
+                crc32,
+                mut data,
+                mut end,
+                mut crc,
+            } => {
+                match self.unescape(byte) {
+                    Unescaped::Byte(byte) if end.is_none() => data.push(byte),
+                    Unescaped::Byte(byte) => crc.push(byte),
+                    Unescaped::End(frame_end) if end.is_none() => end = Some(frame_end),
+                    Unescaped::Pending | Unescaped::Noise => {}
+                    Unescaped::End(_) | Unescaped::Invalid => return Some(Event::Corrupt),
+                }
+                if data.len() > MAX_SUBPACKET {
+                    return Some(Event::Corrupt);
+                }
+                let Some(frame_end) = end else {
+                    self.state = State::Data {
+                        crc32,
+                        data,
+                        end,
+                        crc,
+                    };
+                    return None;
+                };
+                if crc.len() < if crc32 { 4 } else { 2 } {
+                    self.state = State::Data {
+                        crc32,
+                        data,
+                        end,
+                        crc,
+                    };
+                    return None;
+                }
+
+                let mut checked = data.clone();
+                checked.push(frame_end as u8);
+                let valid = if crc32 {
+                    crc32(&checked) == u32::from_le_bytes([crc[0], crc[1], crc[2], crc[3]])
+                } else {
+                    crc16(&checked) == u16::from_be_bytes([crc[0], crc[1]])
+                };
+                if !valid {
+                    return Some(Event::Corrupt);
+                }
+                if !frame_end.ends_frame() {
+                    self.state = State::Data {
+                        crc32,
+                        data: Vec::new(),
+                        end: None,
+                        crc: Vec::new(),
+                    };
+                }
+                Some(Event::Data {
+                    data,
+                    end: frame_end,
+                })
+            }
+        }
+    }
+
+    fn finish_header(&mut self, raw: &[u8], crc32: bool) -> Option<Event> {
+        let valid = if crc32 {
+            crc32(&raw[..5]) == u32::from_le_bytes([raw[5], raw[6], raw[7], raw[8]])
+        } else {
+            crc16(&raw[..5]) == u16::from_be_bytes([raw[5], raw[6]])
+        };
+        if !valid {
+            self.state = State::Idle;
+            return Some(Event::Corrupt);
+        }
+        let header = Header::new(raw[0], [raw[1], raw[2], raw[3], raw[4]]);
+        self.escaped = false;
+        if header.carries_data() {
+            self.state = State::Data {
+                crc32,
+                data: Vec::new(),
+                end: None,
+                crc: Vec::new(),
+            };
+        }
+        Some(Event::Header(header))
+    }
+
+    fn unescape(&mut self, byte: u8) -> Unescaped {
+        if !self.escaped {
+            return match byte {
+                ZDLE => {
+                    self.escaped = true;
+                    Unescaped::Pending
+                }
+                // Flow control characters are always sent escaped
+                0x11 | 0x13 | 0x91 | 0x93 => Unescaped::Noise,
+                _ => Unescaped::Byte(byte),
+            };
+        }
+        self.escaped = false;
+        if let Some(end) = FrameEnd::from_byte(byte) {
+            return Unescaped::End(end);
+        }
+        match byte {
+            b'l' => Unescaped::Byte(0x7f),
+            b'm' => Unescaped::Byte(0xff),
+            _ if byte & 0x60 == 0x40 => Unescaped::Byte(byte ^ 0x40),
+            _ => Unescaped::Invalid,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = State::Idle;
+        self.escaped = false;
+        self.cancels = 0;
+    }
+}
+
+enum Unescaped {
+    Byte(u8),
+    End(FrameEnd),
+    /// A ZDLE; the escaped byte comes next
+    Pending,
+    /// XON/XOFF from the link
+    Noise,
+    Invalid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(decoder: &mut Decoder, mut input: &[u8]) -> Vec<Event> {
+        let mut events = Vec::new();
+        while !input.is_empty() {
+            let (used, event) = decoder.next(input);
+            events.extend(event);
+            input = &input[used..];
+        }
+        events
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut decoder = Decoder::default();
+
+        // The ZRINIT that lrzsz's rz prints
+        let zrinit = b"**\x18B0100000023be50\r\x8a\x11";
+        assert_eq!(
+            decode_all(&mut decoder, zrinit),
+            [Event::Header(Header::new(ZRINIT, [0, 0, 0, 0x23]))]
+        );
+        assert_eq!(
+            hex_header(Header::new(ZRINIT, [0, 0, 0, 0x23])),
+            zrinit.to_vec()
+        );
+
+        let payload: Vec<u8> = (0..=255).chain([ZDLE, b'@', b'\r']).collect();
+        for crc32 in [false, true] {
+            let encoder = Encoder {
+                crc32,
+                escape_ctl: crc32,
+            };
+            let mut stream = encoder.header(Header::with_position(ZDATA, 70_000));
+            stream.extend(encoder.subpacket(&payload, FrameEnd::Crcg));
+            stream.extend(encoder.subpacket(b"end", FrameEnd::Crce));
+            stream.extend(encoder.header(Header::with_position(ZEOF, 70_259)));
+            assert_eq!(
+                decode_all(&mut decoder, &stream),
+                [
+                    Event::Header(Header::with_position(ZDATA, 70_000)),
+                    Event::Data {
+                        data: payload.clone(),
+                        end: FrameEnd::Crcg
+                    },
+                    Event::Data {
+                        data: b"end".to_vec(),
+                        end: FrameEnd::Crce
+                    },
+                    Event::Header(Header::with_position(ZEOF, 70_259)),
+                ]
+            );
+        }
+
+        // A flipped bit is caught, and the next header is still found
+        let mut stream = Encoder::default().header(Header::with_position(ZRPOS, 5));
+        stream[4] ^= 0x01;
+        stream.extend(hex_header(Header::new(ZFIN, [0; 4])));
+        assert_eq!(
+            decode_all(&mut decoder, &stream),
+            [Event::Corrupt, Event::Header(Header::new(ZFIN, [0; 4]))]
+        );
+
+        assert_eq!(decode_all(&mut decoder, &[ZDLE; 5]), [Event::Cancelled]);
+    }
+}
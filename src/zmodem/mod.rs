@@ -0,0 +1,166 @@
+//! In-band file transfers with ZMODEM: `sz` and `rz` run on the host and
+//! talk to us through the terminal session. While a transfer runs the
+//! session's output goes to it instead of the screen.
+
+mod frame;
+mod session;
+
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+pub use session::{Job, TransferIo, refuse, run};
+
+/// Which way a transfer goes, seen from this side
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// `sz` on the host is sending files to us
+    Download,
+    /// `rz` on the host is waiting for a file from us
+    Upload,
+}
+
+/// Start of the first header `sz` sends (ZRQINIT)
+const DOWNLOAD_START: &[u8] = b"**\x18B00";
+/// Start of the first header `rz` sends (ZRINIT)
+const UPLOAD_START: &[u8] = b"**\x18B01";
+
+/// Watches the session's output for the start of a transfer, including one
+/// split across reads
+#[derive(Debug, Default)]
+struct Detector {
+    /// End of the previous read, which may hold the start of a header
+    tail: Vec<u8>,
+}
+
+/// A transfer starting in a read
+struct Detected {
+    direction: Direction,
+    /// Length of the read's text before the transfer
+    before: usize,
+    /// The transfer's bytes so far
+    input: Vec<u8>,
+}
+
+impl Detector {
+    fn scan(&mut self, data: &[u8]) -> Option<Detected> {
+        let tail_len = self.tail.len();
+        let mut window = std::mem::take(&mut self.tail);
+        window.extend_from_slice(data);
+
+        let found = [
+            (Direction::Download, DOWNLOAD_START),
+            (Direction::Upload, UPLOAD_START),
+        ]
+        .into_iter()
+        .filter_map(|(direction, start)| {
+            window
+                .windows(start.len())
+                .position(|candidate| candidate == start)
+                .map(|pos| (pos, direction))
+        })
+        .min_by_key(|(pos, _)| *pos);
+
+        match found {
+            Some((pos, direction)) => Some(Detected {
+                direction,
+                before: pos.saturating_sub(tail_len),
+                input: window[pos..].to_vec(),
+            }),
+            None => {
+                let keep = window.len().min(DOWNLOAD_START.len() - 1);
+                self.tail = window[window.len() - keep..].to_vec();
+                None
+            }
+        }
+    }
+}
+
+/// Diverts a session's output to a transfer from the moment one starts
+/// until [`Tap::finish`]
+#[derive(Debug, Default)]
+pub struct Tap {
+    detector: Detector,
+    capture: Option<mpsc::UnboundedSender<Vec<u8>>>,
+    input: Option<mpsc::UnboundedReceiver<Vec<u8>>>,
+    cancel: CancellationToken,
+}
+
+impl Tap {
+    /// Pass a read through. Returns how much of its start is for the screen,
+    /// and the direction if a transfer started in it.
+    pub fn intercept(&mut self, data: &[u8]) -> (usize, Option<Direction>) {
+        if let Some(capture) = &self.capture {
+            if capture.send(data.to_vec()).is_ok() {
+                return (0, None);
+            }
+            // The transfer went away without finishing; show output again
+            self.capture = None;
+        }
+        let Some(detected) = self.detector.scan(data) else {
+            return (data.len(), None);
+        };
+        let (tx, rx) = mpsc::unbounded_channel();
+        let _ = tx.send(detected.input);
+        self.capture = Some(tx);
+        self.input = Some(rx);
+        self.cancel = CancellationToken::new();
+        (detected.before, Some(detected.direction))
+    }
+
+    /// Take the output meant for the transfer that just started, and the
+    /// token that cancels it
+    pub fn take_input(&mut self) -> Option<(mpsc::UnboundedReceiver<Vec<u8>>, CancellationToken)> {
+        self.input.take().map(|input| (input, self.cancel.clone()))
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    /// Stop diverting output. `leftover` is what the transfer read past its
+    /// end; it is returned with anything still queued, for the screen.
+    pub fn finish(
+        &mut self,
+        mut leftover: Vec<u8>,
+        input: &mut mpsc::UnboundedReceiver<Vec<u8>>,
+    ) -> Vec<u8> {
+        while let Ok(chunk) = input.try_recv() {
+            leftover.extend(chunk);
+        }
+        self.capture = None;
+        self.input = None;
+        self.detector = Detector::default();
+        leftover
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_split_header() {
+        let mut tap = Tap::default();
+        assert_eq!(tap.intercept(b"$ ls\r\n"), (6, None));
+
+        // `sz` prints "rz\r" and a ZRQINIT, here split across two reads
+        assert_eq!(tap.intercept(b"rz\r**\x18"), (6, None));
+        assert_eq!(
+            tap.intercept(b"B00000000000000\r\x8a\x11"),
+            (0, Some(Direction::Download))
+        );
+        let (mut input, _) = tap.take_input().unwrap();
+        assert_eq!(
+            input.try_recv().unwrap(),
+            b"**\x18B00000000000000\r\x8a\x11".to_vec()
+        );
+
+        // Everything goes to the transfer until it finishes
+        assert_eq!(tap.intercept(b"**\x18B01"), (0, None));
+        assert_eq!(tap.finish(b"\r\n".to_vec(), &mut input), b"\r\n**\x18B01");
+        assert_eq!(
+            tap.intercept(b"waiting to receive.**\x18B0100000023be50"),
+            (19, Some(Direction::Upload))
+        );
+    }
+}
@@ -0,0 +1,680 @@
+//! The two sides of a transfer: sending a file to `rz` and receiving files
+//! from `sz`.
+
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::frame::{
+    CANFC32, CANFDX, CANOVIO, Decoder, ESCCTL, Encoder, Event, FrameEnd, Header, ZACK, ZCOMMAND,
+    ZDATA, ZDLE, ZEOF, ZFILE, ZFIN, ZNAK, ZRINIT, ZRPOS, ZRQINIT, ZSINIT, ZSKIP, hex_header,
+};
+use crate::error::{AppError, Result};
+use crate::transfer::{
+    ScpFileProgress, ScpFileResult, ScpResult, ScpTransferProgress, TransferState,
+};
+use crate::transport::TerminalTransport;
+use crate::ui::ScpMode;
+
+/// Data per subpacket when sending
+const BLOCK_SIZE: usize = 1024;
+/// How long to wait for the other side before asking again
+const RECV_TIMEOUT: Duration = Duration::from_secs(10);
+/// Times the other side may go quiet at one step before we give up
+const MAX_RETRIES: u32 = 5;
+/// After an abort, the other side's leftovers are skipped until it has been
+/// quiet this long
+const DRAIN_QUIET: Duration = Duration::from_millis(300);
+const DRAIN_LIMIT: Duration = Duration::from_secs(3);
+/// How long `sz` gets to send the "OO" that ends its session
+const OVER_AND_OUT_WAIT: Duration = Duration::from_millis(500);
+/// Stops the other side: eight CANs, then backspaces that erase them from a
+/// shell's line if nothing was listening
+const ABORT: &[u8] = b"\x18\x18\x18\x18\x18\x18\x18\x18\x08\x08\x08\x08\x08\x08\x08\x08\x08\x08";
+
+/// What to do with the transfer the host started
+#[derive(Clone, Debug)]
+pub enum Job {
+    /// Send this file to `rz`
+    Upload(PathBuf),
+    /// Save the files from `sz` in this directory
+    Download(PathBuf),
+}
+
+pub struct TransferIo {
+    /// Output of the session, from the transfer's first header on
+    pub input: mpsc::UnboundedReceiver<Vec<u8>>,
+    pub output: Arc<dyn TerminalTransport>,
+    pub cancel: CancellationToken,
+}
+
+/// Run a transfer to the end, reporting its files to `progress`. Returns
+/// what the host printed after it, which belongs on the screen.
+pub async fn run(job: Job, io: &mut TransferIo, progress: mpsc::Sender<ScpResult>) -> Vec<u8> {
+    let mut link = Link::new(io);
+    let mut files = Files::new(progress);
+    let result = match &job {
+        Job::Upload(path) => upload(&mut link, path, &mut files).await,
+        Job::Download(dir) => download(&mut link, dir, &mut files).await,
+    };
+    let leftover = match &result {
+        Ok(()) => link.finish(matches!(job, Job::Download(_))).await,
+        Err(e) => {
+            warn!("ZMODEM transfer failed: {}", e);
+            link.abort().await
+        }
+    };
+    files.report(result).await;
+    leftover
+}
+
+/// Turn down a transfer the user didn't want
+pub async fn refuse(io: &mut TransferIo) -> Vec<u8> {
+    info!("Refusing ZMODEM transfer");
+    Link::new(io).abort().await
+}
+
+/// Send `path` to `rz`
+async fn upload(link: &mut Link<'_>, path: &Path, files: &mut Files) -> Result<()> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let metadata = file.metadata().await?;
+    let size = metadata.len();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "upload".to_string());
+    let index = files
+        .add(
+            ScpMode::Send,
+            path.display().to_string(),
+            name.clone(),
+            Some(size),
+        )
+        .await;
+
+    // `rz` announced itself with ZRINIT, which says how to talk to it
+    let zrinit = link
+        .expect(&[ZRINIT], &hex_header(Header::new(ZRQINIT, [0; 4])))
+        .await?;
+    let flags = zrinit.flags();
+    link.encoder = Encoder {
+        crc32: flags & CANFC32 != 0,
+        escape_ctl: flags & ESCCTL != 0,
+    };
+    // Stream unless the receiver can't take data while writing to disk, or
+    // has a limited buffer
+    let streaming = flags & CANFDX != 0 && zrinit.data[..2] == [0, 0];
+
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_secs());
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode()
+    };
+    #[cfg(not(unix))]
+    let mode = 0o100644;
+    let file_info = format!("{name}\0{size} {mtime:o} {mode:o} 0 1 {size}\0");
+    let mut offer = link.encoder.header(Header::new(ZFILE, [0; 4]));
+    offer.extend(link.encoder.subpacket(file_info.as_bytes(), FrameEnd::Crcw));
+    link.send(&offer).await?;
+    let reply = link.expect(&[ZRPOS, ZSKIP], &offer).await?;
+    if reply.kind == ZSKIP {
+        files.finish(index, Some("Skipped by the host".to_string()));
+        return end_session(link).await;
+    }
+
+    let mut block = vec![0; BLOCK_SIZE];
+    let mut position = reply.position();
+    'resend: loop {
+        file.seek(SeekFrom::Start(position)).await?;
+        let mut frame = link.encoder.header(Header::with_position(ZDATA, position));
+        loop {
+            let len = read_block(&mut file, &mut block).await?;
+            let last = len < BLOCK_SIZE;
+            let end = match (streaming, last) {
+                (false, _) => FrameEnd::Crcw,
+                (true, true) => FrameEnd::Crce,
+                (true, false) => FrameEnd::Crcg,
+            };
+            frame.extend(link.encoder.subpacket(&block[..len], end));
+            link.send(&frame).await?;
+            position += len as u64;
+            files.update(index, position, Some(size));
+
+            if end == FrameEnd::Crcw {
+                let ack = link.expect(&[ZACK, ZRPOS], &frame).await?;
+                if ack.kind == ZRPOS {
+                    position = ack.position();
+                    continue 'resend;
+                }
+                frame = link.encoder.header(Header::with_position(ZDATA, position));
+            } else {
+                frame = Vec::new();
+                // The receiver asks for a resend from where it lost track
+                if let Some(Event::Header(header)) = link.poll()?
+                    && header.kind == ZRPOS
+                {
+                    position = header.position();
+                    continue 'resend;
+                }
+            }
+            if last {
+                break;
+            }
+        }
+
+        let eof = link.encoder.header(Header::with_position(ZEOF, position));
+        link.send(&eof).await?;
+        let reply = link.expect(&[ZRINIT, ZRPOS, ZSKIP], &eof).await?;
+        match reply.kind {
+            ZRPOS => position = reply.position(),
+            ZSKIP => {
+                files.finish(index, Some("Skipped by the host".to_string()));
+                break;
+            }
+            _ => {
+                files.finish(index, None);
+                break;
+            }
+        }
+    }
+    end_session(link).await
+}
+
+/// Say goodbye to `rz` once its files are sent
+async fn end_session(link: &mut Link<'_>) -> Result<()> {
+    let fin = hex_header(Header::new(ZFIN, [0; 4]));
+    link.send(&fin).await?;
+    link.expect(&[ZFIN], &fin).await?;
+    link.send(b"OO").await
+}
+
+async fn read_block(file: &mut tokio::fs::File, block: &mut [u8]) -> Result<usize> {
+    let mut len = 0;
+    while len < block.len() {
+        let read = file.read(&mut block[len..]).await?;
+        if read == 0 {
+            break;
+        }
+        len += read;
+    }
+    Ok(len)
+}
+
+/// Save the files `sz` sends in `dir`
+async fn download(link: &mut Link<'_>, dir: &Path, files: &mut Files) -> Result<()> {
+    let zrinit = hex_header(Header::new(ZRINIT, [0, 0, 0, CANFDX | CANOVIO | CANFC32]));
+    link.send(&zrinit).await?;
+    // Kind of the last header, which says what a data subpacket is for
+    let mut last_header = ZRQINIT;
+    let mut timeouts = 0;
+    loop {
+        let Some(event) = link.recv().await? else {
+            timeouts += 1;
+            if timeouts > MAX_RETRIES {
+                return Err(timed_out());
+            }
+            link.send(&zrinit).await?;
+            continue;
+        };
+        timeouts = 0;
+        match event {
+            Event::Header(header) => {
+                last_header = header.kind;
+                match header.kind {
+                    ZRQINIT => link.send(&zrinit).await?,
+                    ZFIN => {
+                        return link.send(&hex_header(Header::new(ZFIN, [0; 4]))).await;
+                    }
+                    ZCOMMAND => {
+                        return Err(AppError::ZmodemError(
+                            "The host asked to run a command, which is not supported".to_string(),
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+            Event::Data { .. } if last_header == ZSINIT => {
+                link.send(&hex_header(Header::new(ZACK, [0; 4]))).await?;
+            }
+            Event::Data { data, .. } if last_header == ZFILE => {
+                receive_file(link, dir, &data, files).await?;
+                last_header = ZEOF;
+                link.send(&zrinit).await?;
+            }
+            Event::Corrupt => link.send(&hex_header(Header::new(ZNAK, [0; 4]))).await?,
+            _ => {}
+        }
+    }
+}
+
+/// Receive one file, described by the subpacket after its ZFILE header
+async fn receive_file(
+    link: &mut Link<'_>,
+    dir: &Path,
+    file_info: &[u8],
+    files: &mut Files,
+) -> Result<()> {
+    let (name, size) = parse_file_info(file_info);
+    let (path, mut file) = create_unique(dir, &name).await?;
+    let index = files
+        .add(ScpMode::Receive, path.display().to_string(), name, size)
+        .await;
+
+    let mut written = 0u64;
+    // Whether data subpackets continue from what was written
+    let mut in_sync = false;
+    let mut timeouts = 0;
+    link.send(&hex_header(Header::with_position(ZRPOS, 0)))
+        .await?;
+    loop {
+        let Some(event) = link.recv().await? else {
+            timeouts += 1;
+            if timeouts > MAX_RETRIES {
+                return Err(timed_out());
+            }
+            link.send(&hex_header(Header::with_position(ZRPOS, written)))
+                .await?;
+            continue;
+        };
+        timeouts = 0;
+        match event {
+            Event::Header(header) if header.kind == ZDATA => {
+                in_sync = header.position() == written;
+                if !in_sync {
+                    link.send(&hex_header(Header::with_position(ZRPOS, written)))
+                        .await?;
+                }
+            }
+            Event::Header(header) if header.kind == ZEOF && header.position() == written => {
+                file.flush().await?;
+                files.finish(index, None);
+                return Ok(());
+            }
+            Event::Data { data, end } if in_sync => {
+                file.write_all(&data).await?;
+                written += data.len() as u64;
+                files.update(index, written, size);
+                if end.wants_ack() {
+                    link.send(&hex_header(Header::with_position(ZACK, written)))
+                        .await?;
+                }
+                if end.ends_frame() {
+                    in_sync = false;
+                }
+            }
+            Event::Corrupt => {
+                in_sync = false;
+                link.send(&hex_header(Header::with_position(ZRPOS, written)))
+                    .await?;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Name and size from a ZFILE subpacket: "name\0size mtime mode ...\0".
+/// Only the last component of the name is kept.
+fn parse_file_info(file_info: &[u8]) -> (String, Option<u64>) {
+    let mut fields = file_info.split(|&byte| byte == 0);
+    let name = String::from_utf8_lossy(fields.next().unwrap_or_default());
+    let name = match name.rsplit(['/', '\\']).next() {
+        Some(name) if !name.is_empty() && name != "." && name != ".." => name.to_string(),
+        _ => "download".to_string(),
+    };
+    let size = fields
+        .next()
+        .and_then(|rest| std::str::from_utf8(rest).ok())
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|size| size.parse().ok());
+    (name, size)
+}
+
+/// Create `name` in `dir`, or "name (1)" and so on if it exists
+async fn create_unique(dir: &Path, name: &str) -> Result<(PathBuf, tokio::fs::File)> {
+    let original = Path::new(name);
+    let stem = original
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = original
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let mut copy = 0;
+    loop {
+        let path = if copy == 0 {
+            dir.join(name)
+        } else {
+            dir.join(format!("{stem} ({copy}){extension}"))
+        };
+        match tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+        {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => copy += 1,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+fn timed_out() -> AppError {
+    AppError::ZmodemError("The host stopped responding".to_string())
+}
+
+/// The session's output, decoded into ZMODEM events, and its input
+struct Link<'a> {
+    io: &'a mut TransferIo,
+    decoder: Decoder,
+    /// Output received but not decoded yet
+    pending: Vec<u8>,
+    encoder: Encoder,
+}
+
+impl<'a> Link<'a> {
+    fn new(io: &'a mut TransferIo) -> Self {
+        Self {
+            io,
+            decoder: Decoder::default(),
+            pending: Vec::new(),
+            encoder: Encoder::default(),
+        }
+    }
+
+    async fn send(&self, bytes: &[u8]) -> Result<()> {
+        self.io.output.write_all(bytes).await
+    }
+
+    fn decode(&mut self) -> Result<Option<Event>> {
+        let (used, event) = self.decoder.next(&self.pending);
+        self.pending.drain(..used);
+        match event {
+            Some(Event::Cancelled) => {
+                Err(AppError::ZmodemError("Cancelled by the host".to_string()))
+            }
+            event => Ok(event),
+        }
+    }
+
+    /// The next event, or `None` if the other side stayed quiet
+    async fn recv(&mut self) -> Result<Option<Event>> {
+        let deadline = Instant::now() + RECV_TIMEOUT;
+        loop {
+            if let Some(event) = self.decode()? {
+                return Ok(Some(event));
+            }
+            tokio::select! {
+                _ = self.io.cancel.cancelled() => {
+                    return Err(AppError::ZmodemError("Transfer cancelled".to_string()));
+                }
+                chunk = tokio::time::timeout_at(deadline, self.io.input.recv()) => match chunk {
+                    Ok(Some(chunk)) => self.pending.extend(chunk),
+                    Ok(None) => {
+                        return Err(AppError::ZmodemError("The session ended".to_string()));
+                    }
+                    Err(_) => return Ok(None),
+                },
+            }
+        }
+    }
+
+    /// An event that has already arrived, without waiting
+    fn poll(&mut self) -> Result<Option<Event>> {
+        if self.io.cancel.is_cancelled() {
+            return Err(AppError::ZmodemError("Transfer cancelled".to_string()));
+        }
+        while let Ok(chunk) = self.io.input.try_recv() {
+            self.pending.extend(chunk);
+        }
+        self.decode()
+    }
+
+    /// Wait for a header of one of `kinds`, sending `nudge` again each time
+    /// the other side goes quiet
+    async fn expect(&mut self, kinds: &[u8], nudge: &[u8]) -> Result<Header> {
+        let mut timeouts = 0;
+        loop {
+            match self.recv().await? {
+                Some(Event::Header(header)) if kinds.contains(&header.kind) => return Ok(header),
+                Some(_) => {}
+                None => {
+                    timeouts += 1;
+                    if timeouts > MAX_RETRIES {
+                        return Err(timed_out());
+                    }
+                    self.send(nudge).await?;
+                }
+            }
+        }
+    }
+
+    /// Output after a finished session. `sz` ends its session with "OO",
+    /// which is dropped.
+    async fn finish(mut self, over_and_out: bool) -> Vec<u8> {
+        let deadline = Instant::now() + OVER_AND_OUT_WAIT;
+        while over_and_out && trim_trailer(&self.pending).len() < 2 {
+            match tokio::time::timeout_at(deadline, self.io.input.recv()).await {
+                Ok(Some(chunk)) => self.pending.extend(chunk),
+                _ => break,
+            }
+        }
+        let rest = trim_trailer(&self.pending);
+        match rest.strip_prefix(b"OO") {
+            Some(after) if over_and_out => after.to_vec(),
+            _ => rest.to_vec(),
+        }
+    }
+
+    /// Stop the other side and skip the rest of its transfer. Returns the
+    /// text after its last ZMODEM bytes.
+    async fn abort(mut self) -> Vec<u8> {
+        if let Err(e) = self.send(ABORT).await {
+            warn!("Failed to cancel the ZMODEM transfer on the host: {}", e);
+        }
+        let limit = Instant::now() + DRAIN_LIMIT;
+        loop {
+            let quiet = (Instant::now() + DRAIN_QUIET).min(limit);
+            match tokio::time::timeout_at(quiet, self.io.input.recv()).await {
+                Ok(Some(chunk)) => self.pending.extend(chunk),
+                _ => break,
+            }
+        }
+        let Some(last) = self.pending.iter().rposition(|&byte| byte == ZDLE) else {
+            return self.pending;
+        };
+        let mut rest = &self.pending[last + 1..];
+        // The rest of a hex header: "B", then 14 hex digits
+        if rest.first() == Some(&b'B')
+            && rest.len() >= 15
+            && rest[1..15].iter().all(u8::is_ascii_hexdigit)
+        {
+            rest = &rest[15..];
+        }
+        trim_trailer(rest).to_vec()
+    }
+}
+
+/// Skip the line end and XON that follow a hex header
+fn trim_trailer(data: &[u8]) -> &[u8] {
+    let start = data
+        .iter()
+        .position(|byte| !matches!(byte, b'\r' | b'\n' | 0x8a | 0x8d | 0x11))
+        .unwrap_or(data.len());
+    &data[start..]
+}
+
+/// The transfer's files, as reported to the progress popup
+struct Files {
+    progress: mpsc::Sender<ScpResult>,
+    results: Vec<ScpFileResult>,
+}
+
+impl Files {
+    fn new(progress: mpsc::Sender<ScpResult>) -> Self {
+        Self {
+            progress,
+            results: Vec::new(),
+        }
+    }
+
+    async fn add(
+        &mut self,
+        mode: ScpMode,
+        local_path: String,
+        remote_path: String,
+        total_bytes: Option<u64>,
+    ) -> usize {
+        let display_name = Path::new(&local_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| remote_path.clone());
+        let file = ScpFileProgress {
+            local_path: local_path.clone(),
+            remote_path: remote_path.clone(),
+            display_name: display_name.clone(),
+            mode,
+            transferred_bytes: 0,
+            total_bytes,
+            state: TransferState::Pending,
+        };
+        let _ = self.progress.send(ScpResult::FileAdded(file)).await;
+        self.results.push(ScpFileResult {
+            mode,
+            local_path,
+            remote_path,
+            destination_filename: display_name,
+            success: false,
+            error: None,
+            completed_at: None,
+        });
+        self.results.len() - 1
+    }
+
+    fn update(&self, index: usize, transferred_bytes: u64, total_bytes: Option<u64>) {
+        // Dropped updates are made up for by the next one
+        let _ = self
+            .progress
+            .try_send(ScpResult::Progress(ScpTransferProgress {
+                file_index: index,
+                transferred_bytes,
+                total_bytes,
+            }));
+    }
+
+    fn finish(&mut self, index: usize, error: Option<String>) {
+        let result = &mut self.results[index];
+        result.success = error.is_none();
+        result.error = error;
+        result.completed_at = Some(std::time::Instant::now());
+    }
+
+    /// Report the outcome; files the transfer didn't get to have failed
+    async fn report(mut self, outcome: Result<()>) {
+        let message = match outcome {
+            Err(e) if self.results.is_empty() => ScpResult::Error {
+                error: e.to_string(),
+            },
+            outcome => {
+                let error = outcome
+                    .err()
+                    .map_or_else(|| "Not sent".to_string(), |e| e.to_string());
+                let now = std::time::Instant::now();
+                for result in self.results.iter_mut().filter(|r| r.completed_at.is_none()) {
+                    result.error = Some(error.clone());
+                    result.completed_at = Some(now);
+                }
+                ScpResult::Completed(self.results)
+            }
+        };
+        let _ = self.progress.send(message).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sends what it is given to the other side of a loopback
+    struct Pipe(mpsc::UnboundedSender<Vec<u8>>);
+
+    impl TerminalTransport for Pipe {
+        fn write_all<'a>(&'a self, data: &'a [u8]) -> futures::future::BoxFuture<'a, Result<()>> {
+            let _ = self.0.send(data.to_vec());
+            Box::pin(async { Ok(()) })
+        }
+
+        fn resize(&self, _cols: u16, _rows: u16) -> futures::future::BoxFuture<'_, Result<()>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn close(&self) -> futures::future::BoxFuture<'_, Result<()>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_download() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let target_dir = tempfile::tempdir().unwrap();
+        let source = source_dir.path().join("data.bin");
+        let contents: Vec<u8> = (0..5000u32).map(|i| (i * 7 % 256) as u8).collect();
+        std::fs::write(&source, &contents).unwrap();
+        std::fs::write(target_dir.path().join("data.bin"), b"existing").unwrap();
+
+        let (to_receiver, receiver_input) = mpsc::unbounded_channel();
+        let (to_sender, sender_input) = mpsc::unbounded_channel();
+        let mut sender = TransferIo {
+            input: sender_input,
+            output: Arc::new(Pipe(to_receiver.clone())),
+            cancel: CancellationToken::new(),
+        };
+        let mut receiver = TransferIo {
+            input: receiver_input,
+            output: Arc::new(Pipe(to_sender)),
+            cancel: CancellationToken::new(),
+        };
+        // `sz` starts the session, like the host would
+        to_receiver
+            .send(hex_header(Header::new(ZRQINIT, [0; 4])))
+            .unwrap();
+
+        let (sender_progress, mut sender_results) = mpsc::channel(1024);
+        let (receiver_progress, mut receiver_results) = mpsc::channel(1024);
+        let upload = run(Job::Upload(source), &mut sender, sender_progress);
+        let download = run(
+            Job::Download(target_dir.path().to_path_buf()),
+            &mut receiver,
+            receiver_progress,
+        );
+        let (_, leftover) = tokio::join!(upload, download);
+        assert!(leftover.is_empty());
+
+        for results in [&mut sender_results, &mut receiver_results] {
+            let mut completed = None;
+            while let Ok(result) = results.try_recv() {
+                if let ScpResult::Completed(files) = result {
+                    completed = Some(files);
+                }
+            }
+            let files = completed.unwrap();
+            assert_eq!(files.len(), 1);
+            assert!(files[0].success, "{:?}", files[0].error);
+        }
+        let saved = std::fs::read(target_dir.path().join("data (1).bin")).unwrap();
+        assert_eq!(saved, contents);
+    }
+}